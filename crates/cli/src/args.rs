@@ -1,5 +1,5 @@
 use anyhow::{Context, Error, Result};
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use std::{
     ffi::OsStr,
     net::SocketAddr,
@@ -38,8 +38,20 @@ impl FromStr for KeyValue {
 
 /// Simple program to greet a person
 #[derive(Parser, Debug)]
-#[clap(author, version, about, long_about = None, trailing_var_arg = true)]
+#[clap(
+    author,
+    version,
+    about,
+    long_about = None,
+    trailing_var_arg = true,
+    args_conflicts_with_subcommands = true,
+    subcommand_negates_reqs = true,
+)]
 pub struct Args {
+    /// The subcommand to run instead of executing the Wasm module.
+    #[clap(subcommand)]
+    command: Option<Command>,
+
     /// The host directory to pre-open for the `guest` to use.
     #[clap(
         long = "dir",
@@ -70,8 +82,9 @@ pub struct Args {
     #[clap(
         value_name = "MODULE",
         value_hint = clap::ValueHint::FilePath,
+        required = true,
     )]
-    wasm_file: PathBuf,
+    wasm_file: Option<PathBuf>,
 
     /// The function to invoke.
     ///
@@ -88,15 +101,91 @@ pub struct Args {
     #[clap(long = "fuel", value_name = "N")]
     fuel: Option<u64>,
 
+    /// Print every executed `wasmi` bytecode instruction to `stderr`.
+    ///
+    /// Each line shows the executed function, the instruction offset within
+    /// the function, the instruction and the top most values on the stack.
+    #[cfg(feature = "trace")]
+    #[clap(long = "trace")]
+    trace: bool,
+
+    /// Wait for a GDB or LLDB debugger to connect to the PORT on the local host.
+    ///
+    /// The execution is paused before its first instruction so that
+    /// breakpoints can be set before continuing the execution.
+    /// Linear memory can be written via hex encoded `M` packets
+    /// whereas binary `X` packets are unsupported and answered with an empty reply.
+    #[cfg(feature = "gdb")]
+    #[clap(long = "gdb-port", value_name = "PORT")]
+    gdb_port: Option<u16>,
+
+    /// Write the profile of the execution to FILE in the folded stacks format.
+    ///
+    /// The folded stacks can be turned into a flamegraph by tools such as `inferno`.
+    /// The functions with the highest costs are printed to `stderr` as well.
+    /// Costs are measured in nanoseconds of elapsed time.
+    #[cfg(feature = "profile")]
+    #[clap(
+        long = "profile",
+        value_name = "FILE",
+        value_hint = clap::ValueHint::FilePath,
+    )]
+    profile: Option<PathBuf>,
+
+    /// Sample the executed function every N executed instructions when profiling.
+    ///
+    /// Smaller intervals are more precise but slow down the execution.
+    #[cfg(feature = "profile")]
+    #[clap(
+        long = "profile-interval",
+        value_name = "N",
+        default_value_t = 1000,
+        requires = "profile",
+        value_parser = clap::value_parser!(u64).range(1..),
+    )]
+    profile_interval: u64,
+
     /// Arguments given to the Wasm module or the invoked function.
     #[clap(value_name = "ARGS")]
     func_args: Vec<String>,
 }
 
+/// The subcommands of the `wasmi` CLI app.
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Print the `wasmi` bytecode of all functions of the WebAssembly module.
+    ///
+    /// Each instruction is shown with its index and the offset of the Wasm
+    /// operator it originates from. Branch targets, constants and callees are resolved.
+    Disasm {
+        /// The file containing the WebAssembly module to disassemble.
+        #[clap(
+            value_name = "MODULE",
+            value_hint = clap::ValueHint::FilePath,
+        )]
+        wasm_file: PathBuf,
+
+        /// Include the instructions that are inserted for fuel metering.
+        #[clap(long = "fuel")]
+        fuel: bool,
+    },
+}
+
 impl Args {
+    /// Returns the subcommand given to the CLI app if any.
+    pub fn command(&self) -> Option<&Command> {
+        self.command.as_ref()
+    }
+
     /// Returns the Wasm file path given to the CLI app.
+    ///
+    /// # Panics
+    ///
+    /// If a subcommand was given to the CLI app.
     pub fn wasm_file(&self) -> &Path {
-        &self.wasm_file
+        self.wasm_file
+            .as_deref()
+            .unwrap_or_else(|| panic!("the Wasm file is required unless a subcommand is given"))
     }
 
     /// Returns the name of the invoked function if any.
@@ -114,6 +203,30 @@ impl Args {
         self.fuel
     }
 
+    /// Returns `true` if execution tracing was enabled via `--trace`.
+    #[cfg(feature = "trace")]
+    pub fn trace(&self) -> bool {
+        self.trace
+    }
+
+    /// Returns the port given via `--gdb-port` if any.
+    #[cfg(feature = "gdb")]
+    pub fn gdb_port(&self) -> Option<u16> {
+        self.gdb_port
+    }
+
+    /// Returns the file given via `--profile` if any.
+    #[cfg(feature = "profile")]
+    pub fn profile(&self) -> Option<&Path> {
+        self.profile.as_deref()
+    }
+
+    /// Returns the sampling interval given via `--profile-interval`.
+    #[cfg(feature = "profile")]
+    pub fn profile_interval(&self) -> u64 {
+        self.profile_interval
+    }
+
     /// Pre-opens all directories given in `--dir` and returns them for use by the [`WasiCtx`].
     ///
     /// # Errors
@@ -159,7 +272,7 @@ impl Args {
         // The WebAssembly filename is expected to be the first argument to WASI.
         // Note that the module name still has it's `.wasm` file extension.
         let module_name = self
-            .wasm_file
+            .wasm_file()
             .file_name()
             .and_then(OsStr::to_str)
             .unwrap_or("")
@@ -14,6 +14,9 @@ pub struct Context {
     store: Store<WasiCtx>,
     /// The Wasm module instance to operate on.
     instance: Instance,
+    /// The bytes of the given Wasm module.
+    #[cfg(feature = "gdb")]
+    wasm_bytes: Vec<u8>,
 }
 
 impl Context {
@@ -50,6 +53,8 @@ impl Context {
             module,
             store,
             instance,
+            #[cfg(feature = "gdb")]
+            wasm_bytes,
         })
     }
 
@@ -76,6 +81,18 @@ impl Context {
         &mut self.store
     }
 
+    /// Returns a shared reference to the [`Module`] and an exclusive reference to the [`Store`].
+    #[cfg(feature = "gdb")]
+    pub fn module_and_store_mut(&mut self) -> (&Module, &mut Store<WasiCtx>) {
+        (&self.module, &mut self.store)
+    }
+
+    /// Returns the bytes of the Wasm [`Module`].
+    #[cfg(feature = "gdb")]
+    pub fn wasm_bytes(&self) -> &[u8] {
+        &self.wasm_bytes
+    }
+
     /// Returns the exported function named `name` if any.
     pub fn get_func(&self, name: &str) -> Result<Func, Error> {
         self.instance
@@ -0,0 +1,863 @@
+//! A GDB remote serial protocol stub to debug the executed Wasm module with GDB or LLDB.
+//!
+//! # Note
+//!
+//! The stub follows the conventions of the LLDB Wasm plugin:
+//!
+//! - Code addresses are Wasm module offsets tagged with [`CODE_ADDRESS`].
+//! - Untagged addresses refer to the linear memory of the paused function.
+//! - Linear memory can be written via `M` packets whereas binary `X` writes are unsupported.
+//! - The `pc` is the only register.
+//! - Call stacks, locals, globals and operands are queried via the
+//!   `qWasmCallStack`, `qWasmLocal`, `qWasmGlobal` and `qWasmStackValue` packets.
+
+use crate::context::Context;
+use anyhow::{anyhow, bail, Context as _, Result};
+use std::{
+    fmt::Write as _,
+    io::{BufReader, Read, Write},
+    net::{TcpListener, TcpStream},
+};
+use wasmi::{
+    core::{UntypedValue, ValueType},
+    DebugCall,
+    Func,
+    Module,
+    PausedInvocation,
+    Value,
+};
+
+/// The address space tag of Wasm code addresses.
+const CODE_ADDRESS: u64 = 0x4000_0000_0000_0000;
+
+/// The mask of the address space tag and module identifier of addresses.
+const ADDRESS_SPACE_MASK: u64 = 0xFFFF_FFFF_0000_0000;
+
+/// The target triple reported to the debugger.
+const TRIPLE: &str = "wasm32-unknown-unknown-wasm";
+
+/// The name of the Wasm module reported to the debugger.
+const MODULE_NAME: &str = "module.wasm";
+
+/// The stop reply sent whenever the execution has been paused.
+const STOP_REPLY: &str = "T05thread:1;";
+
+/// The reply for erroneous requests.
+const ERROR_REPLY: &str = "E01";
+
+/// How the debugger wants to proceed with a paused execution.
+enum Resume {
+    /// Continue until the next breakpoint.
+    Continue,
+    /// Continue until the next Wasm operator.
+    Step,
+    /// Remove all breakpoints and continue until the execution has finished.
+    Detach,
+    /// Abort the execution.
+    Kill,
+}
+
+/// Runs `func` with `inputs` under the debugger attached to the `port` on the local host.
+///
+/// The results are written into `outputs` once the execution has finished.
+///
+/// # Note
+///
+/// Blocks until the debugger has connected and pauses the execution
+/// before its first instruction so that breakpoints can be set.
+///
+/// # Errors
+///
+/// - If the `port` cannot be listened on or the connection to the debugger fails.
+/// - If the execution traps or has been killed by the debugger.
+pub fn serve(
+    ctx: &mut Context,
+    port: u16,
+    func: Func,
+    inputs: &[Value],
+    outputs: &mut [Value],
+) -> Result<()> {
+    let listener = TcpListener::bind(("127.0.0.1", port))
+        .with_context(|| format!("failed to listen for the debugger on port {port}"))?;
+    eprintln!("waiting for the debugger to connect to 127.0.0.1:{port} ...");
+    let (stream, peer) = listener.accept()?;
+    eprintln!("debugger connected from {peer}");
+    let mut conn = Connection::new(stream)?;
+    ctx.store_mut().request_pause();
+    let mut call = func.call_debug(ctx.store_mut(), inputs, outputs)?;
+    let mut resumed = false;
+    loop {
+        let invocation = match call {
+            DebugCall::Finished => {
+                if resumed {
+                    conn.send("W00")?;
+                }
+                return Ok(());
+            }
+            DebugCall::Paused(invocation) => invocation,
+        };
+        if resumed {
+            conn.send(STOP_REPLY)?;
+        }
+        let resume = loop {
+            let packet = match conn.read_packet()? {
+                Some(packet) => packet,
+                None => bail!("the debugger closed the connection"),
+            };
+            if let Some(resume) = handle_packet(ctx, &mut conn, &invocation, &packet)? {
+                break resume;
+            }
+        };
+        let store = ctx.store_mut();
+        let result = match resume {
+            Resume::Continue => invocation.resume(store, outputs),
+            Resume::Step => invocation.step(store, outputs),
+            Resume::Detach => {
+                store.clear_breakpoints();
+                invocation.resume(store, outputs)
+            }
+            Resume::Kill => bail!("the execution has been killed by the debugger"),
+        };
+        call = match result {
+            Ok(call) => call,
+            Err(error) => {
+                // Note: We report the trap as a termination via `SIGTRAP`.
+                conn.send("X05")?;
+                return Err(error.into());
+            }
+        };
+        resumed = !matches!(resume, Resume::Detach);
+    }
+}
+
+/// Handles the `packet` of the debugger for the paused `invocation`.
+///
+/// Returns how to proceed with the execution if the `packet` resumes it.
+///
+/// # Errors
+///
+/// If the reply cannot be sent to the debugger.
+fn handle_packet<R: Read, W: Write>(
+    ctx: &mut Context,
+    conn: &mut Connection<R, W>,
+    invocation: &PausedInvocation,
+    packet: &str,
+) -> Result<Option<Resume>> {
+    let reply = match packet {
+        "?" => STOP_REPLY.into(),
+        "QStartNoAckMode" => {
+            conn.send("OK")?;
+            conn.no_ack = true;
+            return Ok(None);
+        }
+        "qHostInfo" => format!(
+            "vendor:wasmi;ostype:wasi;arch:wasm32;triple:{};endian:little;ptrsize:4;",
+            hex(TRIPLE.as_bytes())
+        ),
+        "qProcessInfo" => format!(
+            "pid:1;parent-pid:1;vendor:wasmi;ostype:wasi;arch:wasm32;triple:{};endian:little;ptrsize:4;",
+            hex(TRIPLE.as_bytes())
+        ),
+        "qC" => "QC1".into(),
+        "qfThreadInfo" => "m1".into(),
+        "qsThreadInfo" => "l".into(),
+        "qAttached" => "1".into(),
+        "qRegisterInfo0" => {
+            "name:pc;alt-name:pc;bitsize:64;offset:0;encoding:uint;format:hex;set:General Purpose Registers;gcc:16;dwarf:16;generic:pc;".into()
+        }
+        "g" => hex(&pc(invocation, 0).to_le_bytes()),
+        "vCont?" => "vCont;c;s".into(),
+        "c" | "vCont;c" => return Ok(Some(Resume::Continue)),
+        "s" | "vCont;s" => return Ok(Some(Resume::Step)),
+        "D" => {
+            conn.send("OK")?;
+            return Ok(Some(Resume::Detach));
+        }
+        "k" | "vKill;1" => return Ok(Some(Resume::Kill)),
+        _ => handle_query(ctx, invocation, packet).unwrap_or_else(|_| ERROR_REPLY.into()),
+    };
+    conn.send(&reply)?;
+    Ok(None)
+}
+
+/// Handles the `packet` queries of the debugger that carry arguments.
+///
+/// Returns an empty reply for unsupported packets.
+///
+/// # Errors
+///
+/// If the `packet` is malformed or refers to unavailable state.
+fn handle_query(ctx: &mut Context, invocation: &PausedInvocation, packet: &str) -> Result<String> {
+    if packet.starts_with("qSupported") {
+        return Ok("PacketSize=4000;QStartNoAckMode+;qXfer:libraries:read+".into());
+    }
+    if packet.starts_with('H') {
+        return Ok("OK".into());
+    }
+    if let Some(args) = packet.strip_prefix("qXfer:libraries:read::") {
+        let (offset, len) = split2(args, ',')?;
+        return Ok(read_chunk(
+            &libraries(),
+            parse_hex(offset)?,
+            parse_hex(len)?,
+        ));
+    }
+    if let Some(register) = packet.strip_prefix('p') {
+        let register = register.split(';').next().unwrap_or_default();
+        if parse_hex(register)? != 0 {
+            bail!("unknown register: {register}")
+        }
+        return Ok(hex(&pc(invocation, 0).to_le_bytes()));
+    }
+    if packet.starts_with("qRegisterInfo") {
+        bail!("unknown register info: {packet}")
+    }
+    if packet.starts_with("qWasmCallStack:") {
+        let mut bytes = Vec::new();
+        for depth in 0..invocation.frames().len() {
+            bytes.extend_from_slice(&pc(invocation, depth).to_le_bytes());
+        }
+        return Ok(hex(&bytes));
+    }
+    if let Some(args) = packet.strip_prefix("qWasmLocal:") {
+        let (depth, index) = frame_and_index(args)?;
+        let value = invocation
+            .locals(depth)
+            .and_then(|locals| locals.get(index))
+            .ok_or_else(|| anyhow!("missing local {index} in frame {depth}"))?;
+        return Ok(hex(&value.to_bits().to_le_bytes()));
+    }
+    if let Some(args) = packet.strip_prefix("qWasmStackValue:") {
+        let (depth, index) = frame_and_index(args)?;
+        let value = invocation
+            .operands(depth)
+            .and_then(|operands| operands.get(index))
+            .ok_or_else(|| anyhow!("missing operand {index} in frame {depth}"))?;
+        return Ok(hex(&value.to_bits().to_le_bytes()));
+    }
+    if let Some(args) = packet.strip_prefix("qWasmGlobal:") {
+        let (depth, index) = frame_and_index(args)?;
+        let store = ctx.store();
+        let global = invocation
+            .global(store, depth, index as u32)
+            .ok_or_else(|| anyhow!("missing global {index} in frame {depth}"))?;
+        return Ok(hex(&value_bytes(&global.get(store))));
+    }
+    if let Some(args) = packet.strip_prefix("qWasmMem:") {
+        let mut args = args.split(';');
+        let mut next = || {
+            args.next()
+                .ok_or_else(|| anyhow!("malformed qWasmMem packet: {packet}"))
+        };
+        let depth = parse_hex(next()?)? as usize;
+        let address = parse_hex(next()?)?;
+        let len = parse_hex(next()?)?;
+        return read_memory(ctx, invocation, depth, address, len);
+    }
+    if let Some(args) = packet.strip_prefix('m') {
+        let (address, len) = split2(args, ',')?;
+        let (address, len) = (parse_hex(address)?, parse_hex(len)?);
+        if address & ADDRESS_SPACE_MASK == CODE_ADDRESS {
+            let offset = address & !ADDRESS_SPACE_MASK;
+            return Ok(read_chunk_hex(ctx.wasm_bytes(), offset, len));
+        }
+        return read_memory(ctx, invocation, 0, address, len);
+    }
+    if let Some(args) = packet.strip_prefix('M') {
+        let (location, data) = split2(args, ':')?;
+        let (address, len) = split2(location, ',')?;
+        let data = parse_hex_bytes(data)?;
+        if parse_hex(len)? != data.len() as u64 {
+            bail!("mismatching length of memory write: {packet}")
+        }
+        write_memory(ctx, invocation, parse_hex(address)?, &data)?;
+        return Ok("OK".into());
+    }
+    if packet.starts_with('X') {
+        // Note: Binary memory writes are unsupported and the debugger
+        //       falls back to hex encoded `M` packets upon an empty reply.
+        return Ok(String::new());
+    }
+    if let Some(args) = packet.strip_prefix("Z0,") {
+        let (address, _kind) = split2(args, ',')?;
+        set_breakpoint(ctx, parse_hex(address)?)?;
+        return Ok("OK".into());
+    }
+    if let Some(args) = packet.strip_prefix("z0,") {
+        let (address, _kind) = split2(args, ',')?;
+        remove_breakpoint(ctx, parse_hex(address)?)?;
+        return Ok("OK".into());
+    }
+    Ok(String::new())
+}
+
+/// Returns the `pc` of the function frame at `depth` of the paused `invocation`.
+fn pc(invocation: &PausedInvocation, depth: usize) -> u64 {
+    let offset = invocation.frames()[depth].wasm_offset().unwrap_or_default();
+    CODE_ADDRESS | offset as u64
+}
+
+/// Sets a breakpoint at the code `address`.
+///
+/// # Errors
+///
+/// If no Wasm operator is located at the code `address`.
+fn set_breakpoint(ctx: &mut Context, address: u64) -> Result<()> {
+    let offset = code_offset(address)?;
+    let (module, store) = ctx.module_and_store_mut();
+    let func_index = func_index_at(module, address, offset)?;
+    store
+        .set_breakpoint(module, func_index, offset)
+        .map_err(|error| anyhow!("failed to set breakpoint at {address:#x}: {error}"))
+}
+
+/// Removes the breakpoint at the code `address`.
+///
+/// # Errors
+///
+/// If no breakpoint is set at the code `address`.
+fn remove_breakpoint(ctx: &mut Context, address: u64) -> Result<()> {
+    let offset = code_offset(address)?;
+    let (module, store) = ctx.module_and_store_mut();
+    let func_index = func_index_at(module, address, offset)?;
+    let removed = store
+        .remove_breakpoint(module, func_index, offset)
+        .map_err(|error| anyhow!("failed to remove breakpoint at {address:#x}: {error}"))?;
+    if !removed {
+        bail!("no breakpoint set at {address:#x}")
+    }
+    Ok(())
+}
+
+/// Returns the index of the function containing the Wasm module `offset` of the code `address`.
+///
+/// # Note
+///
+/// The GDB remote protocol only tells us the address of a breakpoint,
+/// therefore we look up the function whose body contains the Wasm operator.
+///
+/// # Errors
+///
+/// If no function body contains the `offset`.
+fn func_index_at(module: &Module, address: u64, offset: usize) -> Result<u32> {
+    module
+        .func_index_at_offset(offset)
+        .ok_or_else(|| anyhow!("no function located at {address:#x}"))
+}
+
+/// Returns the Wasm module offset of the code `address`.
+///
+/// # Errors
+///
+/// If `address` is no code address.
+fn code_offset(address: u64) -> Result<usize> {
+    if address & ADDRESS_SPACE_MASK != CODE_ADDRESS {
+        bail!("not a code address: {address:#x}")
+    }
+    Ok((address & !ADDRESS_SPACE_MASK) as usize)
+}
+
+/// Reads `len` bytes at `address` of the default linear memory of the frame at `depth`.
+///
+/// # Errors
+///
+/// If the frame has no linear memory or if `address` is out of bounds.
+fn read_memory(
+    ctx: &Context,
+    invocation: &PausedInvocation,
+    depth: usize,
+    address: u64,
+    len: u64,
+) -> Result<String> {
+    let store = ctx.store();
+    let memory = invocation
+        .memory(store, depth, 0)
+        .ok_or_else(|| anyhow!("missing linear memory in frame {depth}"))?;
+    let data = memory.data(store);
+    if address >= data.len() as u64 {
+        bail!("linear memory address out of bounds: {address:#x}")
+    }
+    Ok(read_chunk_hex(data, address, len))
+}
+
+/// Writes `data` at `address` of the default linear memory of the top most frame.
+///
+/// # Errors
+///
+/// - If `address` is a code address since Wasm code is immutable.
+/// - If the frame has no linear memory or if the written bytes are out of bounds.
+fn write_memory(
+    ctx: &mut Context,
+    invocation: &PausedInvocation,
+    address: u64,
+    data: &[u8],
+) -> Result<()> {
+    if address & ADDRESS_SPACE_MASK == CODE_ADDRESS {
+        bail!("cannot write to code address: {address:#x}")
+    }
+    let store = ctx.store_mut();
+    let memory = invocation
+        .memory(&*store, 0, 0)
+        .ok_or_else(|| anyhow!("missing linear memory in frame 0"))?;
+    let bytes = usize::try_from(address)
+        .ok()
+        .and_then(|start| {
+            memory
+                .data_mut(store)
+                .get_mut(start..)?
+                .get_mut(..data.len())
+        })
+        .ok_or_else(|| anyhow!("linear memory address out of bounds: {address:#x}"))?;
+    bytes.copy_from_slice(data);
+    Ok(())
+}
+
+/// Returns the library list of the debugged program in the XML format of GDB.
+fn libraries() -> String {
+    format!(
+        r#"<library-list><library name="{MODULE_NAME}"><section address="{CODE_ADDRESS:#x}"/></library></library-list>"#
+    )
+}
+
+/// Returns the `qXfer` reply for the chunk of `len` bytes at `offset` of `data`.
+fn read_chunk(data: &str, offset: u64, len: u64) -> String {
+    let start = (offset as usize).min(data.len());
+    let end = start.saturating_add(len as usize).min(data.len());
+    let marker = if end == data.len() { 'l' } else { 'm' };
+    format!("{marker}{}", &data[start..end])
+}
+
+/// Returns the hex encoded chunk of at most `len` bytes at `offset` of `data`.
+fn read_chunk_hex(data: &[u8], offset: u64, len: u64) -> String {
+    let start = (offset as usize).min(data.len());
+    let end = start.saturating_add(len as usize).min(data.len());
+    hex(&data[start..end])
+}
+
+/// Returns the little endian bytes of the `value` according to its type.
+fn value_bytes(value: &Value) -> Vec<u8> {
+    let bits = UntypedValue::from(value.clone()).to_bits().to_le_bytes();
+    match value.ty() {
+        ValueType::I32 | ValueType::F32 | ValueType::FuncRef | ValueType::ExternRef => {
+            bits[..4].to_vec()
+        }
+        ValueType::I64 | ValueType::F64 => bits.to_vec(),
+    }
+}
+
+/// Parses the `frame;index` arguments of the `qWasm` queries.
+///
+/// # Errors
+///
+/// If the arguments are malformed.
+fn frame_and_index(args: &str) -> Result<(usize, usize)> {
+    let (depth, index) = split2(args, ';')?;
+    Ok((parse_hex(depth)? as usize, parse_hex(index)? as usize))
+}
+
+/// Splits `args` at the first `delimiter`.
+///
+/// # Errors
+///
+/// If `args` does not contain the `delimiter`.
+fn split2(args: &str, delimiter: char) -> Result<(&str, &str)> {
+    args.split_once(delimiter)
+        .ok_or_else(|| anyhow!("malformed packet arguments: {args}"))
+}
+
+/// Parses the hex encoded number `s`.
+///
+/// # Errors
+///
+/// If `s` is not a valid hex encoded number.
+fn parse_hex(s: &str) -> Result<u64> {
+    u64::from_str_radix(s, 16).with_context(|| format!("invalid hex number: {s}"))
+}
+
+/// Parses the hex encoded bytes `s`.
+///
+/// # Errors
+///
+/// If `s` is not a valid hex encoding of bytes.
+fn parse_hex_bytes(s: &str) -> Result<Vec<u8>> {
+    s.as_bytes()
+        .chunks(2)
+        .map(|chunk| {
+            std::str::from_utf8(chunk)
+                .ok()
+                .filter(|digits| digits.len() == 2)
+                .and_then(|digits| u8::from_str_radix(digits, 16).ok())
+                .ok_or_else(|| anyhow!("invalid hex bytes: {s}"))
+        })
+        .collect()
+}
+
+/// Returns the lower case hex encoding of `bytes`.
+fn hex(bytes: &[u8]) -> String {
+    let mut hex = String::with_capacity(bytes.len() * 2);
+    for byte in bytes {
+        write!(hex, "{byte:02x}").expect("writing to a `String` never fails");
+    }
+    hex
+}
+
+/// Returns the modulo 256 checksum of the packet `data`.
+fn checksum(data: &[u8]) -> u8 {
+    data.iter().fold(0, |sum, byte| sum.wrapping_add(*byte))
+}
+
+/// Returns the packet `data` with all bytes escaped that have a special meaning in packets.
+fn escape(data: &[u8]) -> Vec<u8> {
+    let mut escaped = Vec::with_capacity(data.len());
+    for &byte in data {
+        match byte {
+            b'$' | b'#' | b'}' | b'*' => escaped.extend_from_slice(&[b'}', byte ^ 0x20]),
+            _ => escaped.push(byte),
+        }
+    }
+    escaped
+}
+
+/// Returns the packet `data` with all escaped bytes restored.
+fn unescape(data: &[u8]) -> Vec<u8> {
+    let mut unescaped = Vec::with_capacity(data.len());
+    let mut bytes = data.iter();
+    while let Some(&byte) = bytes.next() {
+        match byte {
+            b'}' => {
+                if let Some(&escaped) = bytes.next() {
+                    unescaped.push(escaped ^ 0x20);
+                }
+            }
+            _ => unescaped.push(byte),
+        }
+    }
+    unescaped
+}
+
+/// The connection to the debugger.
+struct Connection<R = BufReader<TcpStream>, W = TcpStream> {
+    /// The buffered reading end of the connection.
+    reader: R,
+    /// The writing end of the connection.
+    writer: W,
+    /// Whether packets are no longer acknowledged.
+    no_ack: bool,
+}
+
+impl Connection {
+    /// Creates a new [`Connection`] for the `stream`.
+    ///
+    /// # Errors
+    ///
+    /// If the `stream` cannot be cloned.
+    fn new(stream: TcpStream) -> Result<Self> {
+        stream.set_nodelay(true)?;
+        let writer = stream.try_clone()?;
+        Ok(Self {
+            reader: BufReader::new(stream),
+            writer,
+            no_ack: false,
+        })
+    }
+}
+
+impl<R: Read, W: Write> Connection<R, W> {
+    /// Reads the next byte from the debugger.
+    ///
+    /// Returns `None` if the debugger closed the connection.
+    fn read_byte(&mut self) -> Result<Option<u8>> {
+        let mut byte = [0x00];
+        match self.reader.read(&mut byte)? {
+            0 => Ok(None),
+            _ => Ok(Some(byte[0])),
+        }
+    }
+
+    /// Reads the next packet from the debugger.
+    ///
+    /// Returns `None` if the debugger closed the connection.
+    ///
+    /// # Note
+    ///
+    /// - Acknowledgements and interrupt requests are skipped
+    ///   since the execution is already paused while reading.
+    /// - Escaped bytes are restored and invalid UTF-8 is replaced
+    ///   so that unsupported binary packets can still be answered.
+    fn read_packet(&mut self) -> Result<Option<String>> {
+        loop {
+            match self.read_byte()? {
+                None => return Ok(None),
+                Some(b'$') => {}
+                Some(_) => continue,
+            }
+            let mut data = Vec::new();
+            loop {
+                match self.read_byte()? {
+                    None => return Ok(None),
+                    Some(b'#') => break,
+                    Some(byte) => data.push(byte),
+                }
+            }
+            let mut expected = [0x00; 2];
+            self.reader.read_exact(&mut expected)?;
+            let expected = std::str::from_utf8(&expected)
+                .ok()
+                .and_then(|expected| u8::from_str_radix(expected, 16).ok());
+            if expected != Some(checksum(&data)) {
+                if !self.no_ack {
+                    self.writer.write_all(b"-")?;
+                }
+                continue;
+            }
+            if !self.no_ack {
+                self.writer.write_all(b"+")?;
+            }
+            let data = unescape(&data);
+            return Ok(Some(String::from_utf8_lossy(&data).into_owned()));
+        }
+    }
+
+    /// Sends the packet `data` to the debugger.
+    ///
+    /// # Errors
+    ///
+    /// If writing to the connection failed.
+    fn send(&mut self, data: &str) -> Result<()> {
+        let data = escape(data.as_bytes());
+        let mut packet = Vec::with_capacity(data.len() + 4);
+        packet.push(b'$');
+        packet.extend_from_slice(&data);
+        write!(packet, "#{:02x}", checksum(&data))?;
+        self.writer.write_all(&packet)?;
+        self.writer.flush()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{io::Cursor, path::Path};
+    use wasmi_wasi::WasiCtxBuilder;
+
+    /// Returns a [`Connection`] that reads the `input` and records all written bytes.
+    fn connection(input: &[u8]) -> Connection<Cursor<Vec<u8>>, Vec<u8>> {
+        Connection {
+            reader: Cursor::new(input.to_vec()),
+            writer: Vec::new(),
+            no_ack: false,
+        }
+    }
+
+    /// Returns the [`Context`] of the `debug.wat` test module.
+    fn context() -> Context {
+        let wasm_file = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/wats/debug.wat");
+        Context::new(&wasm_file, WasiCtxBuilder::new().build(), None).unwrap()
+    }
+
+    /// Pauses the `add` function of the [`Context`] before its first instruction.
+    fn pause(ctx: &mut Context) -> PausedInvocation {
+        let func = ctx.get_func("add").unwrap();
+        ctx.store_mut().request_pause();
+        let mut outputs = [Value::I32(0)];
+        match func
+            .call_debug(
+                ctx.store_mut(),
+                &[Value::I32(1), Value::I32(2)],
+                &mut outputs,
+            )
+            .unwrap()
+        {
+            DebugCall::Paused(invocation) => invocation,
+            DebugCall::Finished => panic!("expected the execution to be paused"),
+        }
+    }
+
+    /// Sends the `packet` for the paused `invocation` and returns the framed reply.
+    fn request(ctx: &mut Context, invocation: &PausedInvocation, packet: &str) -> String {
+        let mut conn = connection(b"");
+        conn.no_ack = true;
+        let resume = handle_packet(ctx, &mut conn, invocation, packet).unwrap();
+        assert!(resume.is_none());
+        String::from_utf8(conn.writer).unwrap()
+    }
+
+    /// Returns the framed packet for `data`.
+    fn framed(data: &str) -> String {
+        format!("${data}#{:02x}", checksum(data.as_bytes()))
+    }
+
+    #[test]
+    fn read_packet_acknowledges_valid_checksum() {
+        let mut conn = connection(b"+$qC#b4");
+        assert_eq!(conn.read_packet().unwrap().as_deref(), Some("qC"));
+        assert_eq!(conn.writer, b"+");
+        assert_eq!(conn.read_packet().unwrap(), None);
+    }
+
+    #[test]
+    fn read_packet_rejects_invalid_checksum() {
+        let mut conn = connection(b"$qC#00$qC#b4");
+        assert_eq!(conn.read_packet().unwrap().as_deref(), Some("qC"));
+        assert_eq!(conn.writer, b"-+");
+    }
+
+    #[test]
+    fn read_packet_without_acks() {
+        let mut conn = connection(b"$qC#b4");
+        conn.no_ack = true;
+        assert_eq!(conn.read_packet().unwrap().as_deref(), Some("qC"));
+        assert!(conn.writer.is_empty());
+    }
+
+    #[test]
+    fn send_appends_checksum() {
+        let mut conn = connection(b"");
+        conn.send("OK").unwrap();
+        conn.send("").unwrap();
+        assert_eq!(conn.writer, b"$OK#9a$#00");
+    }
+
+    #[test]
+    fn escaping_roundtrip() {
+        let data = b"a$b#c}d*e";
+        let escaped = escape(data);
+        assert_eq!(escaped, b"a}\x04b}\x03c}]d}\x0ae");
+        assert_eq!(unescape(&escaped), data);
+    }
+
+    #[test]
+    fn send_and_read_escaped_packet() {
+        let mut sender = connection(b"");
+        sender.send("}#").unwrap();
+        assert_eq!(sender.writer, b"$}]}\x03#5a");
+        let mut receiver = connection(&sender.writer);
+        assert_eq!(receiver.read_packet().unwrap().as_deref(), Some("}#"));
+    }
+
+    #[test]
+    fn stop_reason() {
+        let mut ctx = context();
+        let invocation = pause(&mut ctx);
+        assert_eq!(request(&mut ctx, &invocation, "?"), framed(STOP_REPLY));
+    }
+
+    #[test]
+    fn malformed_packets() {
+        let mut ctx = context();
+        let invocation = pause(&mut ctx);
+        for packet in [
+            "m1000",
+            "mxyz,4",
+            "qWasmLocal:0",
+            "qWasmLocal:0;5",
+            "qWasmGlobal:0;1",
+            "Z0,zz,1",
+            "M0,2:01",
+        ] {
+            assert_eq!(
+                request(&mut ctx, &invocation, packet),
+                framed(ERROR_REPLY),
+                "packet: {packet}"
+            );
+        }
+    }
+
+    #[test]
+    fn unsupported_packets() {
+        let mut ctx = context();
+        let invocation = pause(&mut ctx);
+        assert_eq!(request(&mut ctx, &invocation, "X0,1:a"), framed(""));
+        assert_eq!(
+            request(&mut ctx, &invocation, "vMustReplyEmpty"),
+            framed("")
+        );
+    }
+
+    #[test]
+    fn wasm_queries() {
+        let mut ctx = context();
+        let invocation = pause(&mut ctx);
+        let pc = pc(&invocation, 0);
+        assert_eq!(pc & ADDRESS_SPACE_MASK, CODE_ADDRESS);
+        assert_eq!(
+            request(&mut ctx, &invocation, "qWasmCallStack:1"),
+            framed(&hex(&pc.to_le_bytes()))
+        );
+        assert_eq!(
+            request(&mut ctx, &invocation, "qWasmLocal:0;1"),
+            framed("0200000000000000")
+        );
+        assert_eq!(
+            request(&mut ctx, &invocation, "qWasmGlobal:0;0"),
+            framed("2a000000")
+        );
+        assert_eq!(
+            request(&mut ctx, &invocation, "qWasmMem:0;1;2"),
+            framed("0203")
+        );
+    }
+
+    #[test]
+    fn wasm_stack_value() {
+        let mut ctx = context();
+        let invocation = pause(&mut ctx);
+        let mut outputs = [Value::I32(0)];
+        let invocation = match invocation.step(ctx.store_mut(), &mut outputs).unwrap() {
+            DebugCall::Paused(invocation) => invocation,
+            DebugCall::Finished => panic!("expected the execution to be paused"),
+        };
+        assert_eq!(
+            request(&mut ctx, &invocation, "qWasmStackValue:0;0"),
+            framed("0100000000000000")
+        );
+    }
+
+    #[test]
+    fn read_and_write_memory() {
+        let mut ctx = context();
+        let invocation = pause(&mut ctx);
+        assert_eq!(request(&mut ctx, &invocation, "m0,4"), framed("01020304"));
+        assert_eq!(request(&mut ctx, &invocation, "M1,2:abcd"), framed("OK"));
+        assert_eq!(request(&mut ctx, &invocation, "m0,4"), framed("01abcd04"));
+        assert_eq!(
+            request(&mut ctx, &invocation, "M10000,1:ff"),
+            framed(ERROR_REPLY)
+        );
+    }
+
+    #[test]
+    fn read_code() {
+        let mut ctx = context();
+        let invocation = pause(&mut ctx);
+        let packet = format!("m{CODE_ADDRESS:x},4");
+        assert_eq!(request(&mut ctx, &invocation, &packet), framed("0061736d"));
+        let packet = format!("M{CODE_ADDRESS:x},1:00");
+        assert_eq!(request(&mut ctx, &invocation, &packet), framed(ERROR_REPLY));
+    }
+
+    #[test]
+    fn set_and_remove_breakpoints() {
+        let mut ctx = context();
+        let invocation = pause(&mut ctx);
+        let pc = pc(&invocation, 0);
+        let set = format!("Z0,{pc:x},1");
+        let remove = format!("z0,{pc:x},1");
+        assert_eq!(request(&mut ctx, &invocation, &set), framed("OK"));
+        assert_eq!(request(&mut ctx, &invocation, &remove), framed("OK"));
+        // The breakpoint has already been removed.
+        assert_eq!(request(&mut ctx, &invocation, &remove), framed(ERROR_REPLY));
+        // No Wasm operator is located at the start of the Wasm module.
+        let start = format!("Z0,{CODE_ADDRESS:x},1");
+        assert_eq!(request(&mut ctx, &invocation, &start), framed(ERROR_REPLY));
+        // No Wasm operator is located in the middle of `local.get`.
+        let inside = format!("Z0,{:x},1", pc + 1);
+        assert_eq!(request(&mut ctx, &invocation, &inside), framed(ERROR_REPLY));
+        // Linear memory addresses cannot have breakpoints.
+        assert_eq!(
+            request(&mut ctx, &invocation, "Z0,0,1"),
+            framed(ERROR_REPLY)
+        );
+    }
+}
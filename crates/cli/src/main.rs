@@ -1,16 +1,18 @@
 use crate::{
-    args::Args,
+    args::{Args, Command},
     display::{DisplayExportedFuncs, DisplayFuncType, DisplaySequence, DisplayValue},
 };
 use anyhow::{anyhow, bail, Error, Result};
 use clap::Parser;
 use context::Context;
 use std::{path::Path, process};
-use wasmi::{Func, FuncType, Value};
+use wasmi::{Config, Engine, Func, FuncType, Module, Value};
 
 mod args;
 mod context;
 mod display;
+#[cfg(feature = "gdb")]
+mod gdb;
 mod utils;
 
 #[cfg(test)]
@@ -18,9 +20,16 @@ mod tests;
 
 fn main() -> Result<()> {
     let args = Args::parse();
+    if let Some(command) = args.command() {
+        return run_command(command);
+    }
     let wasm_file = args.wasm_file();
     let wasi_ctx = args.wasi_context()?;
     let mut ctx = Context::new(wasm_file, wasi_ctx, args.fuel())?;
+    #[cfg(feature = "trace")]
+    if args.trace() {
+        ctx.store_mut().set_trace_hook(print_trace_event);
+    }
     let (func_name, func) = get_invoked_func(&args, &ctx)?;
     let ty = func.ty(ctx.store());
     let func_args = utils::decode_func_args(&ty, args.func_args())?;
@@ -37,7 +46,25 @@ fn main() -> Result<()> {
         )
     }
 
-    match func.call(ctx.store_mut(), &func_args, &mut func_results) {
+    #[cfg(feature = "gdb")]
+    if let Some(port) = args.gdb_port() {
+        gdb::serve(&mut ctx, port, func, &func_args, &mut func_results)
+            .map_err(|error| anyhow!("failed during debugging of {func_name}: {error}"))?;
+        print_remaining_fuel(&args, &ctx);
+        print_pretty_results(&func_results);
+        return Ok(());
+    }
+
+    #[cfg(feature = "profile")]
+    if args.profile().is_some() {
+        ctx.store_mut().start_profiling(args.profile_interval());
+    }
+    let result = func.call(ctx.store_mut(), &func_args, &mut func_results);
+    #[cfg(feature = "profile")]
+    if let Some(profile_file) = args.profile() {
+        write_profile(&mut ctx, profile_file)?;
+    }
+    match result {
         Ok(()) => {
             print_remaining_fuel(&args, &ctx);
             print_pretty_results(&func_results);
@@ -59,6 +86,27 @@ fn main() -> Result<()> {
     }
 }
 
+/// Runs the given subcommand of the `wasmi` CLI app.
+///
+/// # Errors
+///
+/// If the subcommand failed.
+fn run_command(command: &Command) -> Result<()> {
+    match command {
+        Command::Disasm { wasm_file, fuel } => {
+            let mut config = Config::default();
+            config.consume_fuel(*fuel);
+            let engine = Engine::new(&config);
+            let wasm_bytes = utils::read_wasm_or_wat(wasm_file)?;
+            let module = Module::new(&engine, &mut &wasm_bytes[..]).map_err(|error| {
+                anyhow!("failed to parse and validate Wasm module {wasm_file:?}: {error}")
+            })?;
+            print!("{}", module.disassemble());
+            Ok(())
+        }
+    }
+}
+
 /// Prints the remaining fuel so far if fuel metering was enabled.
 fn print_remaining_fuel(args: &Args, ctx: &Context) {
     if let Some(total_fuel) = args.fuel() {
@@ -70,6 +118,62 @@ fn print_remaining_fuel(args: &Args, ctx: &Context) {
     }
 }
 
+/// Prints the executed `wasmi` bytecode instruction of the [`TraceEvent`] to `stderr`.
+///
+/// [`TraceEvent`]: wasmi::TraceEvent
+#[cfg(feature = "trace")]
+fn print_trace_event(event: &wasmi::TraceEvent) {
+    /// The maximum number of top most stack values printed per instruction.
+    const MAX_STACK_VALUES: usize = 3;
+    let stack = event.stack();
+    let top = &stack[stack.len().saturating_sub(MAX_STACK_VALUES)..];
+    let func = event
+        .func_index()
+        .map_or_else(|| String::from("?"), |index| index.to_string());
+    let wasm_offset = event
+        .wasm_offset()
+        .map_or_else(|| String::from("-"), |offset| format!("{offset:#x}"));
+    eprintln!(
+        "func[{func}]@{wasm_offset:<8} {:?} stack: [{}]",
+        event.instr(),
+        DisplaySequence::new(
+            ", ",
+            top.iter().map(|value| format!("{:#x}", value.to_bits()))
+        ),
+    );
+}
+
+/// Writes the profile of the execution to `profile_file` and prints its most costly functions.
+///
+/// # Errors
+///
+/// If the `profile_file` cannot be written.
+#[cfg(feature = "profile")]
+fn write_profile(ctx: &mut Context, profile_file: &Path) -> Result<()> {
+    /// The maximum number of functions printed to `stderr`.
+    const MAX_FUNCS: usize = 10;
+    let profile = ctx
+        .store_mut()
+        .stop_profiling()
+        .unwrap_or_else(|| panic!("profiling is enabled but could not query the profile"));
+    std::fs::write(profile_file, profile.to_folded())
+        .map_err(|error| anyhow!("failed to write profile to {profile_file:?}: {error}"))?;
+    let total = profile.total().max(1) as f64;
+    eprintln!("{:>9} {:>9}  function", "self", "total");
+    for func in profile.funcs().iter().take(MAX_FUNCS) {
+        let name = match func.func_name() {
+            Some(name) => name.to_string(),
+            None => format!("func[{}]", func.func_index()),
+        };
+        eprintln!(
+            "{:>8.2}% {:>8.2}%  {name}",
+            func.exclusive() as f64 / total * 100.0,
+            func.inclusive() as f64 / total * 100.0,
+        );
+    }
+    Ok(())
+}
+
 /// Performs minor typecheck on the function signature.
 ///
 /// # Note
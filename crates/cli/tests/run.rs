@@ -30,6 +30,39 @@ fn test_proc_exit() {
     assert.failure().code(1);
 }
 
+#[test]
+fn test_disasm() {
+    let mut cmd = get_cmd();
+    let assert = cmd.arg("disasm").arg(get_bin_path("simple_print")).assert();
+    let assert = assert.success();
+    let output = assert.get_output();
+    let stdout = &output.stdout;
+    assert!(contains_slice(stdout, b"locals: "));
+    assert!(contains_slice(stdout, b"call func[0]"));
+    assert!(!contains_slice(stdout, b"Hello World"));
+}
+
+#[test]
+#[cfg(feature = "profile")]
+fn test_profile() {
+    let profile = std::env::temp_dir().join("wasmi_cli_test_profile.folded");
+    let mut cmd = get_cmd();
+    let assert = cmd
+        .arg("--profile")
+        .arg(&profile)
+        .arg(get_bin_path("simple_print"))
+        .assert();
+    let assert = assert.success();
+    let output = assert.get_output();
+    assert!(contains_slice(&output.stdout, b"Hello World"));
+    assert!(contains_slice(&output.stderr, b"function"));
+    let folded = std::fs::read_to_string(&profile).unwrap();
+    assert!(!folded.is_empty());
+    assert!(folded
+        .lines()
+        .all(|line| line.rsplit(' ').next().unwrap().parse::<u64>().is_ok()));
+}
+
 /// UTILS
 
 /// gets the path to a wasm binary given it's name
@@ -0,0 +1,11 @@
+(module
+    (memory 1)
+    (data (i32.const 0) "\01\02\03\04")
+    (global $counter (mut i32) (i32.const 42))
+
+    (func $add (export "add") (param $lhs i32) (param $rhs i32) (result i32)
+        local.get $lhs
+        local.get $rhs
+        i32.add
+    )
+)
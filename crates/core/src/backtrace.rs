@@ -0,0 +1,209 @@
+use alloc::{boxed::Box, vec::Vec};
+use core::fmt::{self, Display};
+
+/// The Wasm function frames that were active when a [`Trap`] occurred.
+///
+/// The frames are ordered from the innermost to the outermost frame.
+///
+/// [`Trap`]: crate::Trap
+#[derive(Debug, Default, Clone)]
+pub struct WasmBacktrace {
+    /// The frames of the [`WasmBacktrace`] from innermost to outermost.
+    frames: Vec<FrameInfo>,
+}
+
+impl WasmBacktrace {
+    /// Returns the [`FrameInfo`] of all frames from innermost to outermost.
+    pub fn frames(&self) -> &[FrameInfo] {
+        &self.frames[..]
+    }
+
+    /// Appends the `frames` to the [`WasmBacktrace`] as its outermost frames.
+    pub(crate) fn extend<I>(&mut self, frames: I)
+    where
+        I: IntoIterator<Item = FrameInfo>,
+    {
+        self.frames.extend(frames)
+    }
+}
+
+impl Display for WasmBacktrace {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "wasm backtrace:")?;
+        for (n, frame) in self.frames.iter().enumerate() {
+            write!(f, "\n  {n:>3}: {frame}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Information about a single Wasm function frame of a [`WasmBacktrace`].
+#[derive(Debug, Clone)]
+pub struct FrameInfo {
+    /// The index of the Wasm function within its module.
+    func_index: u32,
+    /// The name of the module of the Wasm function if known.
+    module_name: Option<Box<str>>,
+    /// The name of the Wasm function if known.
+    func_name: Option<Box<str>>,
+    /// The offset of the executed Wasm operator within the Wasm binary if known.
+    wasm_offset: Option<usize>,
+    /// The source level symbols of the executed Wasm operator from innermost to outermost.
+    symbols: Vec<FrameSymbol>,
+}
+
+impl FrameInfo {
+    /// Creates a new [`FrameInfo`] for the Wasm function at `func_index`.
+    #[doc(hidden)]
+    pub fn new(
+        func_index: u32,
+        module_name: Option<Box<str>>,
+        func_name: Option<Box<str>>,
+        wasm_offset: Option<usize>,
+    ) -> Self {
+        Self {
+            func_index,
+            module_name,
+            func_name,
+            wasm_offset,
+            symbols: Vec::new(),
+        }
+    }
+
+    /// Returns the [`FrameInfo`] with its Wasm bytecode offset replaced by `wasm_offset`.
+    #[doc(hidden)]
+    pub fn with_wasm_offset(mut self, wasm_offset: Option<usize>) -> Self {
+        self.wasm_offset = wasm_offset;
+        self
+    }
+
+    /// Returns the [`FrameInfo`] with its source level symbols replaced by `symbols`.
+    #[doc(hidden)]
+    pub fn with_symbols(mut self, symbols: Vec<FrameSymbol>) -> Self {
+        self.symbols = symbols;
+        self
+    }
+
+    /// Returns the index of the Wasm function within its module.
+    pub fn func_index(&self) -> u32 {
+        self.func_index
+    }
+
+    /// Returns the name of the module of the Wasm function if known.
+    pub fn module_name(&self) -> Option<&str> {
+        self.module_name.as_deref()
+    }
+
+    /// Returns the name of the Wasm function if known.
+    pub fn func_name(&self) -> Option<&str> {
+        self.func_name.as_deref()
+    }
+
+    /// Returns the offset of the executed Wasm operator within the Wasm binary if known.
+    ///
+    /// # Note
+    ///
+    /// For all but the innermost frame this is the offset of the call operator.
+    pub fn wasm_offset(&self) -> Option<usize> {
+        self.wasm_offset
+    }
+
+    /// Returns the source level symbols of the executed Wasm operator.
+    ///
+    /// # Note
+    ///
+    /// - The symbols are ordered from the innermost inlined function to
+    ///   the outermost function that contains the executed Wasm operator.
+    /// - The symbols are derived from the DWARF debug information of the
+    ///   module and are empty if it is unavailable.
+    pub fn symbols(&self) -> &[FrameSymbol] {
+        &self.symbols[..]
+    }
+}
+
+impl Display for FrameInfo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(offset) = self.wasm_offset {
+            write!(f, "{offset:#x} - ")?;
+        }
+        match self.module_name() {
+            Some(module_name) => write!(f, "{module_name}!")?,
+            None => write!(f, "<unknown>!")?,
+        }
+        match self.func_name() {
+            Some(func_name) => write!(f, "{func_name}")?,
+            None => write!(f, "<wasm function {}>", self.func_index)?,
+        }
+        for symbol in &self.symbols {
+            write!(f, "\n         {symbol}")?;
+        }
+        Ok(())
+    }
+}
+
+/// A source level symbol of a [`FrameInfo`] derived from DWARF debug information.
+#[derive(Debug, Clone)]
+pub struct FrameSymbol {
+    /// The demangled name of the source function if known.
+    name: Option<Box<str>>,
+    /// The path of the source file if known.
+    file: Option<Box<str>>,
+    /// The line within the source file if known.
+    line: Option<u32>,
+    /// The column within the source line if known.
+    column: Option<u32>,
+}
+
+impl FrameSymbol {
+    /// Creates a new [`FrameSymbol`].
+    #[doc(hidden)]
+    pub fn new(
+        name: Option<Box<str>>,
+        file: Option<Box<str>>,
+        line: Option<u32>,
+        column: Option<u32>,
+    ) -> Self {
+        Self {
+            name,
+            file,
+            line,
+            column,
+        }
+    }
+
+    /// Returns the demangled name of the source function if known.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Returns the path of the source file if known.
+    pub fn file(&self) -> Option<&str> {
+        self.file.as_deref()
+    }
+
+    /// Returns the line within the source file if known.
+    pub fn line(&self) -> Option<u32> {
+        self.line
+    }
+
+    /// Returns the column within the source line if known.
+    pub fn column(&self) -> Option<u32> {
+        self.column
+    }
+}
+
+impl Display for FrameSymbol {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(name) = self.name() {
+            write!(f, "{name} ")?;
+        }
+        write!(f, "at {}", self.file().unwrap_or("<unknown>"))?;
+        if let Some(line) = self.line {
+            write!(f, ":{line}")?;
+            if let Some(column) = self.column {
+                write!(f, ":{column}")?;
+            }
+        }
+        Ok(())
+    }
+}
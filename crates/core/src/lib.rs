@@ -11,6 +11,7 @@
     clippy::items_after_statements
 )]
 
+mod backtrace;
 mod host_error;
 mod nan_preserving_float;
 mod trap;
@@ -36,6 +37,7 @@ use self::value::{
     WrapInto,
 };
 pub use self::{
+    backtrace::{FrameInfo, FrameSymbol, WasmBacktrace},
     host_error::HostError,
     nan_preserving_float::{F32, F64},
     trap::{Trap, TrapCode},
@@ -1,4 +1,4 @@
-use crate::HostError;
+use crate::{FrameInfo, HostError, WasmBacktrace};
 use alloc::{boxed::Box, string::String};
 use core::fmt::{self, Display};
 
@@ -13,8 +13,8 @@ use std::error::Error as StdError;
 /// host embedder.
 #[derive(Debug)]
 pub struct Trap {
-    /// The cloneable reason of a [`Trap`].
-    reason: Box<TrapReason>,
+    /// The reason and backtrace of a [`Trap`].
+    inner: Box<TrapInner>,
 }
 
 #[test]
@@ -25,6 +25,15 @@ fn trap_size() {
     );
 }
 
+/// The reason and backtrace of a [`Trap`].
+#[derive(Debug)]
+struct TrapInner {
+    /// The reason of the [`Trap`].
+    reason: TrapReason,
+    /// The Wasm function frames that were active when the [`Trap`] occurred if any.
+    backtrace: Option<WasmBacktrace>,
+}
+
 /// The reason of a [`Trap`].
 #[derive(Debug)]
 enum TrapReason {
@@ -94,7 +103,10 @@ impl Trap {
     /// Create a new [`Trap`] from the [`TrapReason`].
     fn with_reason(reason: TrapReason) -> Self {
         Self {
-            reason: Box::new(reason),
+            inner: Box::new(TrapInner {
+                reason,
+                backtrace: None,
+            }),
         }
     }
 
@@ -115,7 +127,8 @@ impl Trap {
     where
         T: HostError,
     {
-        self.reason
+        self.inner
+            .reason
             .as_host()
             .and_then(<(dyn HostError + 'static)>::downcast_ref)
     }
@@ -128,7 +141,8 @@ impl Trap {
     where
         T: HostError,
     {
-        self.reason
+        self.inner
+            .reason
             .as_host_mut()
             .and_then(<(dyn HostError + 'static)>::downcast_mut)
     }
@@ -141,7 +155,8 @@ impl Trap {
     where
         T: HostError,
     {
-        self.reason
+        self.inner
+            .reason
             .into_host()
             .and_then(|error| error.downcast().ok())
             .map(|boxed| *boxed)
@@ -159,13 +174,38 @@ impl Trap {
     /// Otherwise returns `None`.
     #[inline]
     pub fn i32_exit_status(&self) -> Option<i32> {
-        self.reason.i32_exit_status()
+        self.inner.reason.i32_exit_status()
     }
 
     /// Returns the [`TrapCode`] traps originating from Wasm execution.
     #[inline]
     pub fn trap_code(&self) -> Option<TrapCode> {
-        self.reason.trap_code()
+        self.inner.reason.trap_code()
+    }
+
+    /// Returns the [`WasmBacktrace`] of the Wasm function frames that were active
+    /// when the [`Trap`] occurred if any.
+    #[inline]
+    pub fn backtrace(&self) -> Option<&WasmBacktrace> {
+        self.inner.backtrace.as_ref()
+    }
+
+    /// Appends the `frames` to the [`WasmBacktrace`] of the [`Trap`] as its outermost frames.
+    ///
+    /// # Note
+    ///
+    /// This is used by the executing engine to record the Wasm function frames
+    /// that were active while the [`Trap`] propagated through them.
+    #[doc(hidden)]
+    #[cold] // see Trap::new
+    pub fn extend_backtrace<I>(&mut self, frames: I)
+    where
+        I: IntoIterator<Item = FrameInfo>,
+    {
+        self.inner
+            .backtrace
+            .get_or_insert_with(WasmBacktrace::default)
+            .extend(frames)
     }
 }
 
@@ -200,7 +240,11 @@ impl Display for TrapReason {
 
 impl Display for Trap {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        <TrapReason as Display>::fmt(&self.reason, f)
+        <TrapReason as Display>::fmt(&self.inner.reason, f)?;
+        match self.backtrace() {
+            Some(backtrace) if !backtrace.frames().is_empty() => write!(f, "\n{backtrace}"),
+            _ => Ok(()),
+        }
     }
 }
 
@@ -291,6 +335,13 @@ pub enum TrapCode {
     /// desire on the part of the embedder to trap the interpreter rather than
     /// merely fail the growth operation.
     GrowthOperationLimited,
+
+    /// This trap is raised when a WebAssembly execution was interrupted.
+    ///
+    /// An execution is interrupted through the `wasmi::InterruptHandle` of the
+    /// `wasmi::Store` that executes it. This allows to cancel long running or
+    /// non-terminating executions from another thread without fuel metering.
+    Interrupted,
 }
 
 impl TrapCode {
@@ -313,6 +364,7 @@ impl TrapCode {
             Self::BadSignature => "indirect call type mismatch",
             Self::OutOfFuel => "all fuel consumed by WebAssembly",
             Self::GrowthOperationLimited => "growth operation limited",
+            Self::Interrupted => "execution interrupted",
         }
     }
 }
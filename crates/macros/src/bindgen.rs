@@ -0,0 +1,928 @@
+//! Expansion of the `bindgen` macro.
+//!
+//! Generates bindings for core Wasm modules following the canonical ABI of a WIT world.
+
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{format_ident, quote};
+use std::path::{Path, PathBuf};
+use syn::{
+    braced,
+    parse::{Parse, ParseStream},
+    Ident,
+    LitStr,
+    Token,
+};
+use wit_parser::{
+    Docs,
+    Function,
+    FunctionKind,
+    InterfaceId,
+    Resolve,
+    Type,
+    TypeDefKind,
+    WorldItem,
+    WorldKey,
+};
+
+/// The maximum number of flat parameters before the canonical ABI passes them indirectly.
+const MAX_FLAT_PARAMS: usize = 16;
+
+/// The configuration of a `bindgen` macro invocation.
+pub struct Config {
+    /// The WIT file or directory relative to the crate root.
+    path: Option<LitStr>,
+    /// The inline WIT source.
+    inline: Option<LitStr>,
+    /// The name of the selected world.
+    world: Option<LitStr>,
+}
+
+impl Parse for Config {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let content;
+        braced!(content in input);
+        let mut config = Config {
+            path: None,
+            inline: None,
+            world: None,
+        };
+        while !content.is_empty() {
+            let key = content.parse::<Ident>()?;
+            content.parse::<Token![:]>()?;
+            let value = content.parse::<LitStr>()?;
+            let option = match key.to_string().as_str() {
+                "path" => &mut config.path,
+                "inline" => &mut config.inline,
+                "world" => &mut config.world,
+                _ => {
+                    return Err(syn::Error::new(
+                        key.span(),
+                        "expected one of `path`, `inline` or `world`",
+                    ))
+                }
+            };
+            if option.replace(value).is_some() {
+                return Err(syn::Error::new(
+                    key.span(),
+                    format!("duplicate `{key}` option"),
+                ));
+            }
+            if content.is_empty() {
+                break;
+            }
+            content.parse::<Token![,]>()?;
+        }
+        Ok(config)
+    }
+}
+
+/// Expands the `bindgen` macro invocation with the given [`Config`].
+pub fn expand(config: &Config) -> syn::Result<TokenStream2> {
+    let mut resolve = Resolve::default();
+    let mut sources = Vec::new();
+    let package = match (&config.path, &config.inline) {
+        (Some(path), Some(_)) => {
+            return Err(syn::Error::new(
+                path.span(),
+                "the `path` and `inline` options are mutually exclusive",
+            ))
+        }
+        (None, Some(inline)) => resolve
+            .push_str("inline.wit", &inline.value())
+            .map_err(|error| syn::Error::new(inline.span(), format!("{error:#}")))?,
+        (path, None) => {
+            let span = path.as_ref().map_or_else(Span::call_site, LitStr::span);
+            let path = path
+                .as_ref()
+                .map_or_else(|| String::from("wit"), LitStr::value);
+            let root = std::env::var_os("CARGO_MANIFEST_DIR")
+                .map(PathBuf::from)
+                .unwrap_or_default();
+            let (package, source_map) = resolve
+                .push_path(root.join(path))
+                .map_err(|error| syn::Error::new(span, format!("{error:#}")))?;
+            sources.extend(source_map.paths().map(Path::to_path_buf));
+            package
+        }
+    };
+    let world = config.world.as_ref().map(LitStr::value);
+    let world = resolve
+        .select_world(&[package], world.as_deref())
+        .map_err(|error| {
+            let span = config
+                .world
+                .as_ref()
+                .map_or_else(Span::call_site, LitStr::span);
+            syn::Error::new(span, format!("{error:#}"))
+        })?;
+    let generator = Generator { resolve: &resolve };
+    let bindings = generator.world(&resolve.worlds[world])?;
+    let sources = sources.iter().map(|path| path.display().to_string());
+    Ok(quote! {
+        #( const _: &str = include_str!(#sources); )*
+        #bindings
+    })
+}
+
+/// A scalar WIT type.
+#[derive(Debug, Copy, Clone)]
+enum Scalar {
+    Bool,
+    U8,
+    S8,
+    U16,
+    S16,
+    U32,
+    S32,
+    U64,
+    S64,
+    F32,
+    F64,
+    Char,
+}
+
+impl Scalar {
+    /// Returns the Rust type of the [`Scalar`].
+    fn rust(self) -> TokenStream2 {
+        match self {
+            Self::Bool => quote! { bool },
+            Self::U8 => quote! { u8 },
+            Self::S8 => quote! { i8 },
+            Self::U16 => quote! { u16 },
+            Self::S16 => quote! { i16 },
+            Self::U32 => quote! { u32 },
+            Self::S32 => quote! { i32 },
+            Self::U64 => quote! { u64 },
+            Self::S64 => quote! { i64 },
+            Self::F32 => quote! { f32 },
+            Self::F64 => quote! { f64 },
+            Self::Char => quote! { char },
+        }
+    }
+
+    /// Returns the flat core Wasm type of the [`Scalar`].
+    fn flat(self) -> TokenStream2 {
+        match self {
+            Self::U64 | Self::S64 => quote! { i64 },
+            Self::F32 => quote! { ::wasmi::core::F32 },
+            Self::F64 => quote! { ::wasmi::core::F64 },
+            _ => quote! { i32 },
+        }
+    }
+
+    /// Converts the flat `value` into the Rust type of the [`Scalar`].
+    fn lift(self, value: &Ident) -> TokenStream2 {
+        match self {
+            Self::Bool => quote! { #value != 0 },
+            Self::S32 | Self::S64 => quote! { #value },
+            Self::F32 => quote! { ::wasmi::__private::lift_f32(#value) },
+            Self::F64 => quote! { ::wasmi::__private::lift_f64(#value) },
+            Self::Char => quote! { ::wasmi::__private::lift_char(#value)? },
+            _ => {
+                let rust = self.rust();
+                quote! { #value as #rust }
+            }
+        }
+    }
+
+    /// Converts the Rust `value` into the flat core Wasm type of the [`Scalar`].
+    fn lower(self, value: &Ident) -> TokenStream2 {
+        match self {
+            Self::Bool | Self::U8 | Self::S8 | Self::U16 | Self::S16 => {
+                quote! { i32::from(#value) }
+            }
+            Self::S32 | Self::S64 => quote! { #value },
+            Self::U32 | Self::Char => quote! { #value as i32 },
+            Self::U64 => quote! { #value as i64 },
+            Self::F32 => quote! { ::wasmi::__private::lower_f32(#value) },
+            Self::F64 => quote! { ::wasmi::__private::lower_f64(#value) },
+        }
+    }
+}
+
+/// A WIT type supported by the generated bindings.
+#[derive(Debug, Copy, Clone)]
+enum Ty {
+    /// A scalar passed as a single flat value.
+    Scalar(Scalar),
+    /// A `string` passed as a pointer and length into linear memory.
+    String,
+    /// A `list` of scalars passed as a pointer and length into linear memory.
+    List(Scalar),
+}
+
+impl Ty {
+    /// Returns the owned Rust type of the [`Ty`].
+    fn owned(self) -> TokenStream2 {
+        match self {
+            Self::Scalar(scalar) => scalar.rust(),
+            Self::String => quote! { ::wasmi::__private::String },
+            Self::List(scalar) => {
+                let rust = scalar.rust();
+                quote! { ::wasmi::__private::Vec<#rust> }
+            }
+        }
+    }
+
+    /// Returns the borrowed Rust type of the [`Ty`].
+    fn borrowed(self) -> TokenStream2 {
+        match self {
+            Self::Scalar(scalar) => scalar.rust(),
+            Self::String => quote! { &str },
+            Self::List(scalar) => {
+                let rust = scalar.rust();
+                quote! { &[#rust] }
+            }
+        }
+    }
+
+    /// Returns the number of flat core Wasm values of the [`Ty`].
+    fn len_flat(self) -> usize {
+        match self {
+            Self::Scalar(_) => 1,
+            Self::String | Self::List(_) => 2,
+        }
+    }
+}
+
+/// A freestanding WIT function with its supported parameter and result types.
+struct Func<'a> {
+    /// The WIT function.
+    func: &'a Function,
+    /// The Rust names and types of the parameters.
+    params: Vec<(Ident, Ty)>,
+    /// The result type if any.
+    result: Option<Ty>,
+}
+
+impl Func<'_> {
+    /// Returns the Rust name of the function.
+    fn name(&self) -> Ident {
+        snake_case(&self.func.name)
+    }
+
+    /// Returns the Rust type of the result.
+    fn result(&self) -> TokenStream2 {
+        match self.result {
+            Some(ty) => ty.owned(),
+            None => quote! { () },
+        }
+    }
+
+    /// Returns `true` if the function passes strings or lists through linear memory.
+    fn uses_memory(&self) -> bool {
+        self.params
+            .iter()
+            .map(|(_, ty)| ty)
+            .chain(&self.result)
+            .any(|ty| !matches!(ty, Ty::Scalar(_)))
+    }
+
+    /// Returns `true` if the result is passed through linear memory.
+    fn indirect_result(&self) -> bool {
+        matches!(self.result, Some(Ty::String | Ty::List(_)))
+    }
+
+    /// Returns the core Wasm type of the flat results of the core function export.
+    fn flat_export_results(&self) -> TokenStream2 {
+        match self.result {
+            Some(Ty::Scalar(scalar)) => scalar.flat(),
+            Some(_) => quote! { i32 },
+            None => quote! { () },
+        }
+    }
+}
+
+/// Generates the bindings of a WIT world.
+struct Generator<'a> {
+    /// The resolved WIT packages.
+    resolve: &'a Resolve,
+}
+
+impl Generator<'_> {
+    /// Returns the supported [`Ty`] of the WIT type.
+    fn ty(&self, ty: &Type) -> Option<Ty> {
+        let scalar = match ty {
+            Type::Bool => Scalar::Bool,
+            Type::U8 => Scalar::U8,
+            Type::S8 => Scalar::S8,
+            Type::U16 => Scalar::U16,
+            Type::S16 => Scalar::S16,
+            Type::U32 => Scalar::U32,
+            Type::S32 => Scalar::S32,
+            Type::U64 => Scalar::U64,
+            Type::S64 => Scalar::S64,
+            Type::F32 => Scalar::F32,
+            Type::F64 => Scalar::F64,
+            Type::Char => Scalar::Char,
+            Type::String => return Some(Ty::String),
+            Type::Id(id) => {
+                return match &self.resolve.types[*id].kind {
+                    TypeDefKind::Type(ty) => self.ty(ty),
+                    TypeDefKind::List(item) => match self.ty(item)? {
+                        Ty::Scalar(scalar) => Some(Ty::List(scalar)),
+                        _ => None,
+                    },
+                    _ => None,
+                }
+            }
+            Type::ErrorContext => return None,
+        };
+        Some(Ty::Scalar(scalar))
+    }
+
+    /// Returns the [`Func`] of the WIT function.
+    ///
+    /// # Errors
+    ///
+    /// If the function uses features not supported by the generated bindings.
+    fn func<'a>(&self, func: &'a Function) -> syn::Result<Func<'a>> {
+        let unsupported = |what: &str| {
+            syn::Error::new(
+                Span::call_site(),
+                format!("unsupported WIT function `{}`: {what}", func.name),
+            )
+        };
+        if !matches!(func.kind, FunctionKind::Freestanding) {
+            return Err(unsupported("only freestanding functions are supported"));
+        }
+        let ty = |ty: &Type| {
+            self.ty(ty).ok_or_else(|| {
+                unsupported("only scalars, `string` and lists of scalars are supported")
+            })
+        };
+        let params = func
+            .params
+            .iter()
+            .map(|(name, param)| Ok((snake_case(name), ty(param)?)))
+            .collect::<syn::Result<Vec<_>>>()?;
+        let result = func.result.as_ref().map(ty).transpose()?;
+        let len_flat = params.iter().map(|(_, ty)| ty.len_flat()).sum::<usize>();
+        if len_flat > MAX_FLAT_PARAMS {
+            return Err(unsupported("too many flat parameters"));
+        }
+        Ok(Func {
+            func,
+            params,
+            result,
+        })
+    }
+
+    /// Returns the Rust name of the interface imported or exported under `key`.
+    fn interface_name(&self, key: &WorldKey, id: InterfaceId) -> Ident {
+        match key {
+            WorldKey::Name(name) => snake_case(name),
+            WorldKey::Interface(_) => {
+                let name = self.resolve.interfaces[id].name.as_deref();
+                snake_case(name.unwrap_or("interface"))
+            }
+        }
+    }
+
+    /// Generates the bindings of the WIT `world`.
+    fn world(&self, world: &wit_parser::World) -> syn::Result<TokenStream2> {
+        let name = camel_case(&world.name);
+        let imports_trait = format_ident!("{}Imports", name);
+        let mut root_imports = Vec::new();
+        let mut interface_imports = Vec::new();
+        for (key, item) in &world.imports {
+            match item {
+                WorldItem::Function(func) => root_imports.push(self.func(func)?),
+                WorldItem::Interface { id, .. } => {
+                    interface_imports.push(self.import_interface(key, *id)?)
+                }
+                WorldItem::Type(_) => {}
+            }
+        }
+        let mut root_exports = Vec::new();
+        let mut interface_exports = Vec::new();
+        for (key, item) in &world.exports {
+            match item {
+                WorldItem::Function(func) => root_exports.push(self.func(func)?),
+                WorldItem::Interface { id, .. } => {
+                    interface_exports.push(self.export_interface(key, *id)?)
+                }
+                WorldItem::Type(_) => {}
+            }
+        }
+        let mut bounds = Vec::new();
+        let mut imports = Vec::new();
+        if !root_imports.is_empty() {
+            let methods = root_imports.iter().map(host_method);
+            let docs = docs(&Docs {
+                contents: Some(format!(
+                    "The host functions imported by the `{}` world.",
+                    world.name
+                )),
+            });
+            imports.push(quote! {
+                #docs
+                pub trait #imports_trait {
+                    #( #methods )*
+                }
+            });
+            bounds.push(quote! { #imports_trait });
+        }
+        let root_wrappers = root_imports
+            .iter()
+            .map(|func| import_wrapper("$root", &imports_trait, func));
+        let (interface_names, interface_imports): (Vec<_>, Vec<_>) =
+            interface_imports.into_iter().unzip();
+        bounds.extend(interface_names.iter().map(|name| quote! { #name::Host }));
+        let add_to_linker = match bounds.is_empty() {
+            true => quote! {},
+            false => quote! {
+                /// Adds all imports of the world to the `linker`.
+                ///
+                /// The `get` closure projects the host state to the host implementation.
+                ///
+                /// # Errors
+                ///
+                /// If any of the imports is already defined in the `linker`.
+                pub fn add_to_linker<T, U>(
+                    linker: &mut ::wasmi::Linker<T>,
+                    get: impl ::core::ops::Fn(&mut T) -> &mut U
+                        + ::core::marker::Send
+                        + ::core::marker::Sync
+                        + ::core::marker::Copy
+                        + 'static,
+                ) -> ::core::result::Result<(), ::wasmi::errors::LinkerError>
+                where
+                    U: #( #bounds )+*,
+                {
+                    #( #root_wrappers )*
+                    #( #interface_names::add_to_linker(linker, get)?; )*
+                    ::core::result::Result::Ok(())
+                }
+            },
+        };
+        let fields = root_exports.iter().map(export_fields);
+        let inits = root_exports
+            .iter()
+            .map(|func| export_init(&func.func.name, func));
+        let methods = root_exports.iter().map(export_method);
+        let (export_names, interface_exports): (Vec<_>, Vec<_>) =
+            interface_exports.into_iter().unzip();
+        let world_docs = docs(&world.docs);
+        Ok(quote! {
+            #( #imports )*
+            #( #interface_imports )*
+
+            /// The exports of instances of the world.
+            pub mod exports {
+                #( #interface_exports )*
+            }
+
+            #world_docs
+            #[derive(Debug, Copy, Clone)]
+            pub struct #name {
+                memory: ::core::option::Option<::wasmi::Memory>,
+                #[allow(dead_code)]
+                realloc: ::core::option::Option<::wasmi::__private::Realloc>,
+                #( #fields )*
+                #( #export_names: exports::#export_names::Guest, )*
+            }
+
+            impl #name {
+                #add_to_linker
+
+                /// Instantiates and starts the `module` via the `linker` and returns its bindings.
+                ///
+                /// # Errors
+                ///
+                /// - If the instantiation fails.
+                /// - If the exports of the instance do not match the world.
+                pub fn instantiate<T>(
+                    mut store: impl ::wasmi::AsContextMut<UserState = T>,
+                    module: &::wasmi::Module,
+                    linker: &::wasmi::Linker<T>,
+                ) -> ::core::result::Result<(Self, ::wasmi::Instance), ::wasmi::Error> {
+                    let instance = linker.instantiate(&mut store, module)?.start(&mut store)?;
+                    let bindings = Self::new(&store, &instance)?;
+                    ::core::result::Result::Ok((bindings, instance))
+                }
+
+                /// Creates the bindings to the exports of the `instance`.
+                ///
+                /// # Errors
+                ///
+                /// If the exports of the `instance` do not match the world.
+                pub fn new(
+                    store: impl ::wasmi::AsContext,
+                    instance: &::wasmi::Instance,
+                ) -> ::core::result::Result<Self, ::wasmi::Error> {
+                    let memory = instance.get_memory(&store, "memory");
+                    let realloc = ::wasmi::__private::optional_typed_func(&store, instance, "cabi_realloc")?;
+                    ::core::result::Result::Ok(Self {
+                        memory,
+                        realloc,
+                        #( #inits )*
+                        #( #export_names: exports::#export_names::Guest::new(&store, instance, memory, realloc)?, )*
+                    })
+                }
+
+                /// Returns the linear memory exported as `memory` if any.
+                pub fn memory(&self) -> ::core::option::Option<::wasmi::Memory> {
+                    self.memory
+                }
+
+                #( #methods )*
+
+                #(
+                    /// Returns the bindings of the exported interface.
+                    pub fn #export_names(&self) -> &exports::#export_names::Guest {
+                        &self.#export_names
+                    }
+                )*
+            }
+        })
+    }
+
+    /// Generates the host trait and `add_to_linker` function of an imported interface.
+    fn import_interface(
+        &self,
+        key: &WorldKey,
+        id: InterfaceId,
+    ) -> syn::Result<(Ident, TokenStream2)> {
+        let interface = &self.resolve.interfaces[id];
+        let name = self.interface_name(key, id);
+        let module = self.resolve.name_world_key(key);
+        let funcs = interface
+            .functions
+            .values()
+            .map(|func| self.func(func))
+            .collect::<syn::Result<Vec<_>>>()?;
+        let methods = funcs.iter().map(host_method);
+        let host = format_ident!("Host");
+        let wrappers = funcs
+            .iter()
+            .map(|func| import_wrapper(&module, &host, func));
+        let docs = docs(&interface.docs);
+        let bindings = quote! {
+            #docs
+            pub mod #name {
+                /// The host functions of the imported interface.
+                pub trait Host {
+                    #( #methods )*
+                }
+
+                /// Adds the host functions of the interface to the `linker`.
+                ///
+                /// The `get` closure projects the host state to the host implementation.
+                ///
+                /// # Errors
+                ///
+                /// If any of the host functions is already defined in the `linker`.
+                pub fn add_to_linker<T, U: Host>(
+                    linker: &mut ::wasmi::Linker<T>,
+                    get: impl ::core::ops::Fn(&mut T) -> &mut U
+                        + ::core::marker::Send
+                        + ::core::marker::Sync
+                        + ::core::marker::Copy
+                        + 'static,
+                ) -> ::core::result::Result<(), ::wasmi::errors::LinkerError> {
+                    #( #wrappers )*
+                    let _ = get;
+                    ::core::result::Result::Ok(())
+                }
+            }
+        };
+        Ok((name, bindings))
+    }
+
+    /// Generates the guest bindings of an exported interface.
+    fn export_interface(
+        &self,
+        key: &WorldKey,
+        id: InterfaceId,
+    ) -> syn::Result<(Ident, TokenStream2)> {
+        let interface = &self.resolve.interfaces[id];
+        let name = self.interface_name(key, id);
+        let prefix = self.resolve.name_world_key(key);
+        let funcs = interface
+            .functions
+            .values()
+            .map(|func| self.func(func))
+            .collect::<syn::Result<Vec<_>>>()?;
+        let fields = funcs.iter().map(export_fields);
+        let inits = funcs
+            .iter()
+            .map(|func| export_init(&format!("{prefix}#{}", func.func.name), func));
+        let methods = funcs.iter().map(export_method);
+        let docs = docs(&interface.docs);
+        let bindings = quote! {
+            #docs
+            pub mod #name {
+                /// The bindings of the functions of the exported interface.
+                #[derive(Debug, Copy, Clone)]
+                pub struct Guest {
+                    #[allow(dead_code)]
+                    memory: ::core::option::Option<::wasmi::Memory>,
+                    #[allow(dead_code)]
+                    realloc: ::core::option::Option<::wasmi::__private::Realloc>,
+                    #( #fields )*
+                }
+
+                impl Guest {
+                    /// Creates the bindings to the exports of the `instance`.
+                    pub(crate) fn new(
+                        store: impl ::wasmi::AsContext,
+                        instance: &::wasmi::Instance,
+                        memory: ::core::option::Option<::wasmi::Memory>,
+                        realloc: ::core::option::Option<::wasmi::__private::Realloc>,
+                    ) -> ::core::result::Result<Self, ::wasmi::Error> {
+                        ::core::result::Result::Ok(Self {
+                            memory,
+                            realloc,
+                            #( #inits )*
+                        })
+                    }
+
+                    #( #methods )*
+                }
+            }
+        };
+        Ok((name, bindings))
+    }
+}
+
+/// Generates the host trait method of the imported `func`.
+fn host_method(func: &Func) -> TokenStream2 {
+    let name = func.name();
+    let docs = docs(&func.func.docs);
+    let params = func.params.iter().map(|(name, ty)| {
+        let ty = ty.owned();
+        quote! { #name: #ty }
+    });
+    let result = func.result();
+    quote! {
+        #docs
+        fn #name(&mut self, #( #params ),*) -> ::core::result::Result<#result, ::wasmi::core::Trap>;
+    }
+}
+
+/// Generates the `linker.func_wrap` call defining the imported `func` of `module`.
+///
+/// The host function calls the method of `host` on the projected host state.
+fn import_wrapper(module: &str, host: &Ident, func: &Func) -> TokenStream2 {
+    let name = &func.func.name;
+    let method = func.name();
+    let mut inputs = Vec::new();
+    let mut lifts = Vec::new();
+    let mut args = Vec::new();
+    for (index, (_, ty)) in func.params.iter().enumerate() {
+        let arg = format_ident!("p{}", index);
+        match ty {
+            Ty::Scalar(scalar) => {
+                let flat = scalar.flat();
+                let lift = scalar.lift(&arg);
+                inputs.push(quote! { #arg: #flat });
+                lifts.push(quote! { let #arg = #lift; });
+            }
+            Ty::String | Ty::List(_) => {
+                let ptr = format_ident!("{}_ptr", arg);
+                let len = format_ident!("{}_len", arg);
+                let lift = match ty {
+                    Ty::String => quote! { ::wasmi::__private::lift_string },
+                    _ => quote! { ::wasmi::__private::lift_list },
+                };
+                inputs.push(quote! { #ptr: i32, #len: i32 });
+                lifts.push(quote! { let #arg = #lift(memory.data(&caller), #ptr, #len)?; });
+            }
+        }
+        args.push(arg);
+    }
+    if func.indirect_result() {
+        inputs.push(quote! { retptr: i32 });
+    }
+    let memory = match func.uses_memory() {
+        true => quote! { let memory = ::wasmi::__private::caller_memory(&caller)?; },
+        false => quote! {},
+    };
+    let (results, lower) = match func.result {
+        None => (quote! { () }, quote! { ::core::result::Result::Ok(result) }),
+        Some(Ty::Scalar(scalar)) => {
+            let result = format_ident!("result");
+            let lower = scalar.lower(&result);
+            (scalar.flat(), quote! { ::core::result::Result::Ok(#lower) })
+        }
+        Some(ty) => {
+            let lower = match ty {
+                Ty::String => quote! { ::wasmi::__private::lower_string },
+                _ => quote! { ::wasmi::__private::lower_list },
+            };
+            let lower = quote! {
+                let realloc = ::wasmi::__private::caller_realloc(&caller)?;
+                let pair = #lower(&mut caller, memory, &realloc, &result)?;
+                ::wasmi::__private::store_pair(memory.data_mut(&mut caller), retptr, pair)
+            };
+            (quote! { () }, lower)
+        }
+    };
+    quote! {
+        linker.func_wrap(
+            #module,
+            #name,
+            move |mut caller: ::wasmi::Caller<'_, T>, #( #inputs ),*|
+                -> ::core::result::Result<#results, ::wasmi::core::Trap> {
+                #memory
+                #( #lifts )*
+                let result = <U as #host>::#method(get(caller.data_mut()), #( #args ),*)?;
+                #lower
+            },
+        )?;
+    }
+}
+
+/// Returns the names of the fields holding the core function export of `func`
+/// and its optional post-return function.
+fn export_field_names(func: &Func) -> (Ident, Ident) {
+    let name = func.name();
+    (
+        format_ident!("func_{}", name),
+        format_ident!("post_{}", name),
+    )
+}
+
+/// Returns the Rust type of the flat parameters or results.
+fn flat_tuple(types: &[TokenStream2]) -> TokenStream2 {
+    match types {
+        [ty] => quote! { #ty },
+        types => quote! { ( #( #types ),* ) },
+    }
+}
+
+/// Returns the flat parameter types of the core function export of `func`.
+fn flat_export_params(func: &Func) -> TokenStream2 {
+    let types = func
+        .params
+        .iter()
+        .flat_map(|(_, ty)| match ty {
+            Ty::Scalar(scalar) => vec![scalar.flat()],
+            Ty::String | Ty::List(_) => vec![quote! { i32 }, quote! { i32 }],
+        })
+        .collect::<Vec<_>>();
+    flat_tuple(&types)
+}
+
+/// Generates the fields of the bindings to the exported `func`.
+fn export_fields(func: &Func) -> TokenStream2 {
+    let (func_field, post_field) = export_field_names(func);
+    let params = flat_export_params(func);
+    let results = func.flat_export_results();
+    quote! {
+        #func_field: ::wasmi::TypedFunc<#params, #results>,
+        #post_field: ::core::option::Option<::wasmi::TypedFunc<#results, ()>>,
+    }
+}
+
+/// Generates the field initializers of the bindings to the `func` exported as `export`.
+fn export_init(export: &str, func: &Func) -> TokenStream2 {
+    let (func_field, post_field) = export_field_names(func);
+    let post = format!("cabi_post_{export}");
+    quote! {
+        #func_field: instance.get_typed_func(&store, #export)?,
+        #post_field: ::wasmi::__private::optional_typed_func(&store, instance, #post)?,
+    }
+}
+
+/// Generates the method calling the exported `func`.
+fn export_method(func: &Func) -> TokenStream2 {
+    let (func_field, post_field) = export_field_names(func);
+    let method = format_ident!("call_{}", func.name());
+    // Hygienic so that it does not clash with parameters named `store`.
+    let store = Ident::new("store", Span::mixed_site());
+    let docs = docs(&func.func.docs);
+    let params = func.params.iter().map(|(name, ty)| {
+        let ty = ty.borrowed();
+        quote! { #name: #ty }
+    });
+    let names = func.params.iter().map(|(name, _)| name);
+    let mut args = Vec::new();
+    let mut lowers = Vec::new();
+    let mut flat = Vec::new();
+    for (index, (_, ty)) in func.params.iter().enumerate() {
+        let arg = format_ident!("p{}", index);
+        match ty {
+            Ty::Scalar(scalar) => {
+                let lower = scalar.lower(&arg);
+                lowers.push(quote! { let #arg = #lower; });
+                flat.push(quote! { #arg });
+            }
+            Ty::String | Ty::List(_) => {
+                let ptr = format_ident!("{}_ptr", arg);
+                let len = format_ident!("{}_len", arg);
+                let lower = match ty {
+                    Ty::String => quote! { ::wasmi::__private::lower_string },
+                    _ => quote! { ::wasmi::__private::lower_list },
+                };
+                lowers.push(quote! {
+                    let (#ptr, #len) = #lower(&mut #store, memory, &realloc, #arg)?;
+                });
+                flat.push(quote! { #ptr });
+                flat.push(quote! { #len });
+            }
+        }
+        args.push(arg);
+    }
+    let bind = match args.is_empty() {
+        true => quote! {},
+        false => quote! { let ( #( #args, )* ) = ( #( #names, )* ); },
+    };
+    let flat = flat_tuple(&flat);
+    let lowers_memory = func
+        .params
+        .iter()
+        .any(|(_, ty)| !matches!(ty, Ty::Scalar(_)));
+    let memory = match (func.uses_memory(), lowers_memory) {
+        (true, true) => quote! {
+            let memory = ::wasmi::__private::required(self.memory, "memory")?;
+            let realloc = ::wasmi::__private::required(self.realloc, "cabi_realloc")?;
+        },
+        (true, false) => quote! {
+            let memory = ::wasmi::__private::required(self.memory, "memory")?;
+        },
+        (false, _) => quote! {},
+    };
+    let result = func.result();
+    let call = match func.result {
+        None => quote! {
+            self.#func_field.call(&mut #store, #flat)?;
+            if let ::core::option::Option::Some(post) = self.#post_field {
+                post.call(&mut #store, ())?;
+            }
+            ::core::result::Result::Ok(())
+        },
+        Some(ty) => {
+            let results = format_ident!("results");
+            let lift = match ty {
+                Ty::Scalar(scalar) => scalar.lift(&results),
+                Ty::String => quote! {{
+                    let (ptr, len) = ::wasmi::__private::load_pair(memory.data(&#store), results)?;
+                    ::wasmi::__private::lift_string(memory.data(&#store), ptr, len)?
+                }},
+                Ty::List(_) => quote! {{
+                    let (ptr, len) = ::wasmi::__private::load_pair(memory.data(&#store), results)?;
+                    ::wasmi::__private::lift_list(memory.data(&#store), ptr, len)?
+                }},
+            };
+            quote! {
+                let results = self.#func_field.call(&mut #store, #flat)?;
+                let result = #lift;
+                if let ::core::option::Option::Some(post) = self.#post_field {
+                    post.call(&mut #store, results)?;
+                }
+                ::core::result::Result::Ok(result)
+            }
+        }
+    };
+    quote! {
+        #docs
+        pub fn #method(
+            &self,
+            mut #store: impl ::wasmi::AsContextMut,
+            #( #params ),*
+        ) -> ::core::result::Result<#result, ::wasmi::core::Trap> {
+            #bind
+            #memory
+            #( #lowers )*
+            #call
+        }
+    }
+}
+
+/// Generates the doc attributes of the WIT `docs`.
+fn docs(docs: &Docs) -> TokenStream2 {
+    let lines = docs
+        .contents
+        .iter()
+        .flat_map(|contents| contents.lines())
+        .map(|line| format!(" {line}"));
+    quote! { #( #[doc = #lines] )* }
+}
+
+/// Converts the kebab-case WIT `name` into a snake-case Rust identifier.
+///
+/// Rust keywords are suffixed with an underscore.
+fn snake_case(name: &str) -> Ident {
+    let name = name.replace('-', "_");
+    match syn::parse_str::<Ident>(&name) {
+        Ok(ident) => ident,
+        Err(_) => format_ident!("{}_", name),
+    }
+}
+
+/// Converts the kebab-case WIT `name` into a camel-case Rust identifier.
+fn camel_case(name: &str) -> Ident {
+    let name = name
+        .split('-')
+        .map(|word| {
+            let mut chars = word.chars();
+            chars
+                .next()
+                .map(|first| first.to_uppercase().chain(chars).collect::<String>())
+                .unwrap_or_default()
+        })
+        .collect::<String>();
+    format_ident!("{}", name)
+}
@@ -0,0 +1,430 @@
+//! Procedural macros for the `wasmi` interpreter.
+//!
+//! Use these through the re-exports of the `wasmi` crate with its `macros` crate feature enabled.
+
+#[cfg(feature = "bindgen")]
+mod bindgen;
+mod wasm_type;
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{format_ident, quote};
+use syn::{
+    parse_macro_input,
+    spanned::Spanned,
+    DeriveInput,
+    FnArg,
+    Ident,
+    ImplItem,
+    ImplItemFn,
+    ItemImpl,
+    LitStr,
+    Pat,
+    ReturnType,
+    Type,
+};
+
+/// Generates a host module from the associated functions of an `impl` block.
+///
+/// Emits the `impl` block unchanged together with an `add_to_linker` associated function
+/// that defines every associated function as a host function named after it in the
+/// host module named by the attribute argument.
+///
+/// The generated `add_to_linker` takes the [`Linker`] and a `get` closure that
+/// projects the host state of the [`Store`] to the type of the `impl` block.
+/// Methods taking `&self` or `&mut self` are called on the projected host state.
+///
+/// # Parameters
+///
+/// - Parameters of type `&str`, `&[u8]` or `&mut [u8]` are each passed as a pair of
+///   `i32` pointer and length parameters by the Wasm caller and resolved to a slice
+///   of the linear memory exported as `memory` by the calling instance.
+///   Out of bounds slices trap with [`TrapCode::MemoryOutOfBounds`] and
+///   invalid UTF-8 strings trap with a message.
+/// - All other parameters must implement [`WasmType`] and are passed as is.
+///
+/// # Results
+///
+/// If the return type is a `Result` its error type must be [`Trap`] and the result
+/// is returned as is. Otherwise the returned value is returned as the result.
+///
+/// # Example
+///
+/// ```ignore
+/// struct Host {
+///     log: Vec<String>,
+/// }
+///
+/// #[wasmi::host_module("env")]
+/// impl Host {
+///     fn log(&mut self, message: &str) {
+///         self.log.push(message.into());
+///     }
+///
+///     fn add(a: i32, b: i32) -> i32 {
+///         a + b
+///     }
+/// }
+///
+/// struct State {
+///     host: Host,
+/// }
+///
+/// let mut linker = <wasmi::Linker<State>>::new(&engine);
+/// Host::add_to_linker(&mut linker, |state| &mut state.host)?;
+/// ```
+///
+/// # Note
+///
+/// - The `impl` block must not be generic and its associated functions must be neither
+///   generic nor `async`.
+/// - At most one `&mut [u8]` parameter is allowed and it cannot be
+///   combined with other slice parameters.
+///
+/// [`Linker`]: https://docs.rs/wasmi/latest/wasmi/struct.Linker.html
+/// [`Store`]: https://docs.rs/wasmi/latest/wasmi/struct.Store.html
+/// [`WasmType`]: https://docs.rs/wasmi/latest/wasmi/trait.WasmType.html
+/// [`Trap`]: https://docs.rs/wasmi/latest/wasmi/core/struct.Trap.html
+/// [`TrapCode::MemoryOutOfBounds`]: https://docs.rs/wasmi/latest/wasmi/core/enum.TrapCode.html
+#[proc_macro_attribute]
+pub fn host_module(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let module = parse_macro_input!(attr as LitStr);
+    let item = parse_macro_input!(item as ItemImpl);
+    match expand_host_module(&module, &item) {
+        Ok(expanded) => expanded.into(),
+        Err(error) => {
+            let error = error.to_compile_error();
+            quote! {
+                #item
+                #error
+            }
+            .into()
+        }
+    }
+}
+
+/// Generates bindings for core Wasm modules from a WIT world.
+///
+/// The WIT world is selected from the WIT file or directory at `path` relative to the
+/// crate root, which defaults to `wit`, or from the `inline` WIT source. The `world`
+/// option is only required if the WIT package defines multiple worlds.
+///
+/// Generates the following items for a world named `my-world`:
+///
+/// - A `MyWorldImports` trait with the functions imported directly by the world.
+/// - A module per imported interface with a `Host` trait and an `add_to_linker` function.
+/// - An `exports` module with a module per exported interface and its `Guest` bindings.
+/// - A `MyWorld` struct with an `add_to_linker` function defining all imports
+///   and `instantiate` and `new` functions to create the bindings to the exports of
+///   an instance. Its `call_<name>` methods call the functions exported by the world.
+///
+/// The bindings follow the component model canonical ABI of core Wasm modules.
+/// Strings and lists are passed through the linear memory exported as `memory`
+/// and allocated in it via the exported `cabi_realloc` function.
+/// The `cabi_post_<name>` functions are called after the results have been read if exported.
+///
+/// # Note
+///
+/// Only freestanding functions with scalar, `string` and `list` of scalar
+/// parameters and results are supported. The generation fails for other WIT items.
+///
+/// # Example
+///
+/// ```ignore
+/// wasmi::bindgen!({
+///     inline: "
+///         package example:greeter;
+///
+///         world greeter {
+///             import log: func(message: string);
+///             export greet: func(name: string) -> string;
+///         }
+///     ",
+/// });
+///
+/// struct Host;
+///
+/// impl GreeterImports for Host {
+///     fn log(&mut self, message: String) -> Result<(), wasmi::core::Trap> {
+///         println!("{message}");
+///         Ok(())
+///     }
+/// }
+///
+/// let mut linker = <wasmi::Linker<Host>>::new(&engine);
+/// Greeter::add_to_linker(&mut linker, |host| host)?;
+/// let (greeter, _instance) = Greeter::instantiate(&mut store, &module, &linker)?;
+/// let greeting = greeter.call_greet(&mut store, "wasmi")?;
+/// ```
+#[cfg(feature = "bindgen")]
+#[proc_macro]
+pub fn bindgen(input: TokenStream) -> TokenStream {
+    let config = parse_macro_input!(input as bindgen::Config);
+    match bindgen::expand(&config) {
+        Ok(expanded) => expanded.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+/// Derives `WasmType` for a `#[repr(transparent)]` newtype of another [`WasmType`] type.
+///
+/// The newtype uses the Wasm value type of its field so that it can be used directly
+/// as parameter or result of host functions and [`TypedFunc`] signatures.
+///
+/// # Example
+///
+/// ```ignore
+/// #[derive(wasmi::WasmType)]
+/// #[repr(transparent)]
+/// struct Handle(u32);
+///
+/// let close = wasmi::Func::wrap(&mut store, |handle: Handle| handle.0 != 0);
+/// ```
+///
+/// [`WasmType`]: https://docs.rs/wasmi/latest/wasmi/trait.WasmType.html
+/// [`TypedFunc`]: https://docs.rs/wasmi/latest/wasmi/struct.TypedFunc.html
+#[proc_macro_derive(WasmType)]
+pub fn derive_wasm_type(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match wasm_type::expand(&input) {
+        Ok(expanded) => expanded.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+/// Expands the `impl` block annotated by `#[host_module(module)]`.
+fn expand_host_module(module: &LitStr, item: &ItemImpl) -> syn::Result<TokenStream2> {
+    if !item.generics.params.is_empty() {
+        return Err(syn::Error::new(
+            item.generics.span(),
+            "host modules cannot be generic",
+        ));
+    }
+    if let Some((_, trait_, _)) = &item.trait_ {
+        return Err(syn::Error::new(
+            trait_.span(),
+            "host modules must be inherent `impl` blocks",
+        ));
+    }
+    let self_ty = &item.self_ty;
+    let linker = hygienic("linker");
+    let get = hygienic("get");
+    let funcs = item
+        .items
+        .iter()
+        .filter_map(|item| match item {
+            ImplItem::Fn(func) => Some(expand_host_func(module, func)),
+            _ => None,
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+    Ok(quote! {
+        #item
+
+        impl #self_ty {
+            /// Adds all host functions of the host module to the `linker`.
+            ///
+            /// The `get` closure projects the host state to the host module.
+            ///
+            /// # Errors
+            ///
+            /// If any of the host functions is already defined in the `linker`.
+            pub fn add_to_linker<T>(
+                #linker: &mut ::wasmi::Linker<T>,
+                #get: impl ::core::ops::Fn(&mut T) -> &mut Self
+                    + ::core::marker::Send
+                    + ::core::marker::Sync
+                    + ::core::marker::Copy
+                    + 'static,
+            ) -> ::core::result::Result<(), ::wasmi::errors::LinkerError> {
+                #( #funcs )*
+                let _ = #get;
+                ::core::result::Result::Ok(())
+            }
+        }
+    })
+}
+
+/// The kind of a parameter of a host function.
+enum Param {
+    /// A parameter passed as is.
+    Value(Ident, Box<Type>),
+    /// A `&str` parameter.
+    Str(Ident),
+    /// A `&[u8]` parameter.
+    Bytes(Ident),
+    /// A `&mut [u8]` parameter.
+    BytesMut(Ident),
+}
+
+impl Param {
+    /// Creates the [`Param`] of the typed function argument.
+    fn new(index: usize, arg: &syn::PatType) -> syn::Result<Self> {
+        let name = match &*arg.pat {
+            Pat::Ident(pat) => pat.ident.clone(),
+            _ => format_ident!("arg{}", index),
+        };
+        let reference = match &*arg.ty {
+            Type::Reference(reference) => reference,
+            _ => return Ok(Self::Value(name, arg.ty.clone())),
+        };
+        let is_mut = reference.mutability.is_some();
+        match &*reference.elem {
+            Type::Path(path) if !is_mut && path.path.is_ident("str") => Ok(Self::Str(name)),
+            Type::Slice(slice) if is_u8(&slice.elem) => match is_mut {
+                true => Ok(Self::BytesMut(name)),
+                false => Ok(Self::Bytes(name)),
+            },
+            _ => Err(syn::Error::new(
+                arg.ty.span(),
+                "host function parameters must be `&str`, `&[u8]`, `&mut [u8]` or Wasm values",
+            )),
+        }
+    }
+
+    /// Returns `true` if the [`Param`] is a slice of the linear memory.
+    fn is_slice(&self) -> bool {
+        !matches!(self, Self::Value(..))
+    }
+}
+
+/// Returns `true` if `ty` is `u8`.
+fn is_u8(ty: &Type) -> bool {
+    matches!(ty, Type::Path(path) if path.path.is_ident("u8"))
+}
+
+/// Returns the identifier `name` that cannot clash with identifiers of the macro input.
+///
+/// # Note
+///
+/// This is used for all local variables introduced by the generated code
+/// since host function parameters might be named `memory` or `get` for example.
+fn hygienic(name: &str) -> Ident {
+    Ident::new(name, Span::mixed_site())
+}
+
+/// Expands the `linker.func_wrap` call defining the host function of `func`.
+fn expand_host_func(module: &LitStr, func: &ImplItemFn) -> syn::Result<TokenStream2> {
+    let sig = &func.sig;
+    if !sig.generics.params.is_empty() {
+        return Err(syn::Error::new(
+            sig.generics.span(),
+            "host functions cannot be generic",
+        ));
+    }
+    if let Some(asyncness) = sig.asyncness {
+        return Err(syn::Error::new(
+            asyncness.span(),
+            "host functions cannot be `async`",
+        ));
+    }
+    let mut receiver = None;
+    let mut params = Vec::new();
+    for (index, arg) in sig.inputs.iter().enumerate() {
+        match arg {
+            FnArg::Receiver(arg) => {
+                if arg.reference.is_none() {
+                    return Err(syn::Error::new(
+                        arg.span(),
+                        "host functions must take `self` by reference",
+                    ));
+                }
+                receiver = Some(arg);
+            }
+            FnArg::Typed(arg) => params.push(Param::new(index, arg)?),
+        }
+    }
+    let len_slices = params.iter().filter(|param| param.is_slice()).count();
+    let has_bytes_mut = params
+        .iter()
+        .any(|param| matches!(param, Param::BytesMut(_)));
+    if has_bytes_mut && len_slices > 1 {
+        return Err(syn::Error::new(
+            sig.inputs.span(),
+            "a `&mut [u8]` parameter cannot be combined with other slice parameters",
+        ));
+    }
+    let name = &sig.ident;
+    let export = LitStr::new(&name.to_string(), name.span());
+    let linker = hygienic("linker");
+    let get = hygienic("get");
+    let caller = hygienic("caller");
+    let memory = hygienic("memory");
+    let state = hygienic("state");
+    let mut inputs = Vec::new();
+    let mut resolve = Vec::new();
+    let mut args = Vec::new();
+    for param in &params {
+        let (slice, resolve_fn) = match param {
+            Param::Value(name, ty) => {
+                inputs.push(quote! { #name: #ty });
+                args.push(quote! { #name });
+                continue;
+            }
+            Param::Str(name) => (name, quote! { ::wasmi::__private::str }),
+            Param::Bytes(name) => (name, quote! { ::wasmi::__private::slice }),
+            Param::BytesMut(name) => (name, quote! { ::wasmi::__private::slice_mut }),
+        };
+        let ptr = format_ident!("{}_ptr", slice, span = Span::mixed_site());
+        let len = format_ident!("{}_len", slice, span = Span::mixed_site());
+        inputs.push(quote! { #ptr: i32, #len: i32 });
+        resolve.push(quote! { let #slice = #resolve_fn(#memory, #ptr, #len)?; });
+        args.push(quote! { #slice });
+    }
+    let (caller_param, prepare) = match (len_slices > 0, receiver.is_some()) {
+        (true, true) => (
+            quote! { mut #caller },
+            quote! {
+                let #memory = ::wasmi::__private::caller_memory(&#caller)?;
+                let (#memory, #state) = #memory.data_and_store_mut(&mut #caller);
+            },
+        ),
+        (true, false) => (
+            quote! { mut #caller },
+            quote! {
+                let #memory = ::wasmi::__private::caller_memory(&#caller)?;
+                let #memory = #memory.data_mut(&mut #caller);
+            },
+        ),
+        (false, true) => (
+            quote! { mut #caller },
+            quote! { let #state = #caller.data_mut(); },
+        ),
+        (false, false) => (quote! { _ }, quote! {}),
+    };
+    let call = match receiver {
+        Some(_) => quote! { #get(#state).#name(#( #args ),*) },
+        None => quote! { Self::#name(#( #args ),*) },
+    };
+    let body = match returns_result(&sig.output) {
+        true => call,
+        false => quote! { ::core::result::Result::Ok(#call) },
+    };
+    Ok(quote! {
+        #linker.func_wrap(
+            #module,
+            #export,
+            move |#caller_param: ::wasmi::Caller<'_, T>, #( #inputs ),*|
+                -> ::core::result::Result<_, ::wasmi::core::Trap> {
+                #prepare
+                #( #resolve )*
+                #body
+            },
+        )?;
+    })
+}
+
+/// Returns `true` if the return type is a `Result`.
+fn returns_result(output: &ReturnType) -> bool {
+    match output {
+        ReturnType::Type(_, ty) => match &**ty {
+            Type::Path(path) => path
+                .path
+                .segments
+                .last()
+                .map(|segment| segment.ident == "Result")
+                .unwrap_or(false),
+            _ => false,
+        },
+        ReturnType::Default => false,
+    }
+}
@@ -0,0 +1,101 @@
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{
+    parenthesized,
+    parse_quote,
+    spanned::Spanned,
+    token::Paren,
+    Data,
+    DeriveInput,
+    Fields,
+    Index,
+    Member,
+};
+
+/// Expands `#[derive(WasmType)]` for the `#[repr(transparent)]` newtype `input`.
+pub fn expand(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    if !is_repr_transparent(input)? {
+        return Err(syn::Error::new(
+            input.ident.span(),
+            "`WasmType` can only be derived for `#[repr(transparent)]` structs",
+        ));
+    }
+    let fields = match &input.data {
+        Data::Struct(data) => &data.fields,
+        _ => {
+            return Err(syn::Error::new(
+                input.ident.span(),
+                "`WasmType` can only be derived for structs",
+            ))
+        }
+    };
+    let field = match fields.iter().collect::<Vec<_>>()[..] {
+        [field] => field,
+        _ => {
+            return Err(syn::Error::new(
+                fields.span(),
+                "`WasmType` can only be derived for structs with exactly one field",
+            ))
+        }
+    };
+    let member = match &field.ident {
+        Some(ident) => Member::Named(ident.clone()),
+        None => Member::Unnamed(Index::from(0)),
+    };
+    let field_ty = &field.ty;
+    let name = &input.ident;
+    let mut generics = input.generics.clone();
+    generics
+        .make_where_clause()
+        .predicates
+        .push(parse_quote!(#field_ty: ::wasmi::WasmType));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let construct = match fields {
+        Fields::Named(_) => quote! { Self { #member: value } },
+        _ => quote! { Self(value) },
+    };
+    Ok(quote! {
+        impl #impl_generics ::wasmi::WasmType for #name #ty_generics #where_clause {
+            #[inline]
+            fn ty() -> ::wasmi::core::ValueType {
+                <#field_ty as ::wasmi::WasmType>::ty()
+            }
+
+            #[inline]
+            fn from_untyped(
+                untyped: ::wasmi::core::UntypedValue,
+            ) -> ::core::result::Result<Self, ::wasmi::core::Trap> {
+                let value = <#field_ty as ::wasmi::WasmType>::from_untyped(untyped)?;
+                ::core::result::Result::Ok(#construct)
+            }
+
+            #[inline]
+            fn into_untyped(self) -> ::wasmi::core::UntypedValue {
+                <#field_ty as ::wasmi::WasmType>::into_untyped(self.#member)
+            }
+        }
+    })
+}
+
+/// Returns `true` if `input` is annotated with `#[repr(transparent)]`.
+fn is_repr_transparent(input: &DeriveInput) -> syn::Result<bool> {
+    let mut transparent = false;
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("repr"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("transparent") {
+                transparent = true;
+            }
+            if meta.input.peek(Paren) {
+                // Note: Skip the arguments of representations such as `align(N)`.
+                let _arguments;
+                parenthesized!(_arguments in meta.input);
+            }
+            Ok(())
+        })?;
+    }
+    Ok(transparent)
+}
@@ -0,0 +1,248 @@
+use wasmi::{core::Trap, Engine, Linker, Module, Store};
+
+wasmi::bindgen!({
+    inline: "
+        package example:host;
+
+        /// Logging of the host.
+        interface logging {
+            /// Logs the `message` with the given `level`.
+            log: func(level: u8, message: string);
+        }
+
+        interface math {
+            sum: func(values: list<s32>) -> s64;
+            scale: func(value: f64, factor: f32) -> f64;
+        }
+
+        world plugin {
+            import logging;
+            import random: func() -> u32;
+            import name: func() -> string;
+
+            export math;
+            export greet: func(name: string) -> string;
+            export is-upper: func(c: char) -> bool;
+            export run: func();
+        }
+    ",
+});
+
+const WAT: &str = r#"
+    (module
+        (import "example:host/logging" "log" (func $log (param i32 i32 i32)))
+        (import "$root" "random" (func $random (result i32)))
+        (import "$root" "name" (func $name (param i32)))
+        (memory (export "memory") 1)
+        (global $heap (mut i32) (i32.const 1024))
+        (global $freed (export "freed") (mut i32) (i32.const 0))
+        (data (i32.const 0) "Hello, ")
+        (func $realloc (export "cabi_realloc") (param i32 i32 i32 i32) (result i32)
+            (local $ptr i32)
+            (local.set $ptr
+                (i32.and
+                    (i32.add (global.get $heap) (i32.sub (local.get 2) (i32.const 1)))
+                    (i32.sub (i32.const 0) (local.get 2))
+                )
+            )
+            (global.set $heap (i32.add (local.get $ptr) (local.get 3)))
+            (local.get $ptr)
+        )
+        (func (export "greet") (param $ptr i32) (param $len i32) (result i32)
+            (local $out i32)
+            (local.set $out
+                (call $realloc
+                    (i32.const 0) (i32.const 0) (i32.const 1)
+                    (i32.add (i32.const 7) (local.get $len))
+                )
+            )
+            (memory.copy (local.get $out) (i32.const 0) (i32.const 7))
+            (memory.copy
+                (i32.add (local.get $out) (i32.const 7))
+                (local.get $ptr)
+                (local.get $len)
+            )
+            (i32.store (i32.const 16) (local.get $out))
+            (i32.store (i32.const 20) (i32.add (i32.const 7) (local.get $len)))
+            (i32.const 16)
+        )
+        (func (export "cabi_post_greet") (param i32)
+            (global.set $freed (i32.add (global.get $freed) (i32.const 1)))
+        )
+        (func (export "is-upper") (param i32) (result i32)
+            (i32.and
+                (i32.ge_u (local.get 0) (i32.const 65))
+                (i32.le_u (local.get 0) (i32.const 90))
+            )
+        )
+        (func (export "run")
+            (call $name (i32.const 32))
+            (call $log (i32.const 2) (i32.load (i32.const 32)) (i32.load (i32.const 36)))
+            (call $log (call $random) (i32.const 0) (i32.const 5))
+        )
+        (func (export "example:host/math#sum") (param $ptr i32) (param $len i32) (result i64)
+            (local $sum i64)
+            (block $done
+                (loop $loop
+                    (br_if $done (i32.eqz (local.get $len)))
+                    (local.set $sum
+                        (i64.add
+                            (local.get $sum)
+                            (i64.extend_i32_s (i32.load (local.get $ptr)))
+                        )
+                    )
+                    (local.set $ptr (i32.add (local.get $ptr) (i32.const 4)))
+                    (local.set $len (i32.sub (local.get $len) (i32.const 1)))
+                    (br $loop)
+                )
+            )
+            (local.get $sum)
+        )
+        (func (export "example:host/math#scale") (param f64 f32) (result f64)
+            (f64.mul (local.get 0) (f64.promote_f32 (local.get 1)))
+        )
+    )
+"#;
+
+#[derive(Debug, Default)]
+struct Host {
+    logs: Vec<(u8, String)>,
+    name: Option<String>,
+    random: u32,
+}
+
+impl PluginImports for Host {
+    fn random(&mut self) -> Result<u32, Trap> {
+        Ok(self.random)
+    }
+
+    fn name(&mut self) -> Result<String, Trap> {
+        self.name.clone().ok_or_else(|| Trap::new("missing name"))
+    }
+}
+
+impl logging::Host for Host {
+    fn log(&mut self, level: u8, message: String) -> Result<(), Trap> {
+        self.logs.push((level, message));
+        Ok(())
+    }
+}
+
+/// Instantiates the `wat` with all imports of the `plugin` world defined by [`Host`].
+fn instantiate(wat: &str) -> Result<(Store<Host>, Plugin, wasmi::Instance), wasmi::Error> {
+    let engine = Engine::default();
+    let wasm = wat::parse_str(wat).unwrap();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let mut store = Store::new(&engine, Host::default());
+    let mut linker = <Linker<Host>>::new(&engine);
+    Plugin::add_to_linker(&mut linker, |host| host).unwrap();
+    let (plugin, instance) = Plugin::instantiate(&mut store, &module, &linker)?;
+    Ok((store, plugin, instance))
+}
+
+#[test]
+fn call_exports() {
+    let (mut store, plugin, instance) = instantiate(WAT).unwrap();
+    assert_eq!(
+        plugin.call_greet(&mut store, "wasmi").unwrap(),
+        "Hello, wasmi"
+    );
+    let freed = instance.get_global(&store, "freed").unwrap();
+    assert_eq!(freed.get(&store).i32(), Some(1));
+    assert!(plugin.call_is_upper(&mut store, 'W').unwrap());
+    assert!(!plugin.call_is_upper(&mut store, 'w').unwrap());
+    assert!(plugin.memory().is_some());
+}
+
+#[test]
+fn call_interface_exports() {
+    let (mut store, plugin, _instance) = instantiate(WAT).unwrap();
+    let math = plugin.math();
+    assert_eq!(math.call_sum(&mut store, &[1, 2, -5]).unwrap(), -2);
+    assert_eq!(math.call_sum(&mut store, &[]).unwrap(), 0);
+    assert_eq!(math.call_scale(&mut store, 1.5, 2.0).unwrap(), 3.0);
+}
+
+#[test]
+fn call_imports() {
+    let (mut store, plugin, _instance) = instantiate(WAT).unwrap();
+    store.data_mut().name = Some(String::from("plugin"));
+    store.data_mut().random = 3;
+    plugin.call_run(&mut store).unwrap();
+    assert_eq!(
+        store.data().logs,
+        [(2, String::from("plugin")), (3, String::from("Hello"))]
+    );
+}
+
+#[test]
+fn import_traps() {
+    let (mut store, plugin, _instance) = instantiate(WAT).unwrap();
+    let error = plugin.call_run(&mut store).unwrap_err();
+    assert!(error.to_string().contains("missing name"));
+    assert!(store.data().logs.is_empty());
+}
+
+#[test]
+fn missing_exports() {
+    let error = instantiate(
+        r#"
+        (module
+            (func (export "greet") (param i32 i32) (result i32)
+                (i32.const 0)
+            )
+        )
+    "#,
+    )
+    .unwrap_err();
+    assert!(matches!(
+        error,
+        wasmi::Error::Func(wasmi::errors::FuncError::ExportedFuncNotFound)
+    ));
+}
+
+/// Bindings whose WIT names clash with Rust keywords or generated parameters.
+mod keywords {
+    wasmi::bindgen!({
+        inline: "
+            package example:keywords;
+
+            world keywords {
+                import move: func(store: u32, self: u32) -> u32;
+                export %type: func(store: u32, %type: u32) -> u32;
+            }
+        ",
+    });
+}
+
+#[derive(Debug, Default)]
+struct KeywordsHost;
+
+impl keywords::KeywordsImports for KeywordsHost {
+    fn move_(&mut self, store: u32, self_: u32) -> Result<u32, Trap> {
+        Ok(store - self_)
+    }
+}
+
+#[test]
+fn keyword_names() {
+    let engine = Engine::default();
+    let wasm = wat::parse_str(
+        r#"
+        (module
+            (import "$root" "move" (func $move (param i32 i32) (result i32)))
+            (func (export "type") (param i32 i32) (result i32)
+                (call $move (local.get 0) (local.get 1))
+            )
+        )
+    "#,
+    )
+    .unwrap();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let mut store = Store::new(&engine, KeywordsHost);
+    let mut linker = <Linker<KeywordsHost>>::new(&engine);
+    keywords::Keywords::add_to_linker(&mut linker, |host| host).unwrap();
+    let (bindings, _instance) =
+        keywords::Keywords::instantiate(&mut store, &module, &linker).unwrap();
+    assert_eq!(bindings.call_type_(&mut store, 5, 3).unwrap(), 2);
+}
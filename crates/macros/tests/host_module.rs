@@ -0,0 +1,227 @@
+use wasmi::{
+    core::{Trap, TrapCode},
+    Engine,
+    Linker,
+    Module,
+    Store,
+};
+
+#[derive(Debug, Default)]
+struct Host {
+    messages: Vec<String>,
+    bytes: Vec<u8>,
+    counter: i64,
+}
+
+#[wasmi::host_module("env")]
+impl Host {
+    fn add(a: i32, b: i32) -> i32 {
+        a + b
+    }
+
+    fn bump(&mut self, delta: i64) -> i64 {
+        self.counter += delta;
+        self.counter
+    }
+
+    fn log(&mut self, message: &str) {
+        self.messages.push(message.into());
+    }
+
+    fn store(&mut self, bytes: &[u8], tag: i32) -> i32 {
+        self.bytes.extend_from_slice(bytes);
+        tag
+    }
+
+    fn fill(&self, buffer: &mut [u8]) {
+        buffer.fill(self.counter as u8);
+    }
+
+    fn check(value: i32) -> Result<i32, Trap> {
+        match value {
+            0 => Err(Trap::new("value must not be zero")),
+            value => Ok(value),
+        }
+    }
+}
+
+/// A host module whose parameter names clash with identifiers of the generated code.
+#[derive(Debug, Default)]
+struct Shadowing {
+    calls: i32,
+}
+
+#[wasmi::host_module("shadowing")]
+impl Shadowing {
+    fn shadow(&mut self, memory: &str, get: i32, state: i32, caller: i32, linker: i32) -> i32 {
+        self.calls += 1;
+        memory.len() as i32 + get + state + caller + linker
+    }
+}
+
+#[derive(Debug, Default)]
+struct State {
+    host: Host,
+    shadowing: Shadowing,
+}
+
+/// Instantiates the `wat` with the `env` host module defined and returns its [`Store`] and instance.
+fn setup(wat: &str) -> (Store<State>, wasmi::Instance) {
+    let engine = Engine::default();
+    let wasm = wat::parse_str(wat).unwrap();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let mut store = Store::new(&engine, State::default());
+    let mut linker = <Linker<State>>::new(&engine);
+    Host::add_to_linker(&mut linker, |state| &mut state.host).unwrap();
+    Shadowing::add_to_linker(&mut linker, |state| &mut state.shadowing).unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    (store, instance)
+}
+
+const WAT: &str = r#"
+    (module
+        (import "env" "add" (func $add (param i32 i32) (result i32)))
+        (import "env" "bump" (func $bump (param i64) (result i64)))
+        (import "env" "log" (func $log (param i32 i32)))
+        (import "env" "store" (func $store (param i32 i32 i32) (result i32)))
+        (import "env" "fill" (func $fill (param i32 i32)))
+        (import "env" "check" (func $check (param i32) (result i32)))
+        (memory (export "memory") 1)
+        (data (i32.const 0) "hello\ff")
+        (func (export "add") (param i32 i32) (result i32)
+            (call $add (local.get 0) (local.get 1))
+        )
+        (func (export "bump") (param i64) (result i64)
+            (call $bump (local.get 0))
+        )
+        (func (export "log") (param i32 i32)
+            (call $log (local.get 0) (local.get 1))
+        )
+        (func (export "store") (param i32 i32) (result i32)
+            (call $store (local.get 0) (local.get 1) (i32.const 7))
+        )
+        (func (export "fill") (param i32 i32)
+            (call $fill (local.get 0) (local.get 1))
+        )
+        (func (export "load") (param i32) (result i32)
+            (i32.load8_u (local.get 0))
+        )
+        (func (export "check") (param i32) (result i32)
+            (call $check (local.get 0))
+        )
+    )
+"#;
+
+#[test]
+fn values_and_state() {
+    let (mut store, instance) = setup(WAT);
+    let add = instance
+        .get_typed_func::<(i32, i32), i32>(&store, "add")
+        .unwrap();
+    assert_eq!(add.call(&mut store, (1, 2)).unwrap(), 3);
+    let bump = instance.get_typed_func::<i64, i64>(&store, "bump").unwrap();
+    assert_eq!(bump.call(&mut store, 5).unwrap(), 5);
+    assert_eq!(bump.call(&mut store, 2).unwrap(), 7);
+    assert_eq!(store.data().host.counter, 7);
+}
+
+#[test]
+fn str_and_bytes() {
+    let (mut store, instance) = setup(WAT);
+    let log = instance
+        .get_typed_func::<(i32, i32), ()>(&store, "log")
+        .unwrap();
+    log.call(&mut store, (0, 5)).unwrap();
+    assert_eq!(store.data().host.messages, ["hello"]);
+    let store_bytes = instance
+        .get_typed_func::<(i32, i32), i32>(&store, "store")
+        .unwrap();
+    assert_eq!(store_bytes.call(&mut store, (4, 2)).unwrap(), 7);
+    assert_eq!(store.data().host.bytes, [b'o', 0xFF]);
+}
+
+#[test]
+fn bytes_mut() {
+    let (mut store, instance) = setup(WAT);
+    store.data_mut().host.counter = 42;
+    let fill = instance
+        .get_typed_func::<(i32, i32), ()>(&store, "fill")
+        .unwrap();
+    fill.call(&mut store, (100, 3)).unwrap();
+    let load = instance.get_typed_func::<i32, i32>(&store, "load").unwrap();
+    assert_eq!(load.call(&mut store, 100).unwrap(), 42);
+    assert_eq!(load.call(&mut store, 102).unwrap(), 42);
+    assert_eq!(load.call(&mut store, 103).unwrap(), 0);
+}
+
+#[test]
+fn result() {
+    let (mut store, instance) = setup(WAT);
+    let check = instance
+        .get_typed_func::<i32, i32>(&store, "check")
+        .unwrap();
+    assert_eq!(check.call(&mut store, 3).unwrap(), 3);
+    let error = check.call(&mut store, 0).unwrap_err();
+    assert!(error.to_string().contains("value must not be zero"));
+}
+
+#[test]
+fn invalid_slices_trap() {
+    let (mut store, instance) = setup(WAT);
+    let log = instance
+        .get_typed_func::<(i32, i32), ()>(&store, "log")
+        .unwrap();
+    let error = log.call(&mut store, (65535, 2)).unwrap_err();
+    assert!(matches!(
+        error.trap_code(),
+        Some(TrapCode::MemoryOutOfBounds)
+    ));
+    let error = log.call(&mut store, (0, 6)).unwrap_err();
+    assert!(error.to_string().contains("invalid UTF-8"));
+    assert!(store.data().host.messages.is_empty());
+}
+
+#[test]
+fn missing_memory_traps() {
+    let (mut store, instance) = setup(
+        r#"
+        (module
+            (import "env" "log" (func $log (param i32 i32)))
+            (func (export "log")
+                (call $log (i32.const 0) (i32.const 0))
+            )
+        )
+    "#,
+    );
+    let log = instance.get_typed_func::<(), ()>(&store, "log").unwrap();
+    let error = log.call(&mut store, ()).unwrap_err();
+    assert!(error.to_string().contains("missing required memory export"));
+}
+
+#[test]
+fn shadowing_param_names() {
+    let (mut store, instance) = setup(
+        r#"
+        (module
+            (import "shadowing" "shadow" (func $shadow (param i32 i32 i32 i32 i32 i32) (result i32)))
+            (memory (export "memory") 1)
+            (data (i32.const 0) "hello")
+            (func (export "shadow") (result i32)
+                (call $shadow
+                    (i32.const 0) (i32.const 5)
+                    (i32.const 10) (i32.const 20) (i32.const 30) (i32.const 40)
+                )
+            )
+        )
+    "#,
+    );
+    let shadow = instance
+        .get_typed_func::<(), i32>(&store, "shadow")
+        .unwrap();
+    assert_eq!(shadow.call(&mut store, ()).unwrap(), 105);
+    assert_eq!(store.data().shadowing.calls, 1);
+}
@@ -0,0 +1,64 @@
+use wasmi::{core::ValueType, Engine, Func, FuncType, Store, WasmType};
+
+/// A handle to a host resource passed to Wasm as `i32`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, WasmType)]
+#[repr(transparent)]
+struct Handle(u32);
+
+/// A timestamp passed to Wasm as `i64`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, WasmType)]
+#[repr(transparent)]
+struct Timestamp {
+    nanos: u64,
+}
+
+/// A generic wrapper around another Wasm type.
+#[derive(Debug, Copy, Clone, PartialEq, WasmType)]
+#[repr(transparent)]
+struct Wrapper<T>(T);
+
+#[test]
+fn newtypes_in_host_funcs() {
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, ());
+    let open = Func::wrap(&mut store, |time: Timestamp| Handle(time.nanos as u32 + 1));
+    assert_eq!(
+        open.ty(&store),
+        FuncType::new([ValueType::I64], [ValueType::I32])
+    );
+    let open = open.typed::<Timestamp, Handle>(&store).unwrap();
+    assert_eq!(
+        open.call(&mut store, Timestamp { nanos: 41 }).unwrap(),
+        Handle(42)
+    );
+    let raw = open.func().typed::<u64, u32>(&store).unwrap();
+    assert_eq!(raw.call(&mut store, 1).unwrap(), 2);
+}
+
+#[test]
+fn generic_newtypes() {
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, ());
+    let scale = Func::wrap(&mut store, |value: Wrapper<f64>| Wrapper(value.0 * 2.0));
+    let scale = scale.typed::<Wrapper<f64>, Wrapper<f64>>(&store).unwrap();
+    assert_eq!(scale.call(&mut store, Wrapper(1.25)).unwrap(), Wrapper(2.5));
+    assert_eq!(<Wrapper<Handle> as WasmType>::ty(), ValueType::I32);
+}
+
+/// A single character passed to Wasm as `i32`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, WasmType)]
+#[repr(transparent)]
+struct Letter(char);
+
+#[test]
+fn fallible_newtypes() {
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, ());
+    let next = Func::wrap(&mut store, |letter: Letter| {
+        Letter(char::from_u32(u32::from(letter.0) + 1).unwrap())
+    });
+    let typed = next.typed::<Letter, Letter>(&store).unwrap();
+    assert_eq!(typed.call(&mut store, Letter('a')).unwrap(), Letter('b'));
+    let raw = next.typed::<u32, u32>(&store).unwrap();
+    assert!(raw.call(&mut store, 0xD800).is_err());
+}
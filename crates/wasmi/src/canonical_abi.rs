@@ -0,0 +1,287 @@
+//! Runtime support for bindings generated by the `bindgen` macro.
+//!
+//! Implements the subset of the component model canonical ABI used by core Wasm modules
+//! for scalars, strings and lists of scalars.
+//!
+//! # Note
+//!
+//! This is only available with the `bindgen` crate feature enabled.
+//! The items are not part of the stable API and are only used by generated code.
+
+use crate::{
+    core::{Trap, TrapCode, F32, F64},
+    host_module::slice,
+    AsContext,
+    AsContextMut,
+    Caller,
+    Error,
+    Extern,
+    Instance,
+    Memory,
+    TypedFunc,
+    WasmParams,
+    WasmResults,
+};
+use alloc::{format, string::String, vec::Vec};
+
+/// The `cabi_realloc` export used to allocate linear memory of a Wasm instance.
+pub type Realloc = TypedFunc<(i32, i32, i32, i32), i32>;
+
+/// A scalar with a little-endian representation in linear memory.
+pub trait Scalar: Sized + Copy {
+    /// The size and alignment of the scalar in bytes.
+    const SIZE: usize;
+
+    /// Loads the scalar from the first [`Scalar::SIZE`] `bytes`.
+    ///
+    /// # Errors
+    ///
+    /// If the `bytes` do not represent a valid scalar.
+    fn load(bytes: &[u8]) -> Result<Self, Trap>;
+
+    /// Stores the scalar into the first [`Scalar::SIZE`] `bytes`.
+    fn store(self, bytes: &mut [u8]);
+}
+
+macro_rules! impl_scalar {
+    ( $( $ty:ty ),* $(,)? ) => {
+        $(
+            impl Scalar for $ty {
+                const SIZE: usize = core::mem::size_of::<$ty>();
+
+                fn load(bytes: &[u8]) -> Result<Self, Trap> {
+                    let mut buffer = [0x00_u8; core::mem::size_of::<$ty>()];
+                    buffer.copy_from_slice(&bytes[..Self::SIZE]);
+                    Ok(<$ty>::from_le_bytes(buffer))
+                }
+
+                fn store(self, bytes: &mut [u8]) {
+                    bytes[..Self::SIZE].copy_from_slice(&self.to_le_bytes());
+                }
+            }
+        )*
+    };
+}
+impl_scalar!(u8, i8, u16, i16, u32, i32, u64, i64, f32, f64);
+
+impl Scalar for bool {
+    const SIZE: usize = 1;
+
+    fn load(bytes: &[u8]) -> Result<Self, Trap> {
+        Ok(bytes[0] != 0)
+    }
+
+    fn store(self, bytes: &mut [u8]) {
+        bytes[0] = u8::from(self);
+    }
+}
+
+impl Scalar for char {
+    const SIZE: usize = 4;
+
+    fn load(bytes: &[u8]) -> Result<Self, Trap> {
+        u32::load(bytes).and_then(|value| lift_char(value as i32))
+    }
+
+    fn store(self, bytes: &mut [u8]) {
+        u32::from(self).store(bytes)
+    }
+}
+
+/// Returns `value` if the required export `name` exists.
+///
+/// # Errors
+///
+/// If `value` is `None`.
+pub fn required<T>(value: Option<T>, name: &str) -> Result<T, Trap> {
+    value.ok_or_else(|| Trap::new(format!("missing required export: `{name}`")))
+}
+
+/// Returns the exported function `name` of the `instance` if any.
+///
+/// # Errors
+///
+/// If the exported function does not match `Params` and `Results`.
+pub fn optional_typed_func<Params, Results>(
+    store: impl AsContext,
+    instance: &Instance,
+    name: &str,
+) -> Result<Option<TypedFunc<Params, Results>>, Error>
+where
+    Params: WasmParams,
+    Results: WasmResults,
+{
+    match instance.get_func(&store, name) {
+        Some(func) => func.typed(&store).map(Some),
+        None => Ok(None),
+    }
+}
+
+/// Returns the `cabi_realloc` export of the instance of the `caller`.
+///
+/// # Errors
+///
+/// If the instance of the `caller` does not export a matching `cabi_realloc` function.
+pub fn caller_realloc<T>(caller: &Caller<'_, T>) -> Result<Realloc, Trap> {
+    let func = match caller.get_export("cabi_realloc") {
+        Some(Extern::Func(func)) => func,
+        _ => return Err(Trap::new("missing required export: `cabi_realloc`")),
+    };
+    func.typed(caller)
+        .map_err(|_| Trap::new("mismatched type of export: `cabi_realloc`"))
+}
+
+/// Returns `true` if `ptr` is aligned to the power of two `align`.
+fn is_aligned(ptr: i32, align: usize) -> bool {
+    (ptr as u32 as usize) & (align - 1) == 0
+}
+
+/// Converts the flat `value` into a [`char`].
+///
+/// # Errors
+///
+/// If `value` is not a Unicode scalar value.
+pub fn lift_char(value: i32) -> Result<char, Trap> {
+    char::from_u32(value as u32).ok_or_else(|| Trap::new("encountered invalid `char` value"))
+}
+
+/// Converts the flat `value` into an [`f32`].
+pub fn lift_f32(value: F32) -> f32 {
+    value.to_float()
+}
+
+/// Converts the flat `value` into an [`f64`].
+pub fn lift_f64(value: F64) -> f64 {
+    value.to_float()
+}
+
+/// Converts the [`f32`] `value` into its flat representation.
+pub fn lower_f32(value: f32) -> F32 {
+    F32::from_float(value)
+}
+
+/// Converts the [`f64`] `value` into its flat representation.
+pub fn lower_f64(value: f64) -> F64 {
+    F64::from_float(value)
+}
+
+/// Reads the UTF-8 string of `len` bytes of `memory` starting at `ptr`.
+///
+/// # Errors
+///
+/// - If the bytes are out of bounds of `memory`.
+/// - If the bytes are not valid UTF-8.
+pub fn lift_string(memory: &[u8], ptr: i32, len: i32) -> Result<String, Trap> {
+    crate::host_module::str(memory, ptr, len).map(String::from)
+}
+
+/// Reads the list of `len` scalars of `memory` starting at `ptr`.
+///
+/// # Errors
+///
+/// - If `ptr` is not aligned to the scalar size.
+/// - If the list is out of bounds of `memory`.
+/// - If any of the items is not a valid scalar.
+pub fn lift_list<T: Scalar>(memory: &[u8], ptr: i32, len: i32) -> Result<Vec<T>, Trap> {
+    if !is_aligned(ptr, T::SIZE) {
+        return Err(Trap::new("encountered unaligned list pointer"));
+    }
+    let size = (len as u32 as usize)
+        .checked_mul(T::SIZE)
+        .and_then(|size| i32::try_from(size).ok())
+        .ok_or(TrapCode::MemoryOutOfBounds)?;
+    slice(memory, ptr, size)?
+        .chunks_exact(T::SIZE)
+        .map(T::load)
+        .collect()
+}
+
+/// Reads the `(ptr, len)` pair stored in `memory` at `ptr`.
+///
+/// # Errors
+///
+/// If the pair is out of bounds of `memory` or unaligned.
+pub fn load_pair(memory: &[u8], ptr: i32) -> Result<(i32, i32), Trap> {
+    match lift_list::<i32>(memory, ptr, 2)?[..] {
+        [ptr, len] => Ok((ptr, len)),
+        _ => unreachable!("lifted exactly two items"),
+    }
+}
+
+/// Stores the `(ptr, len)` `pair` into `memory` at `ptr`.
+///
+/// # Errors
+///
+/// If the pair is out of bounds of `memory` or unaligned.
+pub fn store_pair(memory: &mut [u8], ptr: i32, pair: (i32, i32)) -> Result<(), Trap> {
+    if !is_aligned(ptr, 4) {
+        return Err(Trap::new("encountered unaligned return pointer"));
+    }
+    let bytes = crate::host_module::slice_mut(memory, ptr, 8)?;
+    pair.0.store(&mut bytes[..4]);
+    pair.1.store(&mut bytes[4..]);
+    Ok(())
+}
+
+/// Allocates `size` bytes aligned to `align` via `realloc` and returns the pointer.
+///
+/// # Errors
+///
+/// - If `realloc` traps.
+/// - If the returned pointer is unaligned or the allocation is out of bounds of `memory`.
+fn allocate(
+    mut ctx: impl AsContextMut,
+    memory: Memory,
+    realloc: &Realloc,
+    align: usize,
+    size: usize,
+) -> Result<i32, Trap> {
+    let size = i32::try_from(size).map_err(|_| TrapCode::MemoryOutOfBounds)?;
+    let ptr = realloc.call(&mut ctx, (0, 0, align as i32, size))?;
+    if !is_aligned(ptr, align) {
+        return Err(Trap::new("`cabi_realloc` returned an unaligned pointer"));
+    }
+    slice(memory.data(&ctx), ptr, size)?;
+    Ok(ptr)
+}
+
+/// Copies `value` into newly allocated linear memory and returns its `(ptr, len)` pair.
+///
+/// # Errors
+///
+/// If the allocation fails.
+pub fn lower_string(
+    mut ctx: impl AsContextMut,
+    memory: Memory,
+    realloc: &Realloc,
+    value: &str,
+) -> Result<(i32, i32), Trap> {
+    let ptr = allocate(&mut ctx, memory, realloc, 1, value.len())?;
+    let start = ptr as u32 as usize;
+    memory.data_mut(&mut ctx)[start..][..value.len()].copy_from_slice(value.as_bytes());
+    Ok((ptr, value.len() as i32))
+}
+
+/// Copies `values` into newly allocated linear memory and returns its `(ptr, len)` pair.
+///
+/// # Errors
+///
+/// If the allocation fails.
+pub fn lower_list<T: Scalar>(
+    mut ctx: impl AsContextMut,
+    memory: Memory,
+    realloc: &Realloc,
+    values: &[T],
+) -> Result<(i32, i32), Trap> {
+    let size = values
+        .len()
+        .checked_mul(T::SIZE)
+        .ok_or(TrapCode::MemoryOutOfBounds)?;
+    let ptr = allocate(&mut ctx, memory, realloc, T::SIZE, size)?;
+    let start = ptr as u32 as usize;
+    let bytes = &mut memory.data_mut(&mut ctx)[start..][..size];
+    for (value, bytes) in values.iter().zip(bytes.chunks_exact_mut(T::SIZE)) {
+        value.store(bytes);
+    }
+    Ok((ptr, values.len() as i32))
+}
@@ -0,0 +1,131 @@
+//! Capturing of Wasm backtraces upon traps.
+
+use super::{stack::FuncFrame, CompiledFunc, EngineResources};
+use crate::{
+    core::FrameInfo,
+    func::{FuncEntity, WasmFuncEntity},
+    Func,
+    Instance,
+    StoreInner,
+};
+use alloc::vec::Vec;
+
+/// Returns the [`FrameInfo`] of the Wasm function `frames` from innermost to outermost.
+///
+/// # Note
+///
+/// The instruction pointers of caller frames point to the instruction that
+/// follows their call instruction. If `trapped` is `true` the instruction pointer
+/// of the innermost frame instead points to the trapping instruction itself.
+///
+/// With the `dwarf` crate feature the frames are enriched with source level symbols
+/// if the DWARF debug information of the instantiated module is available.
+///
+/// Frames that cannot be resolved to a Wasm function are skipped.
+pub fn capture_backtrace(
+    res: &EngineResources,
+    ctx: &StoreInner,
+    frames: &[FuncFrame],
+    trapped: bool,
+) -> Vec<FrameInfo> {
+    // Note: Deep call stacks usually are the result of recursion which is why
+    //       we avoid resolving the same function over and over again.
+    let mut last: Option<(Instance, CompiledFunc, FrameInfo)> = None;
+    let mut backtrace = Vec::with_capacity(frames.len());
+    for (n, frame) in frames.iter().rev().enumerate() {
+        let (compiled, index) = match res.code_map.resolve_instr_ptr(frame.ip()) {
+            Some(resolved) => resolved,
+            None => continue,
+        };
+        let index = match n == 0 && trapped {
+            true => index,
+            false => index.saturating_sub(1),
+        };
+        let instance = *frame.instance();
+        let wasm_offset = res.code_map.wasm_offset(compiled, index);
+        let frame = match &last {
+            Some((last_instance, last_compiled, frame))
+                if *last_instance == instance && *last_compiled == compiled =>
+            {
+                frame.clone().with_wasm_offset(wasm_offset)
+            }
+            _ => {
+                let frame = match frame_info(res, ctx, &instance, compiled, wasm_offset) {
+                    Some(frame) => frame,
+                    None => continue,
+                };
+                last = Some((instance, compiled, frame.clone()));
+                frame
+            }
+        };
+        #[cfg(feature = "dwarf")]
+        let frame = match (ctx.resolve_instance(&instance).debug_info(), wasm_offset) {
+            (Some(debug_info), Some(wasm_offset)) => {
+                frame.with_symbols(debug_info.symbols(wasm_offset))
+            }
+            _ => frame,
+        };
+        backtrace.push(frame);
+    }
+    backtrace
+}
+
+/// Returns the [`FrameInfo`] of the Wasm function of `instance` with the `compiled` body.
+///
+/// Returns `None` if `instance` defines no such Wasm function.
+///
+/// # Note
+///
+/// Function names are taken from the `name` custom section of the
+/// instantiated module and fall back to the export names of `instance`.
+pub fn frame_info(
+    res: &EngineResources,
+    ctx: &StoreInner,
+    instance: &Instance,
+    compiled: CompiledFunc,
+    wasm_offset: Option<usize>,
+) -> Option<FrameInfo> {
+    let (func_index, func, _) = resolve_wasm_func(res, ctx, instance, compiled)?;
+    let module_name = ctx
+        .resolve_instance(instance)
+        .names()
+        .module()
+        .map(Into::into);
+    let func_name = func.resolve_name(ctx).map(Into::into);
+    Some(FrameInfo::new(
+        func_index,
+        module_name,
+        func_name,
+        wasm_offset,
+    ))
+}
+
+/// Returns the index, [`Func`] and number of parameters of the Wasm function of
+/// `instance` with the `compiled` function body.
+///
+/// Returns `None` if `instance` defines no such Wasm function.
+pub fn resolve_wasm_func(
+    res: &EngineResources,
+    ctx: &StoreInner,
+    instance: &Instance,
+    compiled: CompiledFunc,
+) -> Option<(u32, Func, usize)> {
+    let index = res.code_map.header(compiled).func_index();
+    let func = ctx.resolve_instance(instance).get_func(index)?;
+    match ctx.resolve_func(&func) {
+        FuncEntity::Wasm(wasm_func) if is_wasm_func(wasm_func, instance, compiled) => {
+            let len_params = res
+                .func_types
+                .resolve_func_type(wasm_func.ty_dedup())
+                .params()
+                .len();
+            Some((wasm_func.index(), func, len_params))
+        }
+        _ => None,
+    }
+}
+
+/// Returns `true` if `wasm_func` is the Wasm function of `instance` with the `compiled` body.
+fn is_wasm_func(wasm_func: &WasmFuncEntity, instance: &Instance, compiled: CompiledFunc) -> bool {
+    wasm_func.func_body() == compiled && wasm_func.instance() == instance
+}
@@ -10,6 +10,7 @@ pub use self::utils::{
     BlockFuel,
     BranchOffset,
     BranchTableTargets,
+    CounterIdx,
     DataSegmentIdx,
     DropKeep,
     DropKeepError,
@@ -75,6 +76,14 @@ pub enum Instruction {
     BrTable(BranchTableTargets),
     Unreachable,
     ConsumeFuel(BlockFuel),
+    /// Increments the coverage counter of the basic block starting at this instruction.
+    ///
+    /// # Note
+    ///
+    /// Only emitted if code coverage instrumentation is enabled via [`Config::coverage`].
+    ///
+    /// [`Config::coverage`]: crate::Config::coverage
+    CountCoverage(CounterIdx),
     Return(DropKeep),
     ReturnIfNez(DropKeep),
     /// Tail calls an internal (compiled) function.
@@ -391,6 +400,10 @@ impl Instruction {
     }
 
     /// Convenience method to create a new `ConsumeFuel` instruction.
+    ///
+    /// # Errors
+    ///
+    /// If `amount` is out of bounds for [`BlockFuel`].
     pub fn consume_fuel(amount: u64) -> Result<Self, TranslationError> {
         let block_fuel = BlockFuel::try_from(amount)?;
         Ok(Self::ConsumeFuel(block_fuel))
@@ -403,6 +416,10 @@ impl Instruction {
     /// - If `self` is not a [`ConsumeFuel`] instruction.
     /// - If the new fuel consumption overflows the internal `u64` value.
     ///
+    /// # Errors
+    ///
+    /// If the new fuel consumption is out of bounds for [`BlockFuel`].
+    ///
     /// [`ConsumeFuel`]: Instruction::ConsumeFuel
     pub fn bump_fuel_consumption(&mut self, delta: u64) -> Result<(), TranslationError> {
         match self {
@@ -7,6 +7,7 @@ fn size_of_instruction() {
     assert_eq!(size_of::<DropKeep>(), 4);
     assert_eq!(size_of::<BranchOffset>(), 4);
     assert_eq!(size_of::<BlockFuel>(), 4);
+    assert_eq!(size_of::<CounterIdx>(), 4);
     assert_eq!(size_of::<BranchTableTargets>(), 4);
     assert_eq!(size_of::<DataSegmentIdx>(), 4);
     assert_eq!(size_of::<ElementSegmentIdx>(), 4);
@@ -222,6 +222,39 @@ impl BlockFuel {
     }
 }
 
+/// A coverage counter index used by [`Instruction::CountCoverage`].
+///
+/// # Note
+///
+/// Coverage counters are allocated by the [`Engine`] and their hits are stored per [`Store`].
+///
+/// [`Instruction::CountCoverage`]: super::Instruction::CountCoverage
+/// [`Engine`]: crate::Engine
+/// [`Store`]: crate::Store
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[repr(transparent)]
+pub struct CounterIdx(u32);
+
+impl TryFrom<usize> for CounterIdx {
+    type Error = TranslationError;
+
+    fn try_from(index: usize) -> Result<Self, Self::Error> {
+        match u32::try_from(index) {
+            Ok(index) => Ok(Self(index)),
+            Err(_) => Err(TranslationError::new(
+                TranslationErrorInner::CounterIdxOutOfBounds,
+            )),
+        }
+    }
+}
+
+impl CounterIdx {
+    /// Returns the index value as `usize`.
+    pub fn to_usize(self) -> usize {
+        self.0 as usize
+    }
+}
+
 /// A linear memory access offset.
 ///
 /// # Note
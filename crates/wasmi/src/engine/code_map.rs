@@ -5,7 +5,7 @@ use alloc::vec::Vec;
 use wasmi_arena::ArenaIndex;
 
 /// A reference to a compiled function stored in the [`CodeMap`] of an [`Engine`](crate::Engine).
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub struct CompiledFunc(u32);
 
 impl ArenaIndex for CompiledFunc {
@@ -64,6 +64,8 @@ impl InstructionsRef {
 pub struct FuncHeader {
     /// A reference to the instructions of the function.
     iref: InstructionsRef,
+    /// The index of the function within the function index space of its module.
+    func_index: u32,
     /// The number of local variables of the function.
     len_locals: usize,
     /// The maximum stack height usage of the function during execution.
@@ -72,21 +74,28 @@ pub struct FuncHeader {
 
 impl FuncHeader {
     /// Create a new initialized [`FuncHeader`].
-    pub fn new(iref: InstructionsRef, len_locals: usize, local_stack_height: usize) -> Self {
+    pub fn new(
+        iref: InstructionsRef,
+        func_index: u32,
+        len_locals: usize,
+        local_stack_height: usize,
+    ) -> Self {
         let max_stack_height = local_stack_height
             .checked_add(len_locals)
             .unwrap_or_else(|| panic!("invalid maximum stack height for function"));
         Self {
             iref,
+            func_index,
             len_locals,
             max_stack_height,
         }
     }
 
-    /// Create a new uninitialized [`FuncHeader`].
-    pub fn uninit() -> Self {
+    /// Create a new uninitialized [`FuncHeader`] for the function at `func_index`.
+    pub fn uninit(func_index: u32) -> Self {
         Self {
             iref: InstructionsRef::uninit(),
+            func_index,
             len_locals: 0,
             max_stack_height: 0,
         }
@@ -102,6 +111,11 @@ impl FuncHeader {
         self.iref
     }
 
+    /// Returns the index of the function within the function index space of its module.
+    pub fn func_index(&self) -> u32 {
+        self.func_index
+    }
+
     /// Returns the amount of local variable of the function.
     pub fn len_locals(&self) -> usize {
         self.len_locals
@@ -133,6 +147,22 @@ pub struct CodeMap {
     /// Also this improves efficiency of deallocating the [`CodeMap`]
     /// and generally improves data locality.
     instrs: Vec<Instruction>,
+    /// All initialized compiled functions in the order of their instructions.
+    ///
+    /// # Note
+    ///
+    /// This is used to efficiently map an [`InstructionPtr`] back to
+    /// its [`CompiledFunc`] via binary search.
+    funcs: Vec<CompiledFunc>,
+    /// The Wasm bytecode offsets of all initialized function bodies.
+    ///
+    /// # Note
+    ///
+    /// Each entry maps the index of an instruction in `instrs` to the offset of
+    /// the Wasm operator it originates from. Subsequent instructions without their
+    /// own entry originate from the same Wasm operator. Entries are sorted by their
+    /// instruction index which allows for efficient lookup via binary search.
+    offsets: Vec<(usize, usize)>,
 }
 
 impl Default for CodeMap {
@@ -144,6 +174,8 @@ impl Default for CodeMap {
             // index value for compiled functions that have yet to be
             // initialized with their actual function bodies.
             instrs: vec![Instruction::Unreachable],
+            funcs: Vec::new(),
+            offsets: Vec::new(),
         }
     }
 }
@@ -151,13 +183,16 @@ impl Default for CodeMap {
 impl CodeMap {
     /// Allocates a new uninitialized [`CompiledFunc`] to the [`CodeMap`].
     ///
+    /// The `func_index` is the index of the function within the function
+    /// index space of the module that defines it.
+    ///
     /// # Note
     ///
     /// The uninitialized [`CompiledFunc`] must be initialized using
     /// [`CodeMap::init_func`] before it is executed.
-    pub fn alloc_func(&mut self) -> CompiledFunc {
+    pub fn alloc_func(&mut self, func_index: u32) -> CompiledFunc {
         let header_index = self.headers.len();
-        self.headers.push(FuncHeader::uninit());
+        self.headers.push(FuncHeader::uninit(func_index));
         CompiledFunc::from_usize(header_index)
     }
 
@@ -167,14 +202,16 @@ impl CodeMap {
     ///
     /// - If `func` is an invalid [`CompiledFunc`] reference for this [`CodeMap`].
     /// - If `func` refers to an already initialized [`CompiledFunc`].
-    pub fn init_func<I>(
+    pub fn init_func<I, O>(
         &mut self,
         func: CompiledFunc,
         len_locals: usize,
         local_stack_height: usize,
         instrs: I,
+        offsets: O,
     ) where
         I: IntoIterator<Item = Instruction>,
+        O: IntoIterator<Item = (usize, usize)>,
     {
         assert!(
             self.header(func).is_uninit(),
@@ -182,8 +219,16 @@ impl CodeMap {
         );
         let start = self.instrs.len();
         self.instrs.extend(instrs);
+        self.offsets.extend(
+            offsets
+                .into_iter()
+                .map(|(index, offset)| (start + index, offset)),
+        );
         let iref = InstructionsRef::new(start);
-        self.headers[func.into_usize()] = FuncHeader::new(iref, len_locals, local_stack_height);
+        let func_index = self.header(func).func_index();
+        self.headers[func.into_usize()] =
+            FuncHeader::new(iref, func_index, len_locals, local_stack_height);
+        self.funcs.push(func);
     }
 
     /// Returns an [`InstructionPtr`] to the instruction at [`InstructionsRef`].
@@ -197,6 +242,83 @@ impl CodeMap {
         &self.headers[func_body.into_usize()]
     }
 
+    /// Resolves the [`CompiledFunc`] that the [`InstructionPtr`] points into.
+    ///
+    /// Returns the [`CompiledFunc`] together with the offset of the pointed-to
+    /// instruction relative to the first instruction of the [`CompiledFunc`].
+    ///
+    /// Returns `None` if `ip` does not point into any compiled function of the [`CodeMap`].
+    pub fn resolve_instr_ptr(&self, ip: InstructionPtr) -> Option<(CompiledFunc, usize)> {
+        let index = (ip.ptr as usize).checked_sub(self.instrs.as_ptr() as usize)?
+            / core::mem::size_of::<Instruction>();
+        if index >= self.instrs.len() {
+            return None;
+        }
+        let start_of = |func: &CompiledFunc| self.header(*func).iref.to_usize();
+        let pos = self.funcs.partition_point(|func| start_of(func) <= index);
+        let func = *self.funcs.get(pos.checked_sub(1)?)?;
+        Some((func, index - start_of(&func)))
+    }
+
+    /// Returns the Wasm bytecode offset of the instruction at `index` of the [`CompiledFunc`].
+    ///
+    /// Returns `None` if `func` has no instruction at `index`.
+    pub fn wasm_offset(&self, func: CompiledFunc, index: usize) -> Option<usize> {
+        let start = self.header(func).iref.to_usize();
+        let index = start + index;
+        if index >= self.func_end(func) {
+            return None;
+        }
+        let pos = self.offsets.partition_point(|(instr, _)| *instr <= index);
+        let (instr, offset) = *self.offsets.get(pos.checked_sub(1)?)?;
+        if instr < start {
+            return None;
+        }
+        Some(offset)
+    }
+
+    /// Returns the index of the first instruction of the [`CompiledFunc`] that
+    /// originates from the Wasm operator at the Wasm bytecode `offset`.
+    ///
+    /// Returns `None` if no Wasm operator of `func` is located at `offset`.
+    #[cfg(feature = "debugger")]
+    pub fn instr_index(&self, func: CompiledFunc, offset: usize) -> Option<usize> {
+        self.wasm_offsets(func)
+            .find(|(_, wasm_offset)| *wasm_offset == offset)
+            .map(|(index, _)| index)
+    }
+
+    /// Returns the instructions of the [`CompiledFunc`].
+    pub fn instrs(&self, func: CompiledFunc) -> &[Instruction] {
+        let start = self.header(func).iref.to_usize();
+        &self.instrs[start..self.func_end(func)]
+    }
+
+    /// Returns an iterator over the Wasm bytecode offsets of the [`CompiledFunc`].
+    ///
+    /// Yields the index of every instruction of `func` that is the first instruction
+    /// originating from a Wasm operator together with the offset of that Wasm operator.
+    pub fn wasm_offsets(&self, func: CompiledFunc) -> impl Iterator<Item = (usize, usize)> + '_ {
+        let start = self.header(func).iref.to_usize();
+        let end = self.func_end(func);
+        let first = self.offsets.partition_point(|(instr, _)| *instr < start);
+        self.offsets[first..]
+            .iter()
+            .take_while(move |(instr, _)| *instr < end)
+            .map(move |(instr, offset)| (instr - start, *offset))
+    }
+
+    /// Returns the index one past the last instruction of the [`CompiledFunc`] in `instrs`.
+    fn func_end(&self, func: CompiledFunc) -> usize {
+        let start_of = |func: &CompiledFunc| self.header(*func).iref.to_usize();
+        let start = start_of(&func);
+        let pos = self.funcs.partition_point(|func| start_of(func) <= start);
+        self.funcs
+            .get(pos)
+            .map(start_of)
+            .unwrap_or(self.instrs.len())
+    }
+
     /// Resolves the instruction at `index` of the compiled [`CompiledFunc`].
     #[cfg(test)]
     pub fn get_instr(&self, func_body: CompiledFunc, index: usize) -> Option<&Instruction> {
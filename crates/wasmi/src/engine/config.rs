@@ -1,5 +1,5 @@
 use super::{stack::StackLimits, DropKeep};
-use core::{mem::size_of, num::NonZeroU64};
+use core::{fmt, mem::size_of, num::NonZeroU64};
 use wasmi_core::UntypedValue;
 use wasmparser::WasmFeatures;
 
@@ -39,6 +39,14 @@ pub struct Config {
     fuel_consumption_mode: FuelConsumptionMode,
     /// The configured fuel costs of all `wasmi` bytecode instructions.
     fuel_costs: FuelCosts,
+    /// The fuel budget for compiling a single Wasm module if any.
+    compilation_fuel: Option<u64>,
+    /// The fuel budget for instantiating a single Wasm module if any.
+    instantiation_fuel: Option<u64>,
+    /// Is `true` if Wasm modules retain their custom sections.
+    retain_custom_sections: bool,
+    /// Is `true` if `wasmi` executions shall record code coverage.
+    coverage: bool,
 }
 
 /// The fuel consumption mode of the `wasmi` [`Engine`].
@@ -90,9 +98,25 @@ pub enum FuelConsumptionMode {
 }
 
 /// Type storing all kinds of fuel costs of instructions.
+///
+/// # Note
+///
+/// - Use [`Config::set_fuel_costs`] to install custom [`FuelCosts`] for an [`Engine`].
+/// - All fuel costs per instruction are charged at the start of the basic block
+///   containing the instruction. Therefore they must not exceed `u32::MAX`.
+/// - Construct custom [`FuelCosts`] by adjusting the fields of [`FuelCosts::default`]
+///   since more fuel costs might be added in the future.
+///
+/// [`Engine`]: crate::Engine
 #[derive(Debug, Copy, Clone)]
+#[non_exhaustive]
 pub struct FuelCosts {
     /// The base fuel costs for all instructions.
+    ///
+    /// # Note
+    ///
+    /// This is charged for all instructions that do not belong
+    /// to any of the more specific instruction classes below.
     pub base: u64,
     /// The fuel cost for instruction operating on Wasm entities.
     ///
@@ -106,14 +130,24 @@ pub struct FuelCosts {
     pub load: u64,
     /// The fuel cost offset for `memory.store` instructions.
     pub store: u64,
-    /// The fuel cost offset for `call` and `call_indirect` instructions.
+    /// The fuel cost offset for `call` and `return_call` instructions.
     pub call: u64,
+    /// The fuel cost offset for `call_indirect` and `return_call_indirect` instructions.
+    pub call_indirect: u64,
+    /// The fuel cost offset for integer `div` and `rem` instructions.
+    pub div: u64,
+    /// The fuel cost offset for instructions operating on `f32` or `f64` values.
+    ///
+    /// # Note
+    ///
+    /// This includes float arithmetic, comparisons and conversions from or to floats.
+    pub float: u64,
     /// Determines how many moved stack values consume one fuel upon a branch or return instruction.
     ///
     /// # Note
     ///
-    /// If this is zero then processing [`DropKeep`] costs nothing.
-    branch_kept_per_fuel: u64,
+    /// If this is zero then moving stack values upon branches and returns costs nothing.
+    pub branch_kept_per_fuel: u64,
     /// Determines how many function locals consume one fuel per function call.
     ///
     /// # Note
@@ -121,22 +155,74 @@ pub struct FuelCosts {
     /// - This is also applied to all function parameters since
     ///   they are translated to local variable slots.
     /// - If this is zero then processing function locals costs nothing.
-    func_locals_per_fuel: u64,
+    pub func_locals_per_fuel: u64,
     /// How many memory bytes can be processed per fuel in a `bulk-memory` instruction.
     ///
     /// # Note
     ///
     /// If this is zero then processing memory bytes costs nothing.
-    memory_bytes_per_fuel: u64,
+    pub memory_bytes_per_fuel: u64,
     /// How many table elements can be processed per fuel in a `bulk-table` instruction.
     ///
     /// # Note
     ///
     /// If this is zero then processing table elements costs nothing.
-    table_elements_per_fuel: u64,
+    pub table_elements_per_fuel: u64,
 }
 
+/// An error that may occur when configuring [`FuelCosts`].
+#[derive(Debug)]
+pub enum FuelCostsError {
+    /// The fuel costs of an instruction class exceed `u32::MAX`.
+    OutOfBounds {
+        /// The name of the instruction class.
+        name: &'static str,
+        /// The out of bounds fuel costs.
+        costs: u64,
+    },
+}
+
+impl fmt::Display for FuelCostsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::OutOfBounds { name, costs } => {
+                write!(
+                    f,
+                    "fuel costs for `{name}` instructions are out of bounds: {costs}"
+                )
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for FuelCostsError {}
+
 impl FuelCosts {
+    /// Validates the [`FuelCosts`].
+    ///
+    /// # Errors
+    ///
+    /// If any of the fuel costs per instruction is greater than `u32::MAX`.
+    fn validate(&self) -> Result<(), FuelCostsError> {
+        let per_instr = [
+            ("base", self.base),
+            ("entity", self.entity),
+            ("load", self.load),
+            ("store", self.store),
+            ("call", self.call),
+            ("call_indirect", self.call_indirect),
+            ("div", self.div),
+            ("float", self.float),
+        ];
+        for (name, costs) in per_instr {
+            if u32::try_from(costs).is_err() {
+                return Err(FuelCostsError::OutOfBounds { name, costs });
+            }
+        }
+        Ok(())
+    }
+
     /// Returns the fuel consumption of the amount of items with costs per items.
     fn costs_per(len_items: u64, items_per_fuel: u64) -> u64 {
         NonZeroU64::new(items_per_fuel)
@@ -183,6 +269,9 @@ impl Default for FuelCosts {
             load: 1,
             store: 1,
             call: 1,
+            call_indirect: 1,
+            div: 1,
+            float: 1,
             func_locals_per_fuel: registers_per_fuel,
             branch_kept_per_fuel: registers_per_fuel,
             memory_bytes_per_fuel,
@@ -208,6 +297,10 @@ impl Default for Config {
             consume_fuel: false,
             fuel_costs: FuelCosts::default(),
             fuel_consumption_mode: FuelConsumptionMode::default(),
+            compilation_fuel: None,
+            instantiation_fuel: None,
+            retain_custom_sections: true,
+            coverage: false,
         }
     }
 }
@@ -373,8 +466,27 @@ impl Config {
         self.consume_fuel
     }
 
+    /// Sets the [`FuelCosts`] used to charge fuel for executed instructions.
+    ///
+    /// # Note
+    ///
+    /// - This has no effect if fuel metering is disabled for the [`Engine`].
+    /// - Defaults to [`FuelCosts::default`].
+    ///
+    /// # Errors
+    ///
+    /// If any of the fuel costs per instruction is greater than `u32::MAX`.
+    /// In this case the [`Config`] remains unchanged.
+    ///
+    /// [`Engine`]: crate::Engine
+    pub fn set_fuel_costs(&mut self, fuel_costs: FuelCosts) -> Result<&mut Self, FuelCostsError> {
+        fuel_costs.validate()?;
+        self.fuel_costs = fuel_costs;
+        Ok(self)
+    }
+
     /// Returns the configured [`FuelCosts`].
-    pub(crate) fn fuel_costs(&self) -> &FuelCosts {
+    pub fn fuel_costs(&self) -> &FuelCosts {
         &self.fuel_costs
     }
 
@@ -400,6 +512,102 @@ impl Config {
             .then_some(self.fuel_consumption_mode)
     }
 
+    /// Sets the fuel budget for compiling a single Wasm [`Module`].
+    ///
+    /// Compilation consumes one unit of fuel per byte of every Wasm function body
+    /// and one unit of fuel per `wasmi` bytecode instruction emitted for it.
+    ///
+    /// # Note
+    ///
+    /// - This is independent of [`Config::consume_fuel`] which only affects executions.
+    /// - Compilation fails with [`ModuleError::OutOfFuel`] once the budget is exhausted.
+    /// - By default compilation is not bounded by a fuel budget.
+    ///
+    /// [`Module`]: crate::Module
+    /// [`ModuleError::OutOfFuel`]: crate::errors::ModuleError::OutOfFuel
+    pub fn set_compilation_fuel(&mut self, fuel: u64) -> &mut Self {
+        self.compilation_fuel = Some(fuel);
+        self
+    }
+
+    /// Returns the fuel budget for compiling a single Wasm module if any.
+    pub(crate) fn get_compilation_fuel(&self) -> Option<u64> {
+        self.compilation_fuel
+    }
+
+    /// Sets the fuel budget for instantiating a single Wasm [`Module`].
+    ///
+    /// Instantiation consumes one unit of fuel per byte of every data segment
+    /// and one unit of fuel per item of every element segment of the [`Module`].
+    ///
+    /// # Note
+    ///
+    /// - This is independent of [`Config::consume_fuel`] which only affects executions.
+    /// - Instantiation fails with [`InstantiationError::OutOfFuel`] once the budget is exhausted.
+    /// - By default instantiation is not bounded by a fuel budget.
+    ///
+    /// [`Module`]: crate::Module
+    /// [`InstantiationError::OutOfFuel`]: crate::errors::InstantiationError::OutOfFuel
+    pub fn set_instantiation_fuel(&mut self, fuel: u64) -> &mut Self {
+        self.instantiation_fuel = Some(fuel);
+        self
+    }
+
+    /// Returns the fuel budget for instantiating a single Wasm module if any.
+    pub(crate) fn get_instantiation_fuel(&self) -> Option<u64> {
+        self.instantiation_fuel
+    }
+
+    /// Configures whether Wasm modules retain their custom sections.
+    ///
+    /// # Note
+    ///
+    /// - Retained custom sections can be queried via [`Module::custom_sections`].
+    /// - Disable this to save memory if custom sections are not needed.
+    /// - The `name` custom section is always used for debug information.
+    /// - With the `dwarf` crate feature the `.debug_*` custom sections are always retained
+    ///   for debug information and share their payload with the retained custom sections.
+    ///
+    /// Enabled by default.
+    ///
+    /// [`Module::custom_sections`]: crate::Module::custom_sections
+    pub fn retain_custom_sections(&mut self, enable: bool) -> &mut Self {
+        self.retain_custom_sections = enable;
+        self
+    }
+
+    /// Returns `true` if Wasm modules retain their custom sections.
+    pub(crate) fn get_retain_custom_sections(&self) -> bool {
+        self.retain_custom_sections
+    }
+
+    /// Configures whether `wasmi` records the code coverage of executions.
+    ///
+    /// # Note
+    ///
+    /// This configuration makes `wasmi` instrument its internal bytecode with a
+    /// counter at the start of every basic block that is incremented whenever the
+    /// basic block is executed. The counters are stored per [`Store`].
+    ///
+    /// - Use [`Store::coverage`] to query the recorded coverage of a [`Module`].
+    /// - Use [`Store::reset_coverage`] to reset all recorded coverage counters.
+    ///
+    /// Disabled by default.
+    ///
+    /// [`Store`]: crate::Store
+    /// [`Store::coverage`]: crate::Store::coverage
+    /// [`Store::reset_coverage`]: crate::Store::reset_coverage
+    /// [`Module`]: crate::Module
+    pub fn coverage(&mut self, enable: bool) -> &mut Self {
+        self.coverage = enable;
+        self
+    }
+
+    /// Returns `true` if the [`Config`] enables code coverage instrumentation.
+    pub(crate) fn get_coverage(&self) -> bool {
+        self.coverage
+    }
+
     /// Returns the [`WasmFeatures`] represented by the [`Config`].
     pub(crate) fn wasm_features(&self) -> WasmFeatures {
         WasmFeatures {
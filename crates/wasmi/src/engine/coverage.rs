@@ -0,0 +1,387 @@
+//! Code coverage recorded by executions of `wasmi` bytecode.
+
+use super::{bytecode::CounterIdx, EngineResources, Instruction};
+use crate::Module;
+use alloc::{
+    boxed::Box,
+    collections::BTreeMap,
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt::Write;
+
+/// The hits of the coverage counters of a [`Store`].
+///
+/// # Note
+///
+/// Coverage counters are allocated by the [`Engine`] for every basic block of
+/// every compiled function if [`Config::coverage`] is enabled.
+///
+/// [`Store`]: crate::Store
+/// [`Engine`]: crate::Engine
+/// [`Config::coverage`]: crate::Config::coverage
+#[derive(Debug, Default)]
+pub struct CoverageCounters {
+    /// The hits of all coverage counters indexed by [`CounterIdx`].
+    ///
+    /// Grows upon instantiation to cover all counters allocated by the [`Engine`] so far.
+    ///
+    /// [`Engine`]: crate::Engine
+    hits: Vec<u64>,
+}
+
+impl CoverageCounters {
+    /// Grows the [`CoverageCounters`] to hold at least `len_counters` counters.
+    ///
+    /// # Note
+    ///
+    /// This is called whenever a [`Module`] is instantiated so that all counters
+    /// of its compiled functions are available before any of them are executed.
+    pub fn grow(&mut self, len_counters: usize) {
+        if len_counters > self.hits.len() {
+            self.hits.resize(len_counters, 0);
+        }
+    }
+
+    /// Increments the hits of the `counter`.
+    ///
+    /// # Panics
+    ///
+    /// If the `counter` has not been allocated before the last call to [`CoverageCounters::grow`].
+    #[inline]
+    pub fn hit(&mut self, counter: CounterIdx) {
+        let hits = &mut self.hits[counter.to_usize()];
+        *hits = hits.saturating_add(1);
+    }
+
+    /// Returns the hits of the `counter`.
+    pub fn get(&self, counter: CounterIdx) -> u64 {
+        self.hits.get(counter.to_usize()).copied().unwrap_or(0)
+    }
+
+    /// Resets the hits of all coverage counters to zero.
+    pub fn reset(&mut self) {
+        self.hits.fill(0);
+    }
+}
+
+/// Returns the [`CoverageReport`] of all internal functions of `module` with hits from `counters`.
+pub fn coverage_report(
+    res: &EngineResources,
+    module: &Module,
+    counters: &CoverageCounters,
+) -> CoverageReport {
+    let code_map = &res.code_map;
+    let len_imported = module.len_imported_funcs() as u32;
+    let funcs = module
+        .internal_funcs()
+        .zip(len_imported..)
+        .map(|((_, compiled), index)| {
+            let blocks = code_map
+                .instrs(compiled)
+                .iter()
+                .enumerate()
+                .filter_map(|(n, instr)| match instr {
+                    Instruction::CountCoverage(counter) => {
+                        let wasm_offset = code_map.wasm_offset(compiled, n)?;
+                        Some(BlockCoverage::new(
+                            module,
+                            wasm_offset,
+                            counters.get(*counter),
+                        ))
+                    }
+                    _ => None,
+                })
+                .collect();
+            FuncCoverage {
+                index,
+                name: module.func_name(index).map(Into::into),
+                blocks,
+            }
+        })
+        .collect();
+    CoverageReport {
+        module: module.name().map(Into::into),
+        funcs,
+    }
+}
+
+/// The code coverage of the internal functions of a [`Module`].
+///
+/// # Note
+///
+/// Can be exported in the LCOV format via [`CoverageReport::to_lcov`]
+/// or as JSON via [`CoverageReport::to_json`].
+#[derive(Debug, Clone)]
+pub struct CoverageReport {
+    /// The name of the [`Module`] if any.
+    module: Option<Box<str>>,
+    /// The coverage of the internal functions of the [`Module`].
+    funcs: Vec<FuncCoverage>,
+}
+
+/// The code coverage of a single internal function of a [`Module`].
+#[derive(Debug, Clone)]
+pub struct FuncCoverage {
+    /// The index of the function within the function index space of the [`Module`].
+    index: u32,
+    /// The name of the function if any.
+    name: Option<Box<str>>,
+    /// The coverage of the basic blocks of the function in bytecode order.
+    blocks: Vec<BlockCoverage>,
+}
+
+/// The code coverage of a single basic block of a function.
+///
+/// # Note
+///
+/// Basic blocks start at function entries, `loop` headers, the branches of
+/// `if` and `else`, after the `end` of `block` and `if` as well as after `br_if`.
+#[derive(Debug, Clone)]
+pub struct BlockCoverage {
+    /// The Wasm bytecode offset of the first Wasm operator of the basic block.
+    wasm_offset: usize,
+    /// The number of times the basic block has been executed.
+    hits: u64,
+    /// The source file of the basic block as defined by the DWARF debug information if any.
+    file: Option<Box<str>>,
+    /// The source line of the basic block as defined by the DWARF debug information if any.
+    line: Option<u32>,
+}
+
+impl CoverageReport {
+    /// Returns the coverage of all internal functions of the [`Module`].
+    pub fn funcs(&self) -> &[FuncCoverage] {
+        &self.funcs
+    }
+
+    /// Returns the coverage of the function at `index` if it is an internal function.
+    pub fn func(&self, index: u32) -> Option<&FuncCoverage> {
+        self.funcs.iter().find(|func| func.index == index)
+    }
+
+    /// Returns the [`CoverageReport`] in the LCOV tracefile format.
+    ///
+    /// # Note
+    ///
+    /// Basic blocks are reported at their source lines if the [`Module`] has DWARF
+    /// debug information. Otherwise they are reported at their Wasm bytecode offsets
+    /// as lines of a source file named after the [`Module`].
+    pub fn to_lcov(&self) -> String {
+        let fallback = self.module.as_deref().unwrap_or("module.wasm");
+        let mut files = <BTreeMap<&str, LcovFile>>::new();
+        for func in &self.funcs {
+            let entry = match func.blocks.first() {
+                Some(entry) => entry,
+                None => continue,
+            };
+            let (file, line) = entry.location(fallback);
+            files
+                .entry(file)
+                .or_default()
+                .funcs
+                .push((line, func.display_name(), entry.hits));
+            for block in &func.blocks {
+                let (file, line) = block.location(fallback);
+                let hits = files
+                    .entry(file)
+                    .or_default()
+                    .lines
+                    .entry(line)
+                    .or_default();
+                *hits = (*hits).max(block.hits);
+            }
+        }
+        let mut lcov = String::new();
+        for (file, record) in files {
+            record.write(&mut lcov, file);
+        }
+        lcov
+    }
+
+    /// Returns the [`CoverageReport`] as JSON document.
+    ///
+    /// # Note
+    ///
+    /// The document is an object with the `module` name and a `functions` array.
+    /// Every function has an `index`, `name` and `hits` as well as a `blocks` array
+    /// of basic blocks with their Wasm bytecode `offset`, `hits`, `file` and `line`.
+    /// Names, files and lines that are unknown are `null`.
+    pub fn to_json(&self) -> String {
+        let mut json = String::from("{\"module\":");
+        write_json_str(&mut json, self.module.as_deref());
+        json.push_str(",\"functions\":[");
+        for (n, func) in self.funcs.iter().enumerate() {
+            if n != 0 {
+                json.push(',');
+            }
+            let _ = write!(json, "{{\"index\":{},\"name\":", func.index);
+            write_json_str(&mut json, func.name());
+            let _ = write!(json, ",\"hits\":{},\"blocks\":[", func.hits());
+            for (n, block) in func.blocks.iter().enumerate() {
+                if n != 0 {
+                    json.push(',');
+                }
+                let _ = write!(
+                    json,
+                    "{{\"offset\":{},\"hits\":{},\"file\":",
+                    block.wasm_offset, block.hits
+                );
+                write_json_str(&mut json, block.file());
+                json.push_str(",\"line\":");
+                match block.line {
+                    Some(line) => {
+                        let _ = write!(json, "{line}");
+                    }
+                    None => json.push_str("null"),
+                }
+                json.push('}');
+            }
+            json.push_str("]}");
+        }
+        json.push_str("]}");
+        json
+    }
+}
+
+impl FuncCoverage {
+    /// Returns the index of the function within the function index space of the [`Module`].
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+
+    /// Returns the name of the function as defined by the `name` custom section if any.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Returns the coverage of the basic blocks of the function in bytecode order.
+    pub fn blocks(&self) -> &[BlockCoverage] {
+        &self.blocks
+    }
+
+    /// Returns the number of times the function has been entered.
+    pub fn hits(&self) -> u64 {
+        self.blocks.first().map(BlockCoverage::hits).unwrap_or(0)
+    }
+
+    /// Returns the name of the function or `func[index]` if it has no name.
+    fn display_name(&self) -> String {
+        match self.name() {
+            Some(name) => name.to_string(),
+            None => format!("func[{}]", self.index),
+        }
+    }
+}
+
+impl BlockCoverage {
+    /// Creates a new [`BlockCoverage`] starting at `wasm_offset` of the `module`.
+    #[cfg_attr(not(feature = "dwarf"), allow(unused_variables))]
+    fn new(module: &Module, wasm_offset: usize, hits: u64) -> Self {
+        #[cfg(feature = "dwarf")]
+        let (file, line) = module
+            .symbols(wasm_offset)
+            .first()
+            .map(|symbol| (symbol.file().map(Into::into), symbol.line()))
+            .unwrap_or_default();
+        #[cfg(not(feature = "dwarf"))]
+        let (file, line) = (None, None);
+        Self {
+            wasm_offset,
+            hits,
+            file,
+            line,
+        }
+    }
+
+    /// Returns the Wasm bytecode offset of the first Wasm operator of the basic block.
+    pub fn wasm_offset(&self) -> usize {
+        self.wasm_offset
+    }
+
+    /// Returns the number of times the basic block has been executed.
+    pub fn hits(&self) -> u64 {
+        self.hits
+    }
+
+    /// Returns the source file of the basic block as defined by the DWARF debug information if any.
+    pub fn file(&self) -> Option<&str> {
+        self.file.as_deref()
+    }
+
+    /// Returns the source line of the basic block as defined by the DWARF debug information if any.
+    pub fn line(&self) -> Option<u32> {
+        self.line
+    }
+
+    /// Returns the source file and line of the basic block for LCOV tracefiles.
+    ///
+    /// Falls back to the Wasm bytecode offset as line of the `fallback` file
+    /// if the basic block has no source location.
+    fn location<'a>(&'a self, fallback: &'a str) -> (&'a str, u64) {
+        match (self.file(), self.line) {
+            (Some(file), Some(line)) => (file, u64::from(line)),
+            _ => (fallback, self.wasm_offset as u64),
+        }
+    }
+}
+
+/// The coverage of a single source file of an LCOV tracefile.
+#[derive(Debug, Default)]
+struct LcovFile {
+    /// The line, name and hits of all functions starting in the source file.
+    funcs: Vec<(u64, String, u64)>,
+    /// The maximum hits of all basic blocks per line of the source file.
+    lines: BTreeMap<u64, u64>,
+}
+
+impl LcovFile {
+    /// Writes the LCOV record of the source `file` to `lcov`.
+    fn write(&self, lcov: &mut String, file: &str) {
+        let _ = writeln!(lcov, "TN:\nSF:{file}");
+        for (line, name, _) in &self.funcs {
+            let _ = writeln!(lcov, "FN:{line},{name}");
+        }
+        for (_, name, hits) in &self.funcs {
+            let _ = writeln!(lcov, "FNDA:{hits},{name}");
+        }
+        let funcs_hit = self.funcs.iter().filter(|(_, _, hits)| *hits != 0).count();
+        let _ = writeln!(lcov, "FNF:{}\nFNH:{funcs_hit}", self.funcs.len());
+        for (line, hits) in &self.lines {
+            let _ = writeln!(lcov, "DA:{line},{hits}");
+        }
+        let lines_hit = self.lines.values().filter(|hits| **hits != 0).count();
+        let _ = writeln!(
+            lcov,
+            "LF:{}\nLH:{lines_hit}\nend_of_record",
+            self.lines.len()
+        );
+    }
+}
+
+/// Writes `value` as JSON string to `json` or `null` if it is `None`.
+fn write_json_str(json: &mut String, value: Option<&str>) {
+    let value = match value {
+        Some(value) => value,
+        None => {
+            json.push_str("null");
+            return;
+        }
+    };
+    json.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if c.is_control() => {
+                let _ = write!(json, "\\u{:04x}", c as u32);
+            }
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+}
@@ -0,0 +1,571 @@
+//! Breakpoints, single-stepping and inspection of paused Wasm executions.
+//!
+//! # Note
+//!
+//! This is only available with the `debugger` crate feature enabled.
+
+use super::{
+    backtrace::resolve_wasm_func,
+    code_map::{CodeMap, InstructionPtr},
+    CompiledFunc,
+    EngineResources,
+    Stack,
+};
+use crate::{
+    core::UntypedValue,
+    AsContext,
+    AsContextMut,
+    Engine,
+    Error,
+    Func,
+    Global,
+    Instance,
+    Memory,
+    StoreInner,
+    Value,
+    Watchpoint,
+    WatchpointHit,
+};
+use alloc::{boxed::Box, collections::BTreeSet, vec::Vec};
+use core::{fmt, mem, ops::Range};
+
+/// An error that may occur when setting breakpoints.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum DebuggerError {
+    /// The function index does not refer to a function defined by the module.
+    UnknownFunction { func_index: u32 },
+    /// No Wasm operator of the function is located at the Wasm bytecode offset.
+    InvalidOffset { func_index: u32, offset: usize },
+}
+
+impl fmt::Display for DebuggerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownFunction { func_index } => {
+                write!(f, "module defines no function at index {func_index}")
+            }
+            Self::InvalidOffset { func_index, offset } => {
+                write!(
+                    f,
+                    "function {func_index} has no Wasm operator at offset {offset:#x}"
+                )
+            }
+        }
+    }
+}
+
+/// The kind of step a paused execution performs upon resumption.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum StepKind {
+    /// Pauses at the next Wasm operator, entering called functions.
+    Into,
+    /// Pauses at the next Wasm operator of the same or a calling function.
+    Over,
+    /// Pauses once the current function has returned to its caller.
+    Out,
+}
+
+/// A step requested by the user upon resuming a paused execution.
+#[derive(Debug, Copy, Clone)]
+struct Step {
+    /// The kind of the step.
+    kind: StepKind,
+    /// The function in which the step started.
+    func: CompiledFunc,
+    /// The Wasm bytecode offset at which the step started.
+    offset: Option<usize>,
+    /// The call depth at which the step started.
+    depth: usize,
+}
+
+/// The boxed closure of a watchpoint hook.
+type WatchpointFn = dyn FnMut(&WatchpointHit) + Send + Sync + 'static;
+
+/// The debugger state of a [`Store`].
+///
+/// [`Store`]: crate::Store
+#[derive(Default)]
+pub struct Debugger {
+    /// The breakpoints as compiled functions and instruction indices.
+    breakpoints: BTreeSet<(CompiledFunc, usize)>,
+    /// The step to perform by the next debugged execution if any.
+    step: Option<Step>,
+    /// Set when resuming a paused execution so that it does not pause
+    /// at the instruction at which it has been paused again.
+    skip: bool,
+    /// Set if the next debugged execution shall pause before its next instruction.
+    pause: bool,
+    /// The number of watchpoints on all linear memories of the [`Store`].
+    ///
+    /// [`Store`]: crate::Store
+    len_watchpoints: usize,
+    /// The identifier of the next created watchpoint.
+    next_watchpoint: u32,
+    /// The hook that is called for every access observed by a watchpoint if any.
+    watchpoint_hook: Option<Box<WatchpointFn>>,
+    /// The access at which the debugged execution has been paused by a watchpoint if any.
+    watchpoint_hit: Option<WatchpointHit>,
+    /// Set when resuming an execution paused by a watchpoint so that
+    /// the paused access is not reported again.
+    ///
+    /// Reset whenever a watchpoint is added or removed.
+    skip_watch: bool,
+}
+
+impl fmt::Debug for Debugger {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Debugger")
+            .field("breakpoints", &self.breakpoints)
+            .field("step", &self.step)
+            .field("skip", &self.skip)
+            .field("pause", &self.pause)
+            .field("len_watchpoints", &self.len_watchpoints)
+            .field("next_watchpoint", &self.next_watchpoint)
+            .field("watchpoint_hit", &self.watchpoint_hit)
+            .field("skip_watch", &self.skip_watch)
+            .finish()
+    }
+}
+
+impl Debugger {
+    /// Sets a breakpoint at the instruction `index` of `func`.
+    pub fn set_breakpoint(&mut self, func: CompiledFunc, index: usize) {
+        self.breakpoints.insert((func, index));
+    }
+
+    /// Removes the breakpoint at the instruction `index` of `func`.
+    ///
+    /// Returns `true` if there was such a breakpoint.
+    pub fn remove_breakpoint(&mut self, func: CompiledFunc, index: usize) -> bool {
+        self.breakpoints.remove(&(func, index))
+    }
+
+    /// Removes all breakpoints.
+    pub fn clear_breakpoints(&mut self) {
+        self.breakpoints.clear();
+    }
+
+    /// Requests the next debugged execution to pause before its next instruction.
+    pub fn request_pause(&mut self) {
+        self.pause = true;
+    }
+
+    /// Returns `true` if the execution shall pause before executing the instruction at `ip`.
+    ///
+    /// The `depth` is the number of callers of the currently executed function.
+    pub fn should_pause(&mut self, code_map: &CodeMap, ip: InstructionPtr, depth: usize) -> bool {
+        if mem::take(&mut self.skip) {
+            return false;
+        }
+        if mem::take(&mut self.pause) {
+            self.step = None;
+            return true;
+        }
+        if self.breakpoints.is_empty() && self.step.is_none() {
+            return false;
+        }
+        let (func, index) = match code_map.resolve_instr_ptr(ip) {
+            Some(resolved) => resolved,
+            None => return false,
+        };
+        let pause = self.breakpoints.contains(&(func, index))
+            || match self.step {
+                Some(step) => {
+                    let moved = step.func != func
+                        || step.offset != code_map.wasm_offset(func, index)
+                        || step.depth != depth;
+                    match step.kind {
+                        StepKind::Into => moved,
+                        StepKind::Over => moved && depth <= step.depth,
+                        StepKind::Out => depth < step.depth,
+                    }
+                }
+                None => false,
+            };
+        if pause {
+            self.step = None;
+        }
+        pause
+    }
+
+    /// Cancels the pending step of a finished or aborted execution if any.
+    pub fn cancel_step(&mut self) {
+        self.step = None;
+        self.skip = false;
+        self.watchpoint_hit = None;
+        self.skip_watch = false;
+    }
+
+    /// Returns a new [`Watchpoint`] and accounts for it until it is removed.
+    pub fn add_watchpoint(&mut self) -> Watchpoint {
+        let watchpoint = Watchpoint::new(self.next_watchpoint);
+        self.next_watchpoint = self
+            .next_watchpoint
+            .checked_add(1)
+            .unwrap_or_else(|| panic!("out of watchpoint identifiers"));
+        self.len_watchpoints += 1;
+        self.forget_watchpoint_hit();
+        watchpoint
+    }
+
+    /// Accounts for the removal of a watchpoint.
+    pub fn remove_watchpoint(&mut self) {
+        self.len_watchpoints -= 1;
+        self.forget_watchpoint_hit();
+    }
+
+    /// Forgets the paused and skipped watchpoint accesses since the watchpoints changed.
+    ///
+    /// This way a resumed execution checks its paused access against the new watchpoints.
+    fn forget_watchpoint_hit(&mut self) {
+        self.watchpoint_hit = None;
+        self.skip_watch = false;
+    }
+
+    /// Returns `true` if there are watchpoints on any linear memory.
+    #[inline(always)]
+    pub fn has_watchpoints(&self) -> bool {
+        self.len_watchpoints != 0
+    }
+
+    /// Sets the hook that is called for every access observed by a watchpoint.
+    pub fn set_watchpoint_hook(
+        &mut self,
+        hook: impl FnMut(&WatchpointHit) + Send + Sync + 'static,
+    ) {
+        self.watchpoint_hook = Some(Box::new(hook));
+    }
+
+    /// Removes the watchpoint hook if any.
+    pub fn remove_watchpoint_hook(&mut self) {
+        self.watchpoint_hook = None;
+    }
+
+    /// Returns `true` if the access of the current instruction shall not be reported.
+    ///
+    /// This is the case once right after resuming an execution paused by a watchpoint.
+    #[inline]
+    pub fn skip_watch(&mut self) -> bool {
+        mem::take(&mut self.skip_watch)
+    }
+
+    /// Reports the watchpoint `hit` to the watchpoint hook if any.
+    ///
+    /// Debugged executions additionally remember the `hit` for their [`PausedInvocation`].
+    pub fn report_watchpoint(&mut self, hit: WatchpointHit, debug: bool) {
+        if let Some(hook) = &mut self.watchpoint_hook {
+            hook(&hit);
+        }
+        if debug {
+            self.step = None;
+            self.watchpoint_hit = Some(hit);
+        }
+    }
+
+    /// Prepares the resumption of the paused execution at `frame` with an optional step.
+    fn resume(&mut self, frame: &DebugFrame, depth: usize, kind: Option<StepKind>) {
+        self.skip = true;
+        self.skip_watch = self.watchpoint_hit.take().is_some();
+        self.step = kind.map(|kind| Step {
+            kind,
+            func: frame.compiled,
+            offset: frame.wasm_offset,
+            depth,
+        });
+    }
+}
+
+/// A function frame of a paused Wasm execution.
+#[derive(Debug, Clone)]
+pub struct DebugFrame {
+    /// The instance in which the function has been defined.
+    instance: Instance,
+    /// The executed function.
+    func: Func,
+    /// The index of the executed function within its instance.
+    func_index: u32,
+    /// The compiled function body of the executed function.
+    compiled: CompiledFunc,
+    /// The Wasm bytecode offset of the current Wasm operator if known.
+    wasm_offset: Option<usize>,
+    /// The range of the function parameters and locals on the value stack.
+    locals: Range<usize>,
+    /// The range of the operands of the function on the value stack.
+    operands: Range<usize>,
+}
+
+impl DebugFrame {
+    /// Returns the [`Instance`] in which the function has been defined.
+    pub fn instance(&self) -> &Instance {
+        &self.instance
+    }
+
+    /// Returns the executed [`Func`].
+    pub fn func(&self) -> &Func {
+        &self.func
+    }
+
+    /// Returns the index of the executed function within its [`Instance`].
+    pub fn func_index(&self) -> u32 {
+        self.func_index
+    }
+
+    /// Returns the Wasm bytecode offset of the current Wasm operator if known.
+    ///
+    /// # Note
+    ///
+    /// For the innermost frame this is the Wasm operator that is executed next.
+    /// For all other frames this is the Wasm call operator that is in progress.
+    pub fn wasm_offset(&self) -> Option<usize> {
+        self.wasm_offset
+    }
+}
+
+/// Returned by calling a [`Func`] under the debugger.
+#[derive(Debug)]
+pub enum DebugCall {
+    /// The call has finished properly and returned its results.
+    Finished,
+    /// The call has been paused by a breakpoint, step or watchpoint and can be resumed.
+    Paused(PausedInvocation),
+}
+
+/// State of a [`Func`] invocation paused by the debugger.
+///
+/// # Note
+///
+/// The paused execution can be inspected, manipulated and resumed.
+/// Dropping a [`PausedInvocation`] aborts the execution.
+#[derive(Debug)]
+pub struct PausedInvocation {
+    /// The engine in use for the function invocation.
+    engine: Engine,
+    /// The underlying root function that is executed.
+    func: Func,
+    /// The function frames of the paused execution from innermost to outermost.
+    frames: Vec<DebugFrame>,
+    /// The watchpoint access at which the execution has been paused if any.
+    watchpoint_hit: Option<WatchpointHit>,
+    /// The value and call stack in use by the [`PausedInvocation`].
+    pub(super) stack: Stack,
+}
+
+impl Drop for PausedInvocation {
+    fn drop(&mut self) {
+        let stack = mem::replace(&mut self.stack, Stack::empty());
+        self.engine.recycle_stack(stack);
+    }
+}
+
+impl PausedInvocation {
+    /// Creates a new [`PausedInvocation`] for the paused execution on `stack`.
+    pub(super) fn new(res: &EngineResources, ctx: &StoreInner, func: Func, stack: Stack) -> Self {
+        let frames = debug_frames(res, ctx, &stack);
+        Self {
+            engine: ctx.engine().clone(),
+            func,
+            frames,
+            watchpoint_hit: ctx.debugger().watchpoint_hit.clone(),
+            stack,
+        }
+    }
+
+    /// Returns the function frames of the paused execution.
+    ///
+    /// The innermost frame comes first and the frame of the called root function comes last.
+    pub fn frames(&self) -> &[DebugFrame] {
+        &self.frames
+    }
+
+    /// Returns the memory access that paused the execution if it has been paused by a watchpoint.
+    ///
+    /// # Note
+    ///
+    /// The execution is paused before the access so that resuming it performs the access.
+    pub fn watchpoint_hit(&self) -> Option<&WatchpointHit> {
+        self.watchpoint_hit.as_ref()
+    }
+
+    /// Returns the parameters and locals of the function frame at `depth` if any.
+    pub fn locals(&self, depth: usize) -> Option<&[UntypedValue]> {
+        let range = self.frames.get(depth)?.locals.clone();
+        Some(&self.stack.values.as_slice()[range])
+    }
+
+    /// Returns the parameters and locals of the function frame at `depth` if any.
+    ///
+    /// # Note
+    ///
+    /// Changes are picked up by the execution when resumed.
+    pub fn locals_mut(&mut self, depth: usize) -> Option<&mut [UntypedValue]> {
+        let range = self.frames.get(depth)?.locals.clone();
+        Some(&mut self.stack.values.as_slice_mut()[range])
+    }
+
+    /// Returns the operand stack of the function frame at `depth` if any.
+    ///
+    /// The last value is the top of the operand stack.
+    pub fn operands(&self, depth: usize) -> Option<&[UntypedValue]> {
+        let range = self.frames.get(depth)?.operands.clone();
+        Some(&self.stack.values.as_slice()[range])
+    }
+
+    /// Returns the operand stack of the function frame at `depth` if any.
+    ///
+    /// # Note
+    ///
+    /// Changes are picked up by the execution when resumed.
+    pub fn operands_mut(&mut self, depth: usize) -> Option<&mut [UntypedValue]> {
+        let range = self.frames.get(depth)?.operands.clone();
+        Some(&mut self.stack.values.as_slice_mut()[range])
+    }
+
+    /// Returns the linear memory at `index` of the function frame at `depth` if any.
+    pub fn memory(&self, ctx: impl AsContext, depth: usize, index: u32) -> Option<Memory> {
+        let instance = self.frames.get(depth)?.instance;
+        ctx.as_context()
+            .store
+            .inner
+            .resolve_instance(&instance)
+            .get_memory(index)
+    }
+
+    /// Returns the global variable at `index` of the function frame at `depth` if any.
+    pub fn global(&self, ctx: impl AsContext, depth: usize, index: u32) -> Option<Global> {
+        let instance = self.frames.get(depth)?.instance;
+        ctx.as_context()
+            .store
+            .inner
+            .resolve_instance(&instance)
+            .get_global(index)
+    }
+
+    /// Resumes the paused execution until it finishes or hits the next breakpoint.
+    ///
+    /// The results are written into `outputs` once the execution has finished.
+    ///
+    /// # Errors
+    ///
+    /// - If the execution traps.
+    /// - If the number of output values does not match the expected number of
+    ///   outputs required by the called function.
+    pub fn resume<T>(
+        self,
+        ctx: impl AsContextMut<UserState = T>,
+        outputs: &mut [Value],
+    ) -> Result<DebugCall, Error> {
+        self.resume_with(ctx, None, outputs)
+    }
+
+    /// Resumes the paused execution until the next Wasm operator.
+    ///
+    /// Enters called Wasm functions.
+    ///
+    /// # Errors
+    ///
+    /// See [`PausedInvocation::resume`].
+    pub fn step<T>(
+        self,
+        ctx: impl AsContextMut<UserState = T>,
+        outputs: &mut [Value],
+    ) -> Result<DebugCall, Error> {
+        self.resume_with(ctx, Some(StepKind::Into), outputs)
+    }
+
+    /// Resumes the paused execution until the next Wasm operator of the current function.
+    ///
+    /// Does not pause in called Wasm functions unless they hit a breakpoint.
+    /// Pauses in the caller if the current function returns.
+    ///
+    /// # Errors
+    ///
+    /// See [`PausedInvocation::resume`].
+    pub fn step_over<T>(
+        self,
+        ctx: impl AsContextMut<UserState = T>,
+        outputs: &mut [Value],
+    ) -> Result<DebugCall, Error> {
+        self.resume_with(ctx, Some(StepKind::Over), outputs)
+    }
+
+    /// Resumes the paused execution until the current function has returned to its caller.
+    ///
+    /// # Errors
+    ///
+    /// See [`PausedInvocation::resume`].
+    pub fn step_out<T>(
+        self,
+        ctx: impl AsContextMut<UserState = T>,
+        outputs: &mut [Value],
+    ) -> Result<DebugCall, Error> {
+        self.resume_with(ctx, Some(StepKind::Out), outputs)
+    }
+
+    /// Resumes the paused execution with an optional step.
+    fn resume_with<T>(
+        self,
+        mut ctx: impl AsContextMut<UserState = T>,
+        step: Option<StepKind>,
+        outputs: &mut [Value],
+    ) -> Result<DebugCall, Error> {
+        self.engine
+            .resolve_func_type(self.func.ty_dedup(ctx.as_context()), |func_type| {
+                func_type.match_results(outputs, false)?;
+                func_type.prepare_outputs(outputs);
+                <Result<(), Error>>::Ok(())
+            })?;
+        let depth = self.frames.len() - 1;
+        ctx.as_context_mut()
+            .store
+            .inner
+            .debugger_mut()
+            .resume(&self.frames[0], depth, step);
+        self.engine
+            .clone()
+            .resume_func_debug(ctx.as_context_mut(), self, outputs)
+            .map_err(Into::into)
+    }
+
+    /// Returns the root [`Func`] of the [`PausedInvocation`].
+    pub(super) fn func(&self) -> Func {
+        self.func
+    }
+
+    /// Replaces the internal stack with an empty one that has no heap allocations.
+    pub(super) fn take_stack(&mut self) -> Stack {
+        mem::replace(&mut self.stack, Stack::empty())
+    }
+}
+
+/// Returns the [`DebugFrame`]s of the paused execution on `stack` from innermost to outermost.
+fn debug_frames(res: &EngineResources, ctx: &StoreInner, stack: &Stack) -> Vec<DebugFrame> {
+    let mut frames = Vec::new();
+    let mut top = stack.values.len();
+    for (n, frame) in stack.frames.frames().iter().rev().enumerate() {
+        let (compiled, index) = res
+            .code_map
+            .resolve_instr_ptr(frame.ip())
+            .unwrap_or_else(|| panic!("failed to resolve paused instruction: {:?}", frame.ip()));
+        // Note: The instruction pointers of all but the innermost frame point
+        //       to the instruction that follows the call instruction.
+        let index = if n == 0 { index } else { index - 1 };
+        let instance = *frame.instance();
+        let (func_index, func, len_params) = resolve_wasm_func(res, ctx, &instance, compiled)
+            .unwrap_or_else(|| panic!("missing Wasm function for {compiled:?} in {instance:?}"));
+        let len_locals = res.code_map.header(compiled).len_locals();
+        let params_end = frame.params_end();
+        let locals = params_end - len_params..params_end + len_locals;
+        let operands = locals.end..top;
+        top = locals.start;
+        frames.push(DebugFrame {
+            instance,
+            func,
+            func_index,
+            compiled,
+            wasm_offset: res.code_map.wasm_offset(compiled, index),
+            locals,
+            operands,
+        });
+    }
+    frames
+}
@@ -0,0 +1,323 @@
+//! Disassembly of the `wasmi` bytecode of compiled Wasm functions.
+
+use super::{bytecode::Instruction, CompiledFunc, DedupFuncType, EngineResources};
+use crate::{core::ValueType, FuncType, Module, ModuleNames};
+use alloc::{collections::BTreeMap, format, string::String};
+use core::fmt::{self, Write};
+
+/// Returns the disassembly of the `wasmi` bytecode of all internal functions of `module`.
+pub fn disassemble(res: &EngineResources, module: &Module) -> String {
+    let mut listing = String::new();
+    Disassembler::new(res, module)
+        .write_module(&mut listing)
+        .unwrap_or_else(|error| panic!("failed to write disassembly: {error}"));
+    listing
+}
+
+/// The disassembler of the internal functions of a [`Module`].
+struct Disassembler<'a> {
+    /// The engine resources that store the compiled functions.
+    res: &'a EngineResources,
+    /// The disassembled [`Module`].
+    module: &'a Module,
+    /// The names of the disassembled [`Module`].
+    names: &'a ModuleNames,
+    /// Maps the compiled functions of the [`Module`] to their function indices.
+    func_indices: BTreeMap<CompiledFunc, u32>,
+}
+
+/// A disassembled [`Instruction`] with its optional comment.
+struct Line {
+    /// The mnemonic and operands of the [`Instruction`].
+    text: String,
+    /// The comment that resolves the operands of the [`Instruction`] if any.
+    comment: Option<String>,
+}
+
+impl Line {
+    /// Creates a [`Line`] without comment.
+    fn new(text: String) -> Self {
+        Self {
+            text,
+            comment: None,
+        }
+    }
+
+    /// Returns the [`Line`] with the `comment` appended.
+    fn with_comment(mut self, comment: String) -> Self {
+        self.comment = Some(comment);
+        self
+    }
+}
+
+impl<'a> Disassembler<'a> {
+    /// Creates a new [`Disassembler`] for `module`.
+    fn new(res: &'a EngineResources, module: &'a Module) -> Self {
+        let len_imported = module.len_imported_funcs() as u32;
+        let func_indices = module
+            .internal_funcs()
+            .zip(len_imported..)
+            .map(|((_, compiled), index)| (compiled, index))
+            .collect();
+        Self {
+            res,
+            module,
+            names: module.names(),
+            func_indices,
+        }
+    }
+
+    /// Writes the disassembly of all internal functions of the [`Module`] to `f`.
+    fn write_module(&self, f: &mut String) -> fmt::Result {
+        for (n, (func_type, compiled)) in self.module.internal_funcs().enumerate() {
+            if n != 0 {
+                writeln!(f)?;
+            }
+            self.write_func(f, func_type, compiled)?;
+        }
+        Ok(())
+    }
+
+    /// Writes the disassembly of the `compiled` function with `func_type` to `f`.
+    fn write_func(
+        &self,
+        f: &mut String,
+        func_type: DedupFuncType,
+        compiled: CompiledFunc,
+    ) -> fmt::Result {
+        let code_map = &self.res.code_map;
+        let index = self.func_indices[&compiled];
+        let func_type = self.res.func_types.resolve_func_type(&func_type);
+        let header = code_map.header(compiled);
+        writeln!(
+            f,
+            "{}: {}",
+            self.func_ref(index),
+            DisplayFuncType(func_type)
+        )?;
+        writeln!(
+            f,
+            "  locals: {}, max stack height: {}",
+            header.len_locals(),
+            header.max_stack_height()
+        )?;
+        let mut offsets = code_map.wasm_offsets(compiled).peekable();
+        let mut len_params = 0;
+        for (index, instr) in code_map.instrs(compiled).iter().enumerate() {
+            match offsets.next_if(|(instr, _)| *instr == index) {
+                Some((_, offset)) => write!(f, "  {index:>6}  {offset:#08x}  ")?,
+                None => write!(f, "  {index:>6}  {:8}  ", "")?,
+            }
+            let line = match len_params {
+                0 => {
+                    len_params = params_of(instr);
+                    self.instr(index, instr)
+                }
+                _ => {
+                    len_params -= 1;
+                    param(instr)
+                }
+            };
+            match line.comment {
+                Some(comment) => writeln!(f, "{:<32} ; {comment}", line.text)?,
+                None => writeln!(f, "{}", line.text)?,
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the reference to the function at `index` including its name if any.
+    fn func_ref(&self, index: u32) -> String {
+        match self.names.func(index) {
+            Some(name) => format!("func[{index}] <{name}>"),
+            None => format!("func[{index}]"),
+        }
+    }
+
+    /// Returns the disassembled `instr` at `index`.
+    fn instr(&self, index: usize, instr: &Instruction) -> Line {
+        let name = mnemonic(instr);
+        let op = |operand: String| Line::new(format!("{name} {operand}"));
+        match *instr {
+            Instruction::LocalGet(depth)
+            | Instruction::LocalSet(depth)
+            | Instruction::LocalTee(depth) => op(format!("{}", depth.to_usize())),
+            Instruction::Br(offset)
+            | Instruction::BrIfEqz(offset)
+            | Instruction::BrIfNez(offset)
+            | Instruction::BrAdjust(offset)
+            | Instruction::BrAdjustIfNez(offset) => {
+                let target = index as i64 + i64::from(offset.to_i32());
+                op(format!("{:+}", offset.to_i32())).with_comment(format!("-> {target}"))
+            }
+            Instruction::BrTable(targets) => {
+                let targets = targets.to_usize();
+                op(format!("{targets}")).with_comment(format!("{targets} targets incl. default"))
+            }
+            Instruction::ConsumeFuel(fuel) => op(format!("{}", fuel.to_u64())),
+            Instruction::CountCoverage(counter) => op(format!("counter[{}]", counter.to_usize())),
+            Instruction::Return(drop_keep) | Instruction::ReturnIfNez(drop_keep) => op(format!(
+                "drop={} keep={}",
+                drop_keep.drop(),
+                drop_keep.keep()
+            )),
+            Instruction::ReturnCallInternal(compiled) | Instruction::CallInternal(compiled) => {
+                match self.func_indices.get(&compiled) {
+                    Some(index) => op(self.func_ref(*index)),
+                    None => op(format!("{compiled:?}")),
+                }
+            }
+            Instruction::ReturnCall(func)
+            | Instruction::Call(func)
+            | Instruction::RefFunc(func) => op(self.func_ref(func.to_u32())),
+            Instruction::ReturnCallIndirect(signature) | Instruction::CallIndirect(signature) => {
+                op(format!("type[{}]", signature.to_u32()))
+            }
+            Instruction::GlobalGet(global) | Instruction::GlobalSet(global) => {
+                let line = op(format!("global[{}]", global.to_u32()));
+                match self.names.global(global.to_u32()) {
+                    Some(name) => line.with_comment(format!("<{name}>")),
+                    None => line,
+                }
+            }
+            Instruction::I32Load(offset)
+            | Instruction::I64Load(offset)
+            | Instruction::F32Load(offset)
+            | Instruction::F64Load(offset)
+            | Instruction::I32Load8S(offset)
+            | Instruction::I32Load8U(offset)
+            | Instruction::I32Load16S(offset)
+            | Instruction::I32Load16U(offset)
+            | Instruction::I64Load8S(offset)
+            | Instruction::I64Load8U(offset)
+            | Instruction::I64Load16S(offset)
+            | Instruction::I64Load16U(offset)
+            | Instruction::I64Load32S(offset)
+            | Instruction::I64Load32U(offset)
+            | Instruction::I32Store(offset)
+            | Instruction::I64Store(offset)
+            | Instruction::F32Store(offset)
+            | Instruction::F64Store(offset)
+            | Instruction::I32Store8(offset)
+            | Instruction::I32Store16(offset)
+            | Instruction::I64Store8(offset)
+            | Instruction::I64Store16(offset)
+            | Instruction::I64Store32(offset) => op(format!("offset={}", offset.into_inner())),
+            Instruction::MemoryInit(segment) | Instruction::DataDrop(segment) => {
+                op(format!("data[{}]", segment.to_u32()))
+            }
+            Instruction::TableSize(table)
+            | Instruction::TableGrow(table)
+            | Instruction::TableFill(table)
+            | Instruction::TableGet(table)
+            | Instruction::TableSet(table)
+            | Instruction::TableCopy(table) => op(format!("table[{}]", table.to_u32())),
+            Instruction::TableInit(segment) | Instruction::ElemDrop(segment) => {
+                op(format!("elem[{}]", segment.to_u32()))
+            }
+            Instruction::Const32(bytes) => {
+                let bits = u32::from_ne_bytes(bytes);
+                op(format!("{bits:#010x}")).with_comment(format!(
+                    "i32: {}, f32: {:?}",
+                    bits as i32,
+                    f32::from_bits(bits)
+                ))
+            }
+            Instruction::I64Const32(value) => op(format!("{value}")),
+            Instruction::ConstRef(cref) => {
+                let line = op(format!("const[{}]", cref.to_usize()));
+                match self.res.const_pool.get(cref) {
+                    Some(value) => line.with_comment(format!("{:#018x}", value.to_bits())),
+                    None => line,
+                }
+            }
+            _ => Line::new(name),
+        }
+    }
+}
+
+/// Returns the number of parameter words that follow `instr`.
+///
+/// # Note
+///
+/// Parameter words are [`Instruction`]s that are never executed
+/// and only store additional parameters of the preceding [`Instruction`].
+fn params_of(instr: &Instruction) -> usize {
+    match instr {
+        Instruction::BrAdjust(_)
+        | Instruction::BrAdjustIfNez(_)
+        | Instruction::ReturnCallInternal(_)
+        | Instruction::ReturnCall(_)
+        | Instruction::CallIndirect(_)
+        | Instruction::TableCopy(_)
+        | Instruction::TableInit(_) => 1,
+        Instruction::ReturnCallIndirect(_) => 2,
+        _ => 0,
+    }
+}
+
+/// Returns the disassembled parameter word `instr`.
+fn param(instr: &Instruction) -> Line {
+    match instr {
+        Instruction::Return(drop_keep) => Line::new(format!(
+            "  (drop={} keep={})",
+            drop_keep.drop(),
+            drop_keep.keep()
+        )),
+        Instruction::TableGet(table) => Line::new(format!("  (table[{}])", table.to_u32())),
+        instr => Line::new(format!("  ({instr:?})")),
+    }
+}
+
+/// Returns the mnemonic of `instr` in `snake_case`.
+///
+/// For example `Instruction::I32TruncSatF32S` yields `i32_trunc_sat_f32_s`.
+fn mnemonic(instr: &Instruction) -> String {
+    let debug = format!("{instr:?}");
+    let name = debug.split('(').next().unwrap_or(&debug);
+    let mut mnemonic = String::with_capacity(name.len() + 4);
+    let mut prev: Option<char> = None;
+    for c in name.chars() {
+        if c.is_ascii_uppercase() {
+            if matches!(prev, Some(prev) if prev.is_ascii_lowercase() || prev.is_ascii_digit()) {
+                mnemonic.push('_');
+            }
+            mnemonic.push(c.to_ascii_lowercase());
+        } else {
+            mnemonic.push(c);
+        }
+        prev = Some(c);
+    }
+    mnemonic
+}
+
+/// Displays a [`FuncType`] as `(params) -> (results)`.
+struct DisplayFuncType<'a>(&'a FuncType);
+
+impl fmt::Display for DisplayFuncType<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_types(f, self.0.params())?;
+        write!(f, " -> ")?;
+        write_types(f, self.0.results())
+    }
+}
+
+/// Writes the value `types` as parenthesized list to `f`.
+fn write_types(f: &mut fmt::Formatter, types: &[ValueType]) -> fmt::Result {
+    write!(f, "(")?;
+    for (n, ty) in types.iter().enumerate() {
+        if n != 0 {
+            write!(f, ", ")?;
+        }
+        let ty = match ty {
+            ValueType::I32 => "i32",
+            ValueType::I64 => "i64",
+            ValueType::F32 => "f32",
+            ValueType::F64 => "f64",
+            ValueType::FuncRef => "funcref",
+            ValueType::ExternRef => "externref",
+        };
+        write!(f, "{ty}")?;
+    }
+    write!(f, ")")
+}
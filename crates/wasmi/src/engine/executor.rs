@@ -1,4 +1,8 @@
 use super::{bytecode::BranchOffset, const_pool::ConstRef, CompiledFunc, ConstPoolView};
+#[cfg(feature = "trace")]
+use crate::engine::TraceEvent;
+#[cfg(feature = "debugger")]
+use crate::WatchKind;
 use crate::{
     core::TrapCode,
     engine::{
@@ -6,6 +10,7 @@ use crate::{
             AddressOffset,
             BlockFuel,
             BranchTableTargets,
+            CounterIdx,
             DataSegmentIdx,
             ElementSegmentIdx,
             FuncIdx,
@@ -48,6 +53,13 @@ pub enum WasmOutcome {
     Return,
     /// The Wasm execution calls a host function.
     Call { host_func: Func, instance: Instance },
+    /// The Wasm execution has been paused by the debugger.
+    ///
+    /// # Note
+    ///
+    /// The paused function frame has been pushed onto the call stack.
+    #[cfg(feature = "debugger")]
+    Pause,
 }
 
 /// The outcome of a Wasm execution.
@@ -171,6 +183,9 @@ struct Executor<'ctx, 'engine> {
     code_map: &'engine CodeMap,
     /// A read-only view to a pool of constant values.
     const_pool: ConstPoolView<'engine>,
+    /// The height of the value stack right after the parameters of the executed function.
+    #[cfg(feature = "debugger")]
+    params_end: usize,
 }
 
 macro_rules! forward_call {
@@ -211,29 +226,76 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
             call_stack,
             code_map,
             const_pool,
+            #[cfg(feature = "debugger")]
+            params_end: frame.params_end(),
         }
     }
 
     /// Executes the function frame until it returns or traps.
+    ///
+    /// # Note
+    ///
+    /// Upon a trap the trapping function frame is pushed onto the call stack
+    /// so that the backtrace of the trap can be captured from the call stack.
     #[inline(always)]
     fn execute(
         mut self,
         resource_limiter: &'ctx mut ResourceLimiterRef<'ctx>,
+    ) -> Result<WasmOutcome, TrapCode> {
+        // Debugged executions and watchpoints can only change from host code
+        // which always returns control to a new executor, so the instrumented
+        // loop is selected once per executor.
+        #[cfg(feature = "debugger")]
+        let outcome = match self.call_stack.is_debug() || self.ctx.debugger().has_watchpoints() {
+            true => self.execute_instrs::<true>(resource_limiter),
+            false => self.execute_instrs::<false>(resource_limiter),
+        };
+        #[cfg(not(feature = "debugger"))]
+        let outcome = self.execute_instrs::<false>(resource_limiter);
+        if outcome.is_err() {
+            self.push_trapping_frame();
+        }
+        outcome
+    }
+
+    /// Executes the instructions of the function frame until it returns or traps.
+    ///
+    /// # Note
+    ///
+    /// Only `INSTRUMENTED` executions check for debugger pauses and watchpoints
+    /// so that executions without either do not pay for them.
+    #[inline(always)]
+    fn execute_instrs<const INSTRUMENTED: bool>(
+        &mut self,
+        resource_limiter: &'ctx mut ResourceLimiterRef<'ctx>,
     ) -> Result<WasmOutcome, TrapCode> {
         use Instruction as Instr;
         loop {
+            #[cfg(feature = "debugger")]
+            if INSTRUMENTED && self.call_stack.is_debug() && self.should_pause() {
+                return self.pause();
+            }
+            #[cfg(feature = "debugger")]
+            if INSTRUMENTED && self.ctx.debugger().has_watchpoints() && self.watch() {
+                return self.pause();
+            }
+            #[cfg(feature = "trace")]
+            self.trace();
+            #[cfg(feature = "profiler")]
+            self.profile();
             match *self.ip.get() {
                 Instr::LocalGet(local_depth) => self.visit_local_get(local_depth),
                 Instr::LocalSet(local_depth) => self.visit_local_set(local_depth),
                 Instr::LocalTee(local_depth) => self.visit_local_tee(local_depth),
-                Instr::Br(offset) => self.visit_br(offset),
-                Instr::BrIfEqz(offset) => self.visit_br_if_eqz(offset),
-                Instr::BrIfNez(offset) => self.visit_br_if_nez(offset),
-                Instr::BrAdjust(offset) => self.visit_br_adjust(offset),
-                Instr::BrAdjustIfNez(offset) => self.visit_br_adjust_if_nez(offset),
+                Instr::Br(offset) => self.visit_br(offset)?,
+                Instr::BrIfEqz(offset) => self.visit_br_if_eqz(offset)?,
+                Instr::BrIfNez(offset) => self.visit_br_if_nez(offset)?,
+                Instr::BrAdjust(offset) => self.visit_br_adjust(offset)?,
+                Instr::BrAdjustIfNez(offset) => self.visit_br_adjust_if_nez(offset)?,
                 Instr::BrTable(targets) => self.visit_br_table(targets),
                 Instr::Unreachable => self.visit_unreachable()?,
                 Instr::ConsumeFuel(block_fuel) => self.visit_consume_fuel(block_fuel)?,
+                Instr::CountCoverage(counter) => self.visit_count_coverage(counter),
                 Instr::Return(drop_keep) => {
                     if let ReturnOutcome::Host = self.visit_ret(drop_keep) {
                         return Ok(WasmOutcome::Return);
@@ -439,6 +501,193 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
         }
     }
 
+    /// Pushes the [`FuncFrame`] of the trapping function onto the call stack.
+    ///
+    /// # Note
+    ///
+    /// The recursion limit of the call stack is ignored since the
+    /// trapping function frame might be the reason of a stack overflow.
+    #[cold]
+    fn push_trapping_frame(&mut self) {
+        let frame = FuncFrame::new(self.ip, self.cache.instance());
+        #[cfg(feature = "debugger")]
+        let frame = frame.with_params_end(self.params_end);
+        self.call_stack.push_unchecked(frame);
+    }
+
+    /// Reports the instruction that is about to be executed to the trace hook if any.
+    #[cfg(feature = "trace")]
+    #[inline(always)]
+    fn trace(&mut self) {
+        let hook = match self.ctx.trace_hook_mut() {
+            Some(hook) => hook,
+            None => return,
+        };
+        self.value_stack.sync_stack_ptr(self.sp);
+        let resolved = self.code_map.resolve_instr_ptr(self.ip);
+        let func_index = resolved.map(|(func, _)| self.code_map.header(func).func_index());
+        let offset = resolved.map(|(_, offset)| offset);
+        let wasm_offset =
+            resolved.and_then(|(func, offset)| self.code_map.wasm_offset(func, offset));
+        let event = TraceEvent::new(
+            self.ip.get(),
+            func_index,
+            offset,
+            wasm_offset,
+            self.value_stack.as_slice(),
+        );
+        hook.call(&event);
+    }
+
+    /// Samples the current function if the profiler requests a sample before the current instruction.
+    #[cfg(feature = "profiler")]
+    #[inline(always)]
+    fn profile(&mut self) {
+        if let Some(profiler) = self.ctx.profiler_mut() {
+            if profiler.tick() {
+                profiler.sample();
+            }
+        }
+    }
+
+    /// Updates the profiled call stack upon a `kind` call of the Wasm function `func` of `instance`.
+    #[cfg(feature = "profiler")]
+    #[inline(always)]
+    fn profile_call(&mut self, instance: Instance, func: CompiledFunc, kind: CallKind) {
+        if let Some(profiler) = self.ctx.profiler_mut() {
+            if matches!(kind, CallKind::Tail) {
+                profiler.ret();
+            }
+            profiler.call(instance, func);
+        }
+    }
+
+    /// Updates the profiled call stack upon a return of the current function.
+    #[cfg(feature = "profiler")]
+    #[inline(always)]
+    fn profile_return(&mut self) {
+        if let Some(profiler) = self.ctx.profiler_mut() {
+            profiler.ret();
+        }
+    }
+
+    /// Returns `true` if the debugger requests to pause before the current instruction.
+    #[cfg(feature = "debugger")]
+    #[inline(always)]
+    fn should_pause(&mut self) -> bool {
+        self.ctx
+            .debugger_mut()
+            .should_pause(self.code_map, self.ip, self.call_stack.len())
+    }
+
+    /// Reports the memory accesses of the current instruction observed by a watchpoint.
+    ///
+    /// Returns `true` if the debugged execution shall pause before the current instruction.
+    ///
+    /// # Note
+    ///
+    /// Accesses that are out of bounds are not reported since they trap instead.
+    #[cfg(feature = "debugger")]
+    #[cold]
+    fn watch(&mut self) -> bool {
+        if self.ctx.debugger_mut().skip_watch() {
+            return false;
+        }
+        let accesses = self.memory_accesses();
+        if accesses.iter().all(Option::is_none) {
+            return false;
+        }
+        let memory = *self.cache.default_memory(self.ctx);
+        let entity = self.ctx.resolve_memory(&memory);
+        let len = entity.data().len();
+        if accesses.iter().flatten().any(|(_, range)| range.end > len) {
+            return false;
+        }
+        let hit = accesses
+            .into_iter()
+            .flatten()
+            .find_map(|(access, range)| entity.watchpoints().hit(memory, access, range));
+        match hit {
+            Some(hit) => {
+                let debug = self.call_stack.is_debug();
+                self.ctx.debugger_mut().report_watchpoint(hit, debug);
+                debug
+            }
+            None => false,
+        }
+    }
+
+    /// Returns the linear memory accesses of the current instruction.
+    ///
+    /// # Note
+    ///
+    /// The operands of the instruction are peeked from the value stack.
+    /// Address computations that overflow yield an empty range at [`usize::MAX`].
+    #[cfg(feature = "debugger")]
+    fn memory_accesses(&self) -> [Option<(WatchKind, core::ops::Range<usize>)>; 2] {
+        use Instruction as Instr;
+        let operand = |depth: usize| u32::from(self.sp.nth_back(depth)) as usize;
+        // Loads have their address on top and stores below the stored value.
+        let access = |kind: WatchKind, depth: usize, offset: AddressOffset, width: usize| {
+            let range = operand(depth)
+                .checked_add(offset.into_inner() as usize)
+                .and_then(|start| Some(start..start.checked_add(width)?))
+                .unwrap_or(usize::MAX..usize::MAX);
+            [Some((kind, range)), None]
+        };
+        let load = |offset, width| access(WatchKind::Read, 1, offset, width);
+        let store = |offset, width| access(WatchKind::Write, 2, offset, width);
+        let bulk = |depth: usize| {
+            let start = operand(depth);
+            start
+                .checked_add(operand(1))
+                .map(|end| start..end)
+                .unwrap_or(usize::MAX..usize::MAX)
+        };
+        match *self.ip.get() {
+            Instr::I32Load8S(offset)
+            | Instr::I32Load8U(offset)
+            | Instr::I64Load8S(offset)
+            | Instr::I64Load8U(offset) => load(offset, 1),
+            Instr::I32Load16S(offset)
+            | Instr::I32Load16U(offset)
+            | Instr::I64Load16S(offset)
+            | Instr::I64Load16U(offset) => load(offset, 2),
+            Instr::I32Load(offset)
+            | Instr::F32Load(offset)
+            | Instr::I64Load32S(offset)
+            | Instr::I64Load32U(offset) => load(offset, 4),
+            Instr::I64Load(offset) | Instr::F64Load(offset) => load(offset, 8),
+            Instr::I32Store8(offset) | Instr::I64Store8(offset) => store(offset, 1),
+            Instr::I32Store16(offset) | Instr::I64Store16(offset) => store(offset, 2),
+            Instr::I32Store(offset) | Instr::F32Store(offset) | Instr::I64Store32(offset) => {
+                store(offset, 4)
+            }
+            Instr::I64Store(offset) | Instr::F64Store(offset) => store(offset, 8),
+            Instr::MemoryFill | Instr::MemoryInit(_) => [Some((WatchKind::Write, bulk(3))), None],
+            Instr::MemoryCopy => [
+                Some((WatchKind::Read, bulk(2))),
+                Some((WatchKind::Write, bulk(3))),
+            ],
+            _ => [None, None],
+        }
+    }
+
+    /// Pauses the execution before the current instruction.
+    ///
+    /// # Note
+    ///
+    /// The paused function frame is pushed onto the call stack so
+    /// that the execution can be inspected and resumed later.
+    #[cfg(feature = "debugger")]
+    #[cold]
+    fn pause(&mut self) -> Result<WasmOutcome, TrapCode> {
+        self.sync_stack_ptr();
+        let frame = FuncFrame::new(self.ip, self.cache.instance()).with_params_end(self.params_end);
+        self.call_stack.push(frame)?;
+        Ok(WasmOutcome::Pause)
+    }
+
     /// Executes a generic Wasm `store[N_{s|u}]` operation.
     ///
     /// # Note
@@ -573,9 +822,19 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
     ///
     /// Offsets the instruction pointer using the given [`BranchOffset`] and
     /// adjusts the value stack using the [`DropKeep`].
+    ///
+    /// # Errors
+    ///
+    /// If the branch is a backward branch and the execution has been interrupted.
     #[inline(always)]
-    fn branch_to(&mut self, offset: BranchOffset) {
-        self.ip.offset(offset.to_i32() as isize)
+    fn branch_to(&mut self, offset: BranchOffset) -> Result<(), TrapCode> {
+        if offset.to_i32() <= 0 {
+            // Backward branches are checked for interrupts since
+            // they are the only way for a Wasm loop to iterate.
+            self.ctx.check_interrupt()?;
+        }
+        self.ip.offset(offset.to_i32() as isize);
+        Ok(())
     }
 
     /// Branches and adjusts the value stack.
@@ -585,7 +844,11 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
     /// Offsets the instruction pointer using the given [`BranchOffset`] and
     /// adjusts the value stack using the [`DropKeep`].
     #[inline(always)]
-    fn branch_to_and_adjust(&mut self, offset: BranchOffset, drop_keep: DropKeep) {
+    fn branch_to_and_adjust(
+        &mut self,
+        offset: BranchOffset,
+        drop_keep: DropKeep,
+    ) -> Result<(), TrapCode> {
         self.sp.drop_keep(drop_keep);
         self.branch_to(offset)
     }
@@ -614,22 +877,42 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
         func: &Func,
         kind: CallKind,
     ) -> Result<CallOutcome, TrapCode> {
+        self.ctx.check_interrupt()?;
         self.next_instr_at(skip);
         self.sync_stack_ptr();
-        if matches!(kind, CallKind::Nested) {
-            self.call_stack
-                .push(FuncFrame::new(self.ip, self.cache.instance()))?;
-        }
         match self.ctx.resolve_func(func) {
             FuncEntity::Wasm(wasm_func) => {
-                let header = self.code_map.header(wasm_func.func_body());
+                let func_body = wasm_func.func_body();
+                let instance = *wasm_func.instance();
+                let header = self.code_map.header(func_body);
+                #[cfg(feature = "debugger")]
+                let params_end = self.value_stack.len();
+                // Note: The callee is prepared before the caller frame is pushed so that
+                //       a trapping preparation leaves no duplicate frame on the call stack.
                 self.value_stack.prepare_wasm_call(header)?;
+                if matches!(kind, CallKind::Nested) {
+                    self.push_caller_frame()?;
+                }
+                #[cfg(feature = "debugger")]
+                {
+                    self.params_end = params_end;
+                }
                 self.sp = self.value_stack.stack_ptr();
-                self.cache.update_instance(wasm_func.instance());
+                self.cache.update_instance(&instance);
                 self.ip = self.code_map.instr_ptr(header.iref());
+                #[cfg(feature = "profiler")]
+                self.profile_call(instance, func_body, kind);
                 Ok(CallOutcome::Continue)
             }
             FuncEntity::Host(_host_func) => {
+                if matches!(kind, CallKind::Nested) {
+                    self.push_caller_frame()?;
+                }
+                // Note: A tail called host function replaces its calling Wasm function.
+                #[cfg(feature = "profiler")]
+                if matches!(kind, CallKind::Tail) {
+                    self.profile_return();
+                }
                 self.cache.reset();
                 Ok(CallOutcome::Call {
                     host_func: *func,
@@ -639,6 +922,19 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
         }
     }
 
+    /// Pushes the [`FuncFrame`] of the currently executed function onto the call stack.
+    ///
+    /// # Errors
+    ///
+    /// If the call stack exceeds its recursion limit.
+    #[inline(always)]
+    fn push_caller_frame(&mut self) -> Result<(), TrapCode> {
+        let frame = FuncFrame::new(self.ip, self.cache.instance());
+        #[cfg(feature = "debugger")]
+        let frame = frame.with_params_end(self.params_end);
+        self.call_stack.push(frame)
+    }
+
     /// Calls the given internal [`CompiledFunc`].
     ///
     /// This also prepares the instruction pointer and stack pointer for
@@ -646,19 +942,28 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
     /// with the outer structures.
     #[inline(always)]
     fn call_func_internal(&mut self, func: CompiledFunc, kind: CallKind) -> Result<(), TrapCode> {
+        self.ctx.check_interrupt()?;
         self.next_instr_at(match kind {
             CallKind::Nested => 1,
             CallKind::Tail => 2,
         });
         self.sync_stack_ptr();
-        if matches!(kind, CallKind::Nested) {
-            self.call_stack
-                .push(FuncFrame::new(self.ip, self.cache.instance()))?;
-        }
         let header = self.code_map.header(func);
+        #[cfg(feature = "debugger")]
+        let params_end = self.value_stack.len();
+        // Note: See `call_func` for why the callee is prepared first.
         self.value_stack.prepare_wasm_call(header)?;
+        if matches!(kind, CallKind::Nested) {
+            self.push_caller_frame()?;
+        }
+        #[cfg(feature = "debugger")]
+        {
+            self.params_end = params_end;
+        }
         self.sp = self.value_stack.stack_ptr();
         self.ip = self.code_map.instr_ptr(header.iref());
+        #[cfg(feature = "profiler")]
+        self.profile_call(*self.cache.instance(), func, kind);
         Ok(())
     }
 
@@ -670,10 +975,16 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
     fn ret(&mut self, drop_keep: DropKeep) -> ReturnOutcome {
         self.sp.drop_keep(drop_keep);
         self.sync_stack_ptr();
+        #[cfg(feature = "profiler")]
+        self.profile_return();
         match self.call_stack.pop() {
             Some(caller) => {
                 self.ip = caller.ip();
                 self.cache.update_instance(caller.instance());
+                #[cfg(feature = "debugger")]
+                {
+                    self.params_end = caller.params_end();
+                }
                 ReturnOutcome::Wasm
             }
             None => ReturnOutcome::Host,
@@ -844,6 +1155,14 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
         self.try_next_instr()
     }
 
+    #[inline(always)]
+    fn visit_count_coverage(&mut self, counter: CounterIdx) {
+        // Just like `ConsumeFuel` these `wasmi` instructions are only
+        // generated if code coverage instrumentation is enabled.
+        self.ctx.coverage_mut().hit(counter);
+        self.next_instr()
+    }
+
     /// Fetches the [`DropKeep`] parameter for an instruction.
     ///
     /// # Note
@@ -883,44 +1202,44 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
     }
 
     #[inline(always)]
-    fn visit_br(&mut self, offset: BranchOffset) {
+    fn visit_br(&mut self, offset: BranchOffset) -> Result<(), TrapCode> {
         self.branch_to(offset)
     }
 
     #[inline(always)]
-    fn visit_br_if_eqz(&mut self, offset: BranchOffset) {
+    fn visit_br_if_eqz(&mut self, offset: BranchOffset) -> Result<(), TrapCode> {
         let condition = self.sp.pop_as();
         if condition {
-            self.next_instr()
+            self.try_next_instr()
         } else {
             self.branch_to(offset)
         }
     }
 
     #[inline(always)]
-    fn visit_br_if_nez(&mut self, offset: BranchOffset) {
+    fn visit_br_if_nez(&mut self, offset: BranchOffset) -> Result<(), TrapCode> {
         let condition = self.sp.pop_as();
         if condition {
             self.branch_to(offset)
         } else {
-            self.next_instr()
+            self.try_next_instr()
         }
     }
 
     #[inline(always)]
-    fn visit_br_adjust(&mut self, offset: BranchOffset) {
+    fn visit_br_adjust(&mut self, offset: BranchOffset) -> Result<(), TrapCode> {
         let drop_keep = self.fetch_drop_keep(1);
         self.branch_to_and_adjust(offset, drop_keep)
     }
 
     #[inline(always)]
-    fn visit_br_adjust_if_nez(&mut self, offset: BranchOffset) {
+    fn visit_br_adjust_if_nez(&mut self, offset: BranchOffset) -> Result<(), TrapCode> {
         let condition = self.sp.pop_as();
         if condition {
             let drop_keep = self.fetch_drop_keep(1);
             self.branch_to_and_adjust(offset, drop_keep)
         } else {
-            self.next_instr_at(2)
+            self.try_next_instr_at(2)
         }
     }
 
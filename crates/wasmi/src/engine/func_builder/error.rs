@@ -82,6 +82,12 @@ impl Display for TranslationError {
                     "fuel required to execute a block is out of bounds for wasmi bytecode"
                 )
             }
+            TranslationErrorInner::CounterIdxOutOfBounds => {
+                write!(
+                    f,
+                    "coverage counter index is out of bounds for wasmi bytecode"
+                )
+            }
         }
     }
 }
@@ -105,4 +111,6 @@ pub enum TranslationErrorInner {
     BlockFuelOutOfBounds,
     /// The constant reference index is out of bounds.
     ConstRefOutOfBounds,
+    /// The coverage counter index is out of bounds.
+    CounterIdxOutOfBounds,
 }
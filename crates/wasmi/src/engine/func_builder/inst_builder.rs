@@ -77,6 +77,14 @@ pub struct InstructionsBuilder {
     insts: Vec<Instruction>,
     /// All labels and their uses.
     labels: LabelRegistry,
+    /// The Wasm bytecode offsets of the instructions of the partially constructed function body.
+    ///
+    /// # Note
+    ///
+    /// Each entry maps the first instruction originating from a Wasm operator
+    /// to the offset of that Wasm operator within the Wasm module.
+    /// Subsequent instructions without their own entry originate from the same Wasm operator.
+    offsets: Vec<(Instr, usize)>,
 }
 
 impl InstructionsBuilder {
@@ -84,6 +92,7 @@ impl InstructionsBuilder {
     pub fn reset(&mut self) {
         self.insts.clear();
         self.labels.reset();
+        self.offsets.clear();
     }
 
     /// Returns the current instruction pointer as index.
@@ -91,6 +100,21 @@ impl InstructionsBuilder {
         Instr::from_usize(self.insts.len())
     }
 
+    /// Updates the Wasm bytecode offset of the Wasm operator that is translated next.
+    ///
+    /// # Note
+    ///
+    /// All instructions pushed until the next call are attributed to the Wasm operator at `pos`.
+    /// Instructions pushed before the first Wasm operator are attributed to the first Wasm operator.
+    pub fn update_pos(&mut self, pos: usize) {
+        let pc = self.current_pc();
+        match self.offsets.last_mut() {
+            Some((instr, offset)) if *instr == pc => *offset = pos,
+            Some(_) => self.offsets.push((pc, pos)),
+            None => self.offsets.push((Instr::from_u32(0), pos)),
+        }
+    }
+
     /// Creates a new unresolved label and returns an index to it.
     pub fn new_label(&mut self) -> LabelRef {
         self.labels.new_label()
@@ -198,7 +222,15 @@ impl InstructionsBuilder {
         local_stack_height: usize,
     ) -> Result<(), TranslationError> {
         self.update_branch_offsets()?;
-        engine.init_func(func, len_locals, local_stack_height, self.insts.drain(..));
+        engine.init_func(
+            func,
+            len_locals,
+            local_stack_height,
+            self.insts.drain(..),
+            self.offsets
+                .drain(..)
+                .map(|(instr, offset)| (instr.into_usize(), offset)),
+        );
         Ok(())
     }
 
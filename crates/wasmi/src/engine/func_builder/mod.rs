@@ -80,6 +80,7 @@ impl<'parser> FuncBuilder<'parser> {
     /// Updates the current position within the Wasm binary while parsing operators.
     pub fn update_pos(&mut self, pos: usize) {
         self.pos = pos;
+        self.translator.update_pos(pos);
     }
 
     /// Returns the current position within the Wasm binary while parsing operators.
@@ -87,6 +88,11 @@ impl<'parser> FuncBuilder<'parser> {
         self.pos
     }
 
+    /// Returns the number of `wasmi` bytecode instructions emitted so far.
+    pub fn len_instrs(&self) -> usize {
+        self.translator.len_instrs()
+    }
+
     /// Finishes constructing the function by initializing its [`CompiledFunc`].
     pub fn finish(mut self, offset: usize) -> Result<ReusableAllocations, TranslationError> {
         self.validator.finish(offset)?;
@@ -182,6 +182,7 @@ impl<'parser> FuncTranslator<'parser> {
             self.fuel_costs()
                 .fuel_for_locals(u64::from(self.locals.len_registered())),
         )?;
+        self.push_coverage_counter()?;
         Ok(())
     }
 
@@ -195,6 +196,16 @@ impl<'parser> FuncTranslator<'parser> {
         )
     }
 
+    /// Updates the Wasm bytecode offset of the Wasm operator that is translated next.
+    pub fn update_pos(&mut self, pos: usize) {
+        self.alloc.inst_builder.update_pos(pos);
+    }
+
+    /// Returns the number of `wasmi` bytecode instructions emitted so far.
+    pub fn len_instrs(&self) -> usize {
+        self.alloc.inst_builder.current_pc().into_usize()
+    }
+
     /// Consumes `self` and returns the underlying reusable [`FuncTranslatorAllocations`].
     pub fn into_allocations(self) -> FuncTranslatorAllocations {
         self.alloc
@@ -213,6 +224,24 @@ impl<'parser> FuncTranslator<'parser> {
         self.engine().config().get_consume_fuel()
     }
 
+    /// Returns `true` if code coverage instrumentation is enabled for the [`Engine`].
+    fn is_coverage_enabled(&self) -> bool {
+        self.engine().config().get_coverage()
+    }
+
+    /// Pushes an [`Instruction::CountCoverage`] for the basic block starting at the current position.
+    ///
+    /// Does nothing if code coverage instrumentation is disabled.
+    fn push_coverage_counter(&mut self) -> Result<(), TranslationError> {
+        if self.is_coverage_enabled() {
+            let counter = self.engine().alloc_counter()?;
+            self.alloc
+                .inst_builder
+                .push_inst(Instruction::CountCoverage(counter));
+        }
+        Ok(())
+    }
+
     /// Creates an [`Instruction::ConsumeFuel`] with base costs.
     fn make_consume_fuel_base(&self) -> Instruction {
         Instruction::consume_fuel(self.fuel_costs().base).expect("base fuel costs must be valid")
@@ -223,6 +252,25 @@ impl<'parser> FuncTranslator<'parser> {
         self.engine().config().fuel_costs()
     }
 
+    /// Returns the fuel costs of an arithmetic or comparison `inst` operating on `value_type` values.
+    fn fuel_for_operation(&self, value_type: ValueType, inst: &Instruction) -> u64 {
+        let costs = self.fuel_costs();
+        match inst {
+            Instruction::I32DivS
+            | Instruction::I32DivU
+            | Instruction::I32RemS
+            | Instruction::I32RemU
+            | Instruction::I64DivS
+            | Instruction::I64DivU
+            | Instruction::I64RemS
+            | Instruction::I64RemU => costs.div,
+            _ => match value_type {
+                ValueType::F32 | ValueType::F64 => costs.float,
+                _ => costs.base,
+            },
+        }
+    }
+
     /// Returns the most recent [`ConsumeFuel`] instruction in the translation process.
     ///
     /// Returns `None` if gas metering is disabled.
@@ -597,11 +645,11 @@ impl<'parser> FuncTranslator<'parser> {
     /// - `{i32, u32, i64, u64, f32, f64}.ge`
     fn translate_binary_cmp(
         &mut self,
-        _input_type: ValueType,
+        input_type: ValueType,
         inst: Instruction,
     ) -> Result<(), TranslationError> {
         self.translate_if_reachable(|builder| {
-            builder.bump_fuel_consumption(builder.fuel_costs().base)?;
+            builder.bump_fuel_consumption(builder.fuel_for_operation(input_type, &inst))?;
             builder.stack_height.pop2();
             builder.stack_height.push();
             builder.alloc.inst_builder.push_inst(inst);
@@ -627,11 +675,11 @@ impl<'parser> FuncTranslator<'parser> {
     /// - `{f32, f64}.sqrt`
     fn translate_unary_operation(
         &mut self,
-        _value_type: ValueType,
+        value_type: ValueType,
         inst: Instruction,
     ) -> Result<(), TranslationError> {
         self.translate_if_reachable(|builder| {
-            builder.bump_fuel_consumption(builder.fuel_costs().base)?;
+            builder.bump_fuel_consumption(builder.fuel_for_operation(value_type, &inst))?;
             builder.alloc.inst_builder.push_inst(inst);
             Ok(())
         })
@@ -660,11 +708,11 @@ impl<'parser> FuncTranslator<'parser> {
     /// - `{f32, f64}.copysign`
     fn translate_binary_operation(
         &mut self,
-        _value_type: ValueType,
+        value_type: ValueType,
         inst: Instruction,
     ) -> Result<(), TranslationError> {
         self.translate_if_reachable(|builder| {
-            builder.bump_fuel_consumption(builder.fuel_costs().base)?;
+            builder.bump_fuel_consumption(builder.fuel_for_operation(value_type, &inst))?;
             builder.stack_height.pop2();
             builder.stack_height.push();
             builder.alloc.inst_builder.push_inst(inst);
@@ -690,12 +738,18 @@ impl<'parser> FuncTranslator<'parser> {
     /// - `f64.reinterpret_i64`
     fn translate_conversion(
         &mut self,
-        _input_type: ValueType,
-        _output_type: ValueType,
+        input_type: ValueType,
+        output_type: ValueType,
         inst: Instruction,
     ) -> Result<(), TranslationError> {
         self.translate_if_reachable(|builder| {
-            builder.bump_fuel_consumption(builder.fuel_costs().base)?;
+            let fuel = match (input_type, output_type) {
+                (ValueType::F32 | ValueType::F64, _) | (_, ValueType::F32 | ValueType::F64) => {
+                    builder.fuel_costs().float
+                }
+                _ => builder.fuel_costs().base,
+            };
+            builder.bump_fuel_consumption(fuel)?;
             builder.alloc.inst_builder.push_inst(inst);
             Ok(())
         })
@@ -881,6 +935,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator<'a> {
                     .inst_builder
                     .push_inst(self.make_consume_fuel_base())
             });
+            self.push_coverage_counter()?;
             self.alloc.control_frames.push_frame(LoopControlFrame::new(
                 block_type,
                 header,
@@ -915,6 +970,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator<'a> {
                     .inst_builder
                     .push_inst(self.make_consume_fuel_base())
             });
+            self.push_coverage_counter()?;
             self.alloc.control_frames.push_frame(IfControlFrame::new(
                 block_type,
                 end_label,
@@ -976,6 +1032,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator<'a> {
                 .push_inst(self.make_consume_fuel_base());
             if_frame.update_consume_fuel_instr(consume_fuel);
         });
+        self.push_coverage_counter()?;
         // We need to reset the value stack to exactly how it has been
         // when entering the `if` in the first place so that the `else`
         // block has the same parameters on top of the stack.
@@ -1010,6 +1067,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator<'a> {
         // These bindings are required because of borrowing issues.
         let frame_reachable = frame.is_reachable();
         let frame_stack_height = frame.stack_height();
+        let frame_kind = frame.kind();
         if self.alloc.control_frames.len() == 1 {
             // If the control flow frames stack is empty after this point
             // we know that we are ending the function body `block`
@@ -1019,6 +1077,11 @@ impl<'a> VisitOperator<'a> for FuncTranslator<'a> {
             // The following code is only reachable if the ended control flow
             // frame was reachable upon entering to begin with.
             self.reachable = frame_reachable;
+            // The `end` of a `block` or `if` may be reached via branches
+            // and therefore starts a new basic block.
+            if frame_reachable && !matches!(frame_kind, ControlFrameKind::Loop) {
+                self.push_coverage_counter()?;
+            }
         }
         if let Some(frame_stack_height) = frame_stack_height {
             self.stack_height.shrink_to(frame_stack_height);
@@ -1090,6 +1153,8 @@ impl<'a> VisitOperator<'a> for FuncTranslator<'a> {
                         .push_inst(Instruction::ReturnIfNez(drop_keep));
                 }
             }
+            // The fallthrough of `br_if` starts a new basic block.
+            builder.push_coverage_counter()?;
             Ok(())
         })
     }
@@ -1253,7 +1318,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator<'a> {
             let table = TableIdx::from(table_index);
             builder.stack_height.pop1();
             let drop_keep = builder.drop_keep_return_call(&func_type)?;
-            builder.bump_fuel_consumption(builder.fuel_costs().call)?;
+            builder.bump_fuel_consumption(builder.fuel_costs().call_indirect)?;
             builder.bump_fuel_consumption(builder.fuel_costs().fuel_for_drop_keep(drop_keep))?;
             builder
                 .alloc
@@ -1308,7 +1373,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator<'a> {
         _table_byte: u8,
     ) -> Result<(), TranslationError> {
         self.translate_if_reachable(|builder| {
-            builder.bump_fuel_consumption(builder.fuel_costs().call)?;
+            builder.bump_fuel_consumption(builder.fuel_costs().call_indirect)?;
             let func_type = SignatureIdx::from(func_type_index);
             let table = TableIdx::from(table_index);
             builder.stack_height.pop1();
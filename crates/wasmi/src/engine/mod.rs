@@ -1,25 +1,55 @@
 //! The `wasmi` interpreter.
 
+mod backtrace;
 pub mod bytecode;
 mod cache;
 pub mod code_map;
 mod config;
 mod const_pool;
+mod coverage;
+#[cfg(feature = "debugger")]
+mod debugger;
+mod disasm;
 pub mod executor;
 mod func_args;
 mod func_builder;
 mod func_types;
+#[cfg(feature = "profiler")]
+mod profiler;
 mod resumable;
 pub mod stack;
 mod traits;
 
+#[cfg(feature = "trace")]
+mod trace;
+
 #[cfg(test)]
 mod tests;
 
+#[cfg(feature = "debugger")]
+pub use self::debugger::{DebugCall, DebugFrame, Debugger, DebuggerError, PausedInvocation};
+#[cfg(feature = "profiler")]
+pub use self::profiler::{FuncProfile, Profile, Profiler};
+#[cfg(feature = "trace")]
+pub use self::trace::{TraceEvent, TraceHook, TraceInstr};
+use self::{
+    backtrace::capture_backtrace,
+    bytecode::{CounterIdx, Instruction},
+    cache::InstanceCache,
+    code_map::CodeMap,
+    const_pool::{ConstPool, ConstPoolView, ConstRef},
+    coverage::coverage_report,
+    disasm::disassemble,
+    executor::{execute_wasm, WasmOutcome},
+    func_types::FuncTypeRegistry,
+    resumable::ResumableCallBase,
+    stack::{FuncFrame, HostCallError, Stack, ValueStack},
+};
 pub use self::{
     bytecode::DropKeep,
     code_map::CompiledFunc,
-    config::{Config, FuelConsumptionMode},
+    config::{Config, FuelConsumptionMode, FuelCosts, FuelCostsError},
+    coverage::{BlockCoverage, CoverageReport, FuncCoverage},
     func_builder::{
         FuncBuilder,
         FuncTranslatorAllocations,
@@ -31,30 +61,29 @@ pub use self::{
     stack::StackLimits,
     traits::{CallParams, CallResults},
 };
-use self::{
-    bytecode::Instruction,
-    cache::InstanceCache,
-    code_map::CodeMap,
-    const_pool::{ConstPool, ConstPoolView, ConstRef},
-    executor::{execute_wasm, WasmOutcome},
-    func_types::FuncTypeRegistry,
-    resumable::ResumableCallBase,
-    stack::{FuncFrame, Stack, ValueStack},
-};
 pub(crate) use self::{
+    coverage::CoverageCounters,
     func_args::{FuncFinished, FuncParams, FuncResults},
     func_types::DedupFuncType,
 };
+#[cfg(feature = "profiler")]
+use crate::StoreInner;
+#[cfg(feature = "debugger")]
+use crate::Value;
 use crate::{
     core::{Trap, TrapCode},
     func::FuncEntity,
+    store::ExecutionGuard,
     AsContext,
     AsContextMut,
+    CallHook,
     Func,
     FuncType,
+    Module,
+    StoreContext,
     StoreContextMut,
 };
-use alloc::{sync::Arc, vec::Vec};
+use alloc::{string::String, sync::Arc, vec::Vec};
 use core::sync::atomic::{AtomicU32, Ordering};
 use spin::{Mutex, RwLock};
 use wasmi_arena::{ArenaIndex, GuardedEntity};
@@ -148,6 +177,20 @@ impl Engine {
         self.inner.alloc_const(value)
     }
 
+    /// Allocates a new coverage counter to the [`Engine`].
+    ///
+    /// # Errors
+    ///
+    /// If too many coverage counters have been allocated for the [`Engine`] this way.
+    pub(super) fn alloc_counter(&self) -> Result<CounterIdx, TranslationError> {
+        self.inner.alloc_counter()
+    }
+
+    /// Returns the number of coverage counters allocated by the [`Engine`] so far.
+    pub(crate) fn len_counters(&self) -> usize {
+        self.inner.res.read().len_counters
+    }
+
     /// Resolves a deduplicated function type into a [`FuncType`] entity.
     ///
     /// # Panics
@@ -163,9 +206,12 @@ impl Engine {
 
     /// Allocates a new uninitialized [`CompiledFunc`] to the [`Engine`].
     ///
+    /// The `func_index` is the index of the function within the function
+    /// index space of the module that defines it.
+    ///
     /// Returns a [`CompiledFunc`] reference to allow accessing the allocated [`CompiledFunc`].
-    pub(super) fn alloc_func(&self) -> CompiledFunc {
-        self.inner.alloc_func()
+    pub(super) fn alloc_func(&self, func_index: u32) -> CompiledFunc {
+        self.inner.alloc_func(func_index)
     }
 
     /// Initializes the uninitialized [`CompiledFunc`] for the [`Engine`].
@@ -174,17 +220,24 @@ impl Engine {
     ///
     /// - If `func` is an invalid [`CompiledFunc`] reference for this [`CodeMap`].
     /// - If `func` refers to an already initialized [`CompiledFunc`].
-    pub(super) fn init_func<I>(
+    ///
+    /// # Note
+    ///
+    /// The `offsets` map indices of `instrs` to the Wasm bytecode offsets of their originating
+    /// Wasm operators. Instructions without their own entry belong to the preceding entry.
+    pub(super) fn init_func<I, O>(
         &self,
         func: CompiledFunc,
         len_locals: usize,
         local_stack_height: usize,
         instrs: I,
+        offsets: O,
     ) where
         I: IntoIterator<Item = Instruction>,
+        O: IntoIterator<Item = (usize, usize)>,
     {
         self.inner
-            .init_func(func, len_locals, local_stack_height, instrs)
+            .init_func(func, len_locals, local_stack_height, instrs, offsets)
     }
 
     /// Resolves the [`CompiledFunc`] to the underlying `wasmi` bytecode instructions.
@@ -313,6 +366,97 @@ impl Engine {
         self.inner.resume_func(ctx, invocation, params, results)
     }
 
+    /// Executes the given [`Func`] with parameters `params` under the debugger.
+    ///
+    /// Stores the execution result into `results` upon a successful execution.
+    /// If the execution hits a breakpoint it returns a handle to the user
+    /// that allows to inspect the paused execution and resume it.
+    ///
+    /// # Note
+    ///
+    /// Assumes that the `params` and `results` are well typed.
+    ///
+    /// # Errors
+    ///
+    /// When encountering a Wasm or host trap during the execution of `func`.
+    #[cfg(feature = "debugger")]
+    pub(crate) fn execute_func_debug<T>(
+        &self,
+        ctx: StoreContextMut<T>,
+        func: &Func,
+        params: impl CallParams,
+        results: &mut [Value],
+    ) -> Result<DebugCall, Trap> {
+        self.inner.execute_func_debug(ctx, func, params, results)
+    }
+
+    /// Resumes the paused `invocation` under the debugger.
+    ///
+    /// # Note
+    ///
+    /// Assumes that the `results` are well typed.
+    ///
+    /// # Errors
+    ///
+    /// When encountering a Wasm or host trap during the execution of `func`.
+    #[cfg(feature = "debugger")]
+    pub(crate) fn resume_func_debug<T>(
+        &self,
+        ctx: StoreContextMut<T>,
+        invocation: PausedInvocation,
+        results: &mut [Value],
+    ) -> Result<DebugCall, Trap> {
+        self.inner.resume_func_debug(ctx, invocation, results)
+    }
+
+    /// Returns the index of the first instruction of `func` that originates
+    /// from the Wasm operator at the Wasm bytecode `offset` if any.
+    #[cfg(feature = "debugger")]
+    pub(crate) fn resolve_wasm_offset(&self, func: CompiledFunc, offset: usize) -> Option<usize> {
+        self.inner.res.read().code_map.instr_index(func, offset)
+    }
+
+    /// Returns the position of the function in `funcs` whose body contains the Wasm bytecode `offset`.
+    ///
+    /// # Note
+    ///
+    /// The `funcs` must be ordered by the position of their bodies in the Wasm module.
+    #[cfg(feature = "debugger")]
+    pub(crate) fn resolve_func_at_offset(
+        &self,
+        funcs: &[CompiledFunc],
+        offset: usize,
+    ) -> Option<usize> {
+        let res = self.inner.res.read();
+        let start_of = |func: &CompiledFunc| {
+            res.code_map
+                .wasm_offsets(*func)
+                .next()
+                .map(|(_, start)| start)
+        };
+        let pos = funcs.partition_point(|func| match start_of(func) {
+            Some(start) => start <= offset,
+            None => true,
+        });
+        pos.checked_sub(1)
+    }
+
+    /// Returns the disassembly of the `wasmi` bytecode of all internal functions of `module`.
+    pub(crate) fn disassemble(&self, module: &Module) -> String {
+        disassemble(&self.inner.res.read(), module)
+    }
+
+    /// Returns the [`CoverageReport`] of all internal functions of `module` with hits from `counters`.
+    pub(crate) fn coverage(&self, module: &Module, counters: &CoverageCounters) -> CoverageReport {
+        coverage_report(&self.inner.res.read(), module, counters)
+    }
+
+    /// Finishes the `profiler` of the store `ctx` and returns its recorded [`Profile`].
+    #[cfg(feature = "profiler")]
+    pub(crate) fn finish_profiler(&self, ctx: &StoreInner, profiler: Profiler) -> Profile {
+        profiler.finish(&self.inner.res.read(), ctx)
+    }
+
     /// Recycles the given [`Stack`] for reuse in the [`Engine`].
     pub(crate) fn recycle_stack(&self, stack: Stack) {
         self.inner.recycle_stack(stack)
@@ -402,11 +546,23 @@ impl EngineInner {
         self.res.write().const_pool.alloc(value)
     }
 
+    /// Allocates a new coverage counter to the [`EngineInner`].
+    ///
+    /// # Errors
+    ///
+    /// If too many coverage counters have been allocated for the [`EngineInner`] this way.
+    fn alloc_counter(&self) -> Result<CounterIdx, TranslationError> {
+        let mut res = self.res.write();
+        let counter = CounterIdx::try_from(res.len_counters)?;
+        res.len_counters += 1;
+        Ok(counter)
+    }
+
     /// Allocates a new uninitialized [`CompiledFunc`] to the [`EngineInner`].
     ///
     /// Returns a [`CompiledFunc`] reference to allow accessing the allocated [`CompiledFunc`].
-    fn alloc_func(&self) -> CompiledFunc {
-        self.res.write().code_map.alloc_func()
+    fn alloc_func(&self, func_index: u32) -> CompiledFunc {
+        self.res.write().code_map.alloc_func(func_index)
     }
 
     /// Initializes the uninitialized [`CompiledFunc`] for the [`EngineInner`].
@@ -415,19 +571,21 @@ impl EngineInner {
     ///
     /// - If `func` is an invalid [`CompiledFunc`] reference for this [`CodeMap`].
     /// - If `func` refers to an already initialized [`CompiledFunc`].
-    fn init_func<I>(
+    fn init_func<I, O>(
         &self,
         func: CompiledFunc,
         len_locals: usize,
         local_stack_height: usize,
         instrs: I,
+        offsets: O,
     ) where
         I: IntoIterator<Item = Instruction>,
+        O: IntoIterator<Item = (usize, usize)>,
     {
         self.res
             .write()
             .code_map
-            .init_func(func, len_locals, local_stack_height, instrs)
+            .init_func(func, len_locals, local_stack_height, instrs, offsets)
     }
 
     fn resolve_func_type<F, R>(&self, func_type: &DedupFuncType, f: F) -> R
@@ -448,7 +606,7 @@ impl EngineInner {
 
     fn execute_func<T, Results>(
         &self,
-        ctx: StoreContextMut<T>,
+        mut ctx: StoreContextMut<T>,
         func: &Func,
         params: impl CallParams,
         results: Results,
@@ -458,9 +616,10 @@ impl EngineInner {
     {
         let res = self.res.read();
         let mut stack = self.stacks.lock().reuse_or_new();
-        let results = EngineExecutor::new(&res, &mut stack)
-            .execute_func(ctx, func, params, results)
-            .map_err(TaggedTrap::into_trap);
+        let mut executor = EngineExecutor::new(&res, &mut stack);
+        let results = executor
+            .execute_func(ctx.as_context_mut(), func, params, results)
+            .map_err(|trap| executor.resolve_trap(ctx.as_context(), trap));
         self.stacks.lock().recycle(stack);
         results
     }
@@ -538,6 +697,95 @@ impl EngineInner {
         }
     }
 
+    #[cfg(feature = "debugger")]
+    fn execute_func_debug<T>(
+        &self,
+        mut ctx: StoreContextMut<T>,
+        func: &Func,
+        params: impl CallParams,
+        results: &mut [Value],
+    ) -> Result<DebugCall, Trap> {
+        let res = self.res.read();
+        let mut stack = self.stacks.lock().reuse_or_new();
+        ctx.as_context_mut()
+            .store
+            .inner
+            .debugger_mut()
+            .cancel_step();
+        let mut executor = EngineExecutor::new(&res, &mut stack);
+        let paused = executor
+            .execute_func_debug(ctx.as_context_mut(), func, params, results)
+            .map_err(|trap| executor.resolve_trap(ctx.as_context(), trap));
+        match paused {
+            Ok(true) => Ok(DebugCall::Paused(PausedInvocation::new(
+                &res,
+                &ctx.as_context().store.inner,
+                *func,
+                stack,
+            ))),
+            Ok(false) => {
+                ctx.as_context_mut()
+                    .store
+                    .inner
+                    .debugger_mut()
+                    .cancel_step();
+                self.stacks.lock().recycle(stack);
+                Ok(DebugCall::Finished)
+            }
+            Err(trap) => {
+                ctx.as_context_mut()
+                    .store
+                    .inner
+                    .debugger_mut()
+                    .cancel_step();
+                self.stacks.lock().recycle(stack);
+                Err(trap)
+            }
+        }
+    }
+
+    #[cfg(feature = "debugger")]
+    fn resume_func_debug<T>(
+        &self,
+        mut ctx: StoreContextMut<T>,
+        mut invocation: PausedInvocation,
+        results: &mut [Value],
+    ) -> Result<DebugCall, Trap> {
+        let res = self.res.read();
+        let func = invocation.func();
+        let mut stack = invocation.take_stack();
+        let mut executor = EngineExecutor::new(&res, &mut stack);
+        let paused = executor
+            .resume_func_debug(ctx.as_context_mut(), results)
+            .map_err(|trap| executor.resolve_trap(ctx.as_context(), trap));
+        match paused {
+            Ok(true) => Ok(DebugCall::Paused(PausedInvocation::new(
+                &res,
+                &ctx.as_context().store.inner,
+                func,
+                stack,
+            ))),
+            Ok(false) => {
+                ctx.as_context_mut()
+                    .store
+                    .inner
+                    .debugger_mut()
+                    .cancel_step();
+                self.stacks.lock().recycle(stack);
+                Ok(DebugCall::Finished)
+            }
+            Err(trap) => {
+                ctx.as_context_mut()
+                    .store
+                    .inner
+                    .debugger_mut()
+                    .cancel_step();
+                self.stacks.lock().recycle(stack);
+                Err(trap)
+            }
+        }
+    }
+
     fn recycle_stack(&self, stack: Stack) {
         self.stacks.lock().recycle(stack);
     }
@@ -559,6 +807,8 @@ pub struct EngineResources {
     /// The engine deduplicates function types to make the equality
     /// comparison very fast. This helps to speed up indirect calls.
     func_types: FuncTypeRegistry,
+    /// The number of coverage counters allocated by the engine.
+    len_counters: usize,
 }
 
 impl EngineResources {
@@ -569,6 +819,7 @@ impl EngineResources {
             code_map: CodeMap::default(),
             const_pool: ConstPool::default(),
             func_types: FuncTypeRegistry::new(engine_idx),
+            len_counters: 0,
         }
     }
 }
@@ -590,14 +841,6 @@ impl TaggedTrap {
             host_trap,
         }
     }
-
-    /// Returns the [`Trap`] of the [`TaggedTrap`].
-    pub fn into_trap(self) -> Trap {
-        match self {
-            TaggedTrap::Wasm(trap) => trap,
-            TaggedTrap::Host { host_trap, .. } => host_trap,
-        }
-    }
 }
 
 impl From<Trap> for TaggedTrap {
@@ -648,6 +891,75 @@ impl<'engine> EngineExecutor<'engine> {
     {
         self.stack.reset();
         self.stack.values.extend(params.call_params());
+        self.call_func(ctx.as_context_mut(), func)?;
+        let results = self.write_results_back(results)?;
+        Ok(results)
+    }
+
+    /// Executes the given [`Func`] using the given `params` under the debugger.
+    ///
+    /// Stores the execution result into `results` upon a successful execution.
+    /// Returns `true` if the execution has been paused by the debugger.
+    ///
+    /// # Errors
+    ///
+    /// When encountering a Wasm or host trap during the execution of `func`.
+    #[cfg(feature = "debugger")]
+    fn execute_func_debug<T>(
+        &mut self,
+        mut ctx: StoreContextMut<T>,
+        func: &Func,
+        params: impl CallParams,
+        results: &mut [Value],
+    ) -> Result<bool, TaggedTrap> {
+        self.stack.reset();
+        self.stack.frames.set_debug(true);
+        self.stack.values.extend(params.call_params());
+        self.call_func(ctx.as_context_mut(), func)?;
+        self.finish_debug(results)
+    }
+
+    /// Resumes the execution paused by the debugger.
+    ///
+    /// Stores the execution result into `results` upon a successful execution.
+    /// Returns `true` if the execution has been paused by the debugger again.
+    ///
+    /// # Errors
+    ///
+    /// When encountering a Wasm or host trap during the execution.
+    #[cfg(feature = "debugger")]
+    fn resume_func_debug<T>(
+        &mut self,
+        ctx: StoreContextMut<T>,
+        results: &mut [Value],
+    ) -> Result<bool, TaggedTrap> {
+        assert!(
+            self.stack.frames.peek().is_some(),
+            "a frame must be on the call stack upon resumption"
+        );
+        self.execute_wasm_func(ctx)?;
+        self.finish_debug(results)
+    }
+
+    /// Writes the results back into `results` unless the execution has been paused.
+    ///
+    /// Returns `true` if the execution has been paused by the debugger.
+    #[cfg(feature = "debugger")]
+    fn finish_debug(&mut self, results: &mut [Value]) -> Result<bool, TaggedTrap> {
+        // Note: A paused execution leaves its paused frame on the call stack.
+        if self.stack.frames.peek().is_some() {
+            return Ok(true);
+        }
+        self.write_results_back(results)?;
+        Ok(false)
+    }
+
+    /// Calls the given [`Func`] with its parameters on top of the value stack.
+    ///
+    /// # Errors
+    ///
+    /// When encountering a Wasm or host trap during the execution of `func`.
+    fn call_func<T>(&mut self, mut ctx: StoreContextMut<T>, func: &Func) -> Result<(), TaggedTrap> {
         match ctx.as_context().store.inner.resolve_func(func) {
             FuncEntity::Wasm(wasm_func) => {
                 self.stack
@@ -656,6 +968,8 @@ impl<'engine> EngineExecutor<'engine> {
             }
             FuncEntity::Host(host_func) => {
                 let host_func = *host_func;
+                self.stack
+                    .consume_host_fuel(&mut ctx, &host_func, &self.res.func_types)?;
                 self.stack.call_host_as_root(
                     ctx.as_context_mut(),
                     host_func,
@@ -663,8 +977,7 @@ impl<'engine> EngineExecutor<'engine> {
                 )?;
             }
         };
-        let results = self.write_results_back(results);
-        Ok(results)
+        Ok(())
     }
 
     /// Resumes the execution of the given [`Func`] using `params`.
@@ -695,7 +1008,7 @@ impl<'engine> EngineExecutor<'engine> {
             "a frame must be on the call stack upon resumption"
         );
         self.execute_wasm_func(ctx.as_context_mut())?;
-        let results = self.write_results_back(results);
+        let results = self.write_results_back(results)?;
         Ok(results)
     }
 
@@ -705,24 +1018,75 @@ impl<'engine> EngineExecutor<'engine> {
     ///
     /// The value stack is empty after this operation.
     ///
+    /// # Errors
+    ///
+    /// If the results cannot be decoded into the `results` buffer.
+    ///
     /// # Panics
     ///
     /// - If the `results` buffer length does not match the remaining amount of stack values.
     #[inline]
-    fn write_results_back<Results>(&mut self, results: Results) -> <Results as CallResults>::Results
+    fn write_results_back<Results>(
+        &mut self,
+        results: Results,
+    ) -> Result<<Results as CallResults>::Results, Trap>
     where
         Results: CallResults,
     {
         results.call_results(self.stack.values.drain())
     }
 
+    /// Executes the top most Wasm function on the [`Stack`] until the [`Stack`] is empty.
+    ///
+    /// # Note
+    ///
+    /// - Calls the [`CallHook`] of the [`Store`] upon entering and leaving Wasm.
+    /// - Interrupt requests issued before the execution started are discarded
+    ///   unless this is a nested execution started by a host function.
+    ///
+    /// # Errors
+    ///
+    /// - When encountering a Wasm or host trap during the execution of `func`.
+    /// - If the [`CallHook`] of the [`Store`] returns a trap.
+    ///
+    /// [`Store`]: crate::Store
+    fn execute_wasm_func<T>(&mut self, mut ctx: StoreContextMut<T>) -> Result<(), TaggedTrap> {
+        ctx.store.invoke_call_hook(CallHook::CallingWasm)?;
+        let mut guard = ExecutionGuard::new(ctx.as_context_mut());
+        #[cfg(feature = "profiler")]
+        self.profile_frames(&mut guard.as_context_mut().store.inner);
+        let result = self.execute_wasm_frames(guard.as_context_mut());
+        drop(guard);
+        let hook_result = ctx.store.invoke_call_hook(CallHook::ReturningFromWasm);
+        result?;
+        hook_result?;
+        Ok(())
+    }
+
+    /// Descends the profiled call stack into the Wasm functions on the [`Stack`].
+    ///
+    /// # Note
+    ///
+    /// The [`Stack`] holds more than the called function if a paused or
+    /// resumable execution is continued.
+    #[cfg(feature = "profiler")]
+    fn profile_frames(&self, ctx: &mut StoreInner) {
+        if let Some(profiler) = ctx.profiler_mut() {
+            for frame in self.stack.frames.frames() {
+                if let Some((func, _)) = self.res.code_map.resolve_instr_ptr(frame.ip()) {
+                    profiler.call(*frame.instance(), func);
+                }
+            }
+        }
+    }
+
     /// Executes the top most Wasm function on the [`Stack`] until the [`Stack`] is empty.
     ///
     /// # Errors
     ///
     /// When encountering a Wasm or host trap during the execution of `func`.
     #[inline(never)]
-    fn execute_wasm_func<T>(&mut self, mut ctx: StoreContextMut<T>) -> Result<(), TaggedTrap> {
+    fn execute_wasm_frames<T>(&mut self, mut ctx: StoreContextMut<T>) -> Result<(), TaggedTrap> {
         let mut cache = self
             .stack
             .frames
@@ -733,6 +1097,8 @@ impl<'engine> EngineExecutor<'engine> {
         loop {
             match self.execute_wasm(ctx.as_context_mut(), &mut cache)? {
                 WasmOutcome::Return => return Ok(()),
+                #[cfg(feature = "debugger")]
+                WasmOutcome::Pause => return Ok(()),
                 WasmOutcome::Call {
                     ref host_func,
                     instance,
@@ -742,25 +1108,48 @@ impl<'engine> EngineExecutor<'engine> {
                         FuncEntity::Wasm(_) => unreachable!("`func` must be a host function"),
                         FuncEntity::Host(host_func) => *host_func,
                     };
+                    // Note: Running out of fuel before calling the host function
+                    //       is a Wasm trap and thus must not be resumable.
+                    self.stack
+                        .consume_host_fuel(&mut ctx, &host_func, &self.res.func_types)
+                        .map_err(|trap_code| {
+                            self.with_backtrace(ctx.as_context(), trap_code.into(), false)
+                        })?;
                     let result = self.stack.call_host_impl(
                         ctx.as_context_mut(),
                         host_func,
                         Some(&instance),
                         &self.res.func_types,
                     );
-                    if self.stack.frames.peek().is_some() {
-                        // Case: There is a frame on the call stack.
-                        //
-                        // This is the default case and we can easily make host function
-                        // errors return a resumable call handle.
-                        result.map_err(|trap| TaggedTrap::host(*func, trap))?;
-                    } else {
-                        // Case: No frame is on the call stack. (edge case)
-                        //
-                        // This can happen if the host function was called by a tail call.
-                        // In this case we treat host function errors the same as if we called
-                        // the host function as root and do not allow to resume the call.
-                        result.map_err(TaggedTrap::Wasm)?;
+                    // Note: The time spent in the host function is attributed to its caller.
+                    #[cfg(feature = "profiler")]
+                    if let Some(profiler) = ctx.store.inner.profiler_mut() {
+                        profiler.attribute();
+                    }
+                    match result {
+                        Ok(()) => {}
+                        Err(HostCallError::Host(trap)) if self.stack.frames.peek().is_some() => {
+                            // Case: There is a frame on the call stack.
+                            //
+                            // This is the default case and we can easily make host function
+                            // errors return a resumable call handle.
+                            //
+                            // Note: The backtrace is captured via `resolve_trap` only
+                            //       once it is clear that the call is not resumed.
+                            return Err(TaggedTrap::host(*func, trap));
+                        }
+                        Err(error) => {
+                            // Case: The call hook trapped or no frame is on the call stack.
+                            //
+                            // Traps of the call hook did not originate from the host function
+                            // and thus must not be resumable.
+                            // No frame is on the call stack if the host function was called
+                            // by a tail call. In this case we treat host function errors the
+                            // same as if we called the host function as root and do not allow
+                            // to resume the call.
+                            let trap = error.into_trap();
+                            return Err(self.with_backtrace(ctx.as_context(), trap, false).into());
+                        }
                     }
                 }
             }
@@ -808,6 +1197,39 @@ impl<'engine> EngineExecutor<'engine> {
             const_pool,
             &mut resource_limiter,
         )
-        .map_err(make_trap)
+        .map_err(|trap_code| {
+            // Note: Stack overflows occur upon calls after the instruction pointer
+            //       of the trapping frame already has been advanced past the call.
+            let trapped = !matches!(trap_code, TrapCode::StackOverflow);
+            self.with_backtrace(ctx.as_context(), make_trap(trap_code), trapped)
+        })
+    }
+
+    /// Returns the [`Trap`] of the `trap` of a call that cannot be resumed.
+    ///
+    /// # Note
+    ///
+    /// Host traps are resumable which is why their backtraces are not captured
+    /// eagerly. Instead they are captured here from the unchanged call stack.
+    #[cold]
+    fn resolve_trap<T>(&self, ctx: StoreContext<T>, trap: TaggedTrap) -> Trap {
+        match trap {
+            TaggedTrap::Wasm(trap) => trap,
+            TaggedTrap::Host { host_trap, .. } => self.with_backtrace(ctx, host_trap, false),
+        }
+    }
+
+    /// Appends the Wasm function frames on the call stack to the backtrace of the `trap`.
+    ///
+    /// # Note
+    ///
+    /// If `trapped` is `true` the innermost frame on the call stack is the trapping frame.
+    /// Otherwise all frames on the call stack are caller frames.
+    #[cold]
+    fn with_backtrace<T>(&self, ctx: StoreContext<T>, mut trap: Trap, trapped: bool) -> Trap {
+        let frames = self.stack.frames.frames();
+        let backtrace = capture_backtrace(self.res, &ctx.store.inner, frames, trapped);
+        trap.extend_backtrace(backtrace);
+        trap
     }
 }
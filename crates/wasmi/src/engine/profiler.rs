@@ -0,0 +1,348 @@
+//! Sampling profiler of Wasm executions.
+//!
+//! # Note
+//!
+//! This is only available with the `profiler` crate feature enabled.
+
+use super::{backtrace::frame_info, CompiledFunc, EngineResources};
+use crate::{core::FrameInfo, Instance, StoreInner};
+use alloc::{
+    collections::BTreeMap,
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::{fmt::Write, num::NonZeroU64};
+use std::time::Instant;
+
+/// The profiling state of a [`Store`].
+///
+/// Attributes the elapsed time to the currently executed Wasm function every
+/// `interval` executed `wasmi` bytecode instructions and whenever a host function
+/// called by Wasm returns.
+///
+/// # Note
+///
+/// The profiled call stacks form a call tree whose current node is updated upon
+/// every call and return so that sampling does not need to walk the call stack.
+///
+/// [`Store`]: crate::Store
+#[derive(Debug)]
+pub struct Profiler {
+    /// The number of executed instructions in between two samples.
+    interval: NonZeroU64,
+    /// The number of instructions to execute until the next sample is taken.
+    countdown: u64,
+    /// The nodes of the call tree of all profiled call stacks.
+    ///
+    /// # Note
+    ///
+    /// The first node is the root of the call tree and represents the host.
+    nodes: Vec<Node>,
+    /// The node of the currently executed Wasm function.
+    current: usize,
+    /// The nodes that were current when the executions in progress started.
+    entered: Vec<usize>,
+    /// The point in time up to which the elapsed time has been attributed.
+    last: Instant,
+    /// The instances of all profiled functions used to resolve their names.
+    instances: BTreeMap<CompiledFunc, Instance>,
+}
+
+/// A node of the call tree of a [`Profiler`].
+#[derive(Debug)]
+struct Node {
+    /// The parent node of the caller.
+    parent: usize,
+    /// The called function or `None` for the root node.
+    func: Option<CompiledFunc>,
+    /// The nodes of the functions called by this node.
+    children: BTreeMap<CompiledFunc, usize>,
+    /// The number of samples taken while the node was current.
+    samples: u64,
+    /// The elapsed time in nanoseconds attributed to the node.
+    costs: u64,
+}
+
+impl Node {
+    /// Creates a new [`Node`] for the call of `func` by `parent`.
+    fn new(parent: usize, func: Option<CompiledFunc>) -> Self {
+        Self {
+            parent,
+            func,
+            children: BTreeMap::new(),
+            samples: 0,
+            costs: 0,
+        }
+    }
+}
+
+impl Profiler {
+    /// The index of the root node of the call tree.
+    const ROOT: usize = 0;
+
+    /// Creates a new [`Profiler`] sampling every `interval` executed instructions.
+    pub fn new(interval: NonZeroU64) -> Self {
+        Self {
+            interval,
+            countdown: interval.get(),
+            nodes: Vec::from([Node::new(Self::ROOT, None)]),
+            current: Self::ROOT,
+            entered: Vec::new(),
+            last: Instant::now(),
+            instances: BTreeMap::new(),
+        }
+    }
+
+    /// Accounts for the execution of a single instruction.
+    ///
+    /// Returns `true` if a sample must be taken before the instruction is executed.
+    #[inline(always)]
+    pub fn tick(&mut self) -> bool {
+        self.countdown -= 1;
+        if self.countdown == 0 {
+            self.countdown = self.interval.get();
+            return true;
+        }
+        false
+    }
+
+    /// Signals that a Wasm execution starts.
+    ///
+    /// # Note
+    ///
+    /// The time elapsed in between two outermost executions is not attributed.
+    pub fn enter(&mut self) {
+        if self.entered.is_empty() {
+            self.last = Instant::now();
+        }
+        self.entered.push(self.current);
+    }
+
+    /// Signals that a Wasm execution has ended.
+    ///
+    /// Attributes the remaining elapsed time and restores the current
+    /// node of the execution that started the ended execution.
+    pub fn leave(&mut self) {
+        self.attribute();
+        if let Some(node) = self.entered.pop() {
+            self.current = node;
+        }
+    }
+
+    /// Descends the call tree into the Wasm function `func` of `instance`.
+    pub fn call(&mut self, instance: Instance, func: CompiledFunc) {
+        let next = self.nodes.len();
+        let node = *self.nodes[self.current]
+            .children
+            .entry(func)
+            .or_insert(next);
+        if node == next {
+            self.nodes.push(Node::new(self.current, Some(func)));
+            self.instances.entry(func).or_insert(instance);
+        }
+        self.current = node;
+    }
+
+    /// Ascends the call tree upon a return of the current Wasm function.
+    pub fn ret(&mut self) {
+        self.current = self.nodes[self.current].parent;
+    }
+
+    /// Records a sample of the current Wasm function and attributes the elapsed time to it.
+    #[cold]
+    pub fn sample(&mut self) {
+        self.nodes[self.current].samples += 1;
+        self.attribute();
+    }
+
+    /// Attributes the time elapsed since the last attribution to the current Wasm function.
+    pub fn attribute(&mut self) {
+        let now = Instant::now();
+        let elapsed = now.saturating_duration_since(self.last).as_nanos();
+        let node = &mut self.nodes[self.current];
+        node.costs = node
+            .costs
+            .saturating_add(u64::try_from(elapsed).unwrap_or(u64::MAX));
+        self.last = now;
+    }
+
+    /// Finishes profiling and returns the recorded [`Profile`].
+    pub fn finish(self, res: &EngineResources, ctx: &StoreInner) -> Profile {
+        let mut indices = BTreeMap::new();
+        let mut funcs = Vec::with_capacity(self.instances.len());
+        for (compiled, instance) in &self.instances {
+            indices.insert(*compiled, funcs.len());
+            let frame = frame_info(res, ctx, instance, *compiled, None).unwrap_or_else(|| {
+                panic!("missing Wasm function for {compiled:?} in {instance:?}")
+            });
+            funcs.push(frame);
+        }
+        let stacks = self
+            .nodes
+            .iter()
+            .filter(|node| node.func.is_some() && (node.samples != 0 || node.costs != 0))
+            .map(|node| {
+                let mut stack = Vec::new();
+                let mut cursor = node;
+                while let Some(func) = cursor.func {
+                    stack.push(indices[&func]);
+                    cursor = &self.nodes[cursor.parent];
+                }
+                stack.reverse();
+                (stack, node.costs)
+            })
+            .collect();
+        Profile {
+            interval: self.interval.get(),
+            funcs,
+            stacks,
+        }
+    }
+}
+
+/// A profile of Wasm executions recorded by a [`Store`].
+///
+/// # Note
+///
+/// - All costs are measured in nanoseconds of elapsed time.
+/// - The time spent in host functions is attributed to their calling Wasm function.
+///
+/// [`Store`]: crate::Store
+#[derive(Debug, Clone)]
+pub struct Profile {
+    /// The number of executed instructions in between two samples.
+    interval: u64,
+    /// The sampled Wasm functions.
+    funcs: Vec<FrameInfo>,
+    /// The sampled call stacks as indices into `funcs` from outermost to innermost with their costs.
+    stacks: Vec<(Vec<usize>, u64)>,
+}
+
+/// The costs of a single Wasm function within a [`Profile`].
+#[derive(Debug, Clone)]
+pub struct FuncProfile<'a> {
+    /// The function that is profiled.
+    frame: &'a FrameInfo,
+    /// The costs of the function including its callees.
+    inclusive: u64,
+    /// The costs of the function excluding its callees.
+    exclusive: u64,
+}
+
+impl Profile {
+    /// Returns the number of executed instructions in between two samples.
+    pub fn interval(&self) -> u64 {
+        self.interval
+    }
+
+    /// Returns the total costs of all profiled call stacks of the [`Profile`].
+    pub fn total(&self) -> u64 {
+        self.stacks.iter().map(|(_, costs)| costs).sum()
+    }
+
+    /// Returns the costs of all sampled Wasm functions ordered by their exclusive costs.
+    pub fn funcs(&self) -> Vec<FuncProfile<'_>> {
+        let mut inclusive = vec![0_u64; self.funcs.len()];
+        let mut exclusive = vec![0_u64; self.funcs.len()];
+        let mut seen = Vec::new();
+        for (stack, costs) in &self.stacks {
+            // Recursive functions must account for their inclusive costs only once per stack.
+            seen.clear();
+            for func in stack {
+                if !seen.contains(func) {
+                    seen.push(*func);
+                    inclusive[*func] += costs;
+                }
+            }
+            if let Some(func) = stack.last() {
+                exclusive[*func] += costs;
+            }
+        }
+        let mut funcs = self
+            .funcs
+            .iter()
+            .zip(inclusive.into_iter().zip(exclusive))
+            .map(|(frame, (inclusive, exclusive))| FuncProfile {
+                frame,
+                inclusive,
+                exclusive,
+            })
+            .collect::<Vec<_>>();
+        funcs.sort_by(|a, b| {
+            b.exclusive
+                .cmp(&a.exclusive)
+                .then(b.inclusive.cmp(&a.inclusive))
+        });
+        funcs
+    }
+
+    /// Returns the [`Profile`] in the folded stacks format.
+    ///
+    /// # Note
+    ///
+    /// Every line lists the functions of a sampled call stack from outermost to
+    /// innermost separated by `;` followed by the costs of the call stack.
+    /// This is the input format of flamegraph tools such as `inferno` or `flamegraph.pl`.
+    pub fn to_folded(&self) -> String {
+        let names = self.funcs.iter().map(folded_name).collect::<Vec<_>>();
+        let mut folded = String::new();
+        for (stack, costs) in &self.stacks {
+            for (n, func) in stack.iter().enumerate() {
+                if n != 0 {
+                    folded.push(';');
+                }
+                folded.push_str(&names[*func]);
+            }
+            let _ = writeln!(folded, " {costs}");
+        }
+        folded
+    }
+}
+
+impl<'a> FuncProfile<'a> {
+    /// Returns the index of the Wasm function within its module.
+    pub fn func_index(&self) -> u32 {
+        self.frame.func_index()
+    }
+
+    /// Returns the name of the module of the Wasm function if known.
+    pub fn module_name(&self) -> Option<&'a str> {
+        self.frame.module_name()
+    }
+
+    /// Returns the name of the Wasm function if known.
+    ///
+    /// # Note
+    ///
+    /// Names are taken from the `name` custom section and fall back to export names.
+    pub fn func_name(&self) -> Option<&'a str> {
+        self.frame.func_name()
+    }
+
+    /// Returns the costs of the Wasm function including its callees.
+    pub fn inclusive(&self) -> u64 {
+        self.inclusive
+    }
+
+    /// Returns the costs of the Wasm function excluding its callees.
+    pub fn exclusive(&self) -> u64 {
+        self.exclusive
+    }
+}
+
+/// Returns the name of the Wasm function of `frame` for the folded stacks format.
+///
+/// Falls back to `func[index]` for unnamed functions and replaces all `;`
+/// since they separate the functions of a call stack.
+fn folded_name(frame: &FrameInfo) -> String {
+    let name = match frame.func_name() {
+        Some(name) => name.to_string(),
+        None => format!("func[{}]", frame.func_index()),
+    };
+    let name = match frame.module_name() {
+        Some(module) => format!("{module}!{name}"),
+        None => name,
+    };
+    name.replace(';', ":")
+}
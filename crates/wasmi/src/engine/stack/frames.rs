@@ -17,6 +17,13 @@ pub struct FuncFrame {
     /// non-local to the function such as linear memories, global variables
     /// and tables.
     instance: Instance,
+    /// The height of the value stack right after the parameters of the function.
+    ///
+    /// # Note
+    ///
+    /// This is used by the debugger to locate the locals of the function.
+    #[cfg(feature = "debugger")]
+    params_end: usize,
 }
 
 impl FuncFrame {
@@ -25,9 +32,24 @@ impl FuncFrame {
         Self {
             ip,
             instance: *instance,
+            #[cfg(feature = "debugger")]
+            params_end: 0,
         }
     }
 
+    /// Sets the height of the value stack right after the parameters of the function.
+    #[cfg(feature = "debugger")]
+    pub fn with_params_end(mut self, params_end: usize) -> Self {
+        self.params_end = params_end;
+        self
+    }
+
+    /// Returns the height of the value stack right after the parameters of the function.
+    #[cfg(feature = "debugger")]
+    pub fn params_end(&self) -> usize {
+        self.params_end
+    }
+
     /// Returns the current instruction pointer.
     pub fn ip(&self) -> InstructionPtr {
         self.ip
@@ -46,6 +68,9 @@ pub struct CallStack {
     frames: Vec<FuncFrame>,
     /// The maximum allowed depth of the `frames` stack.
     recursion_limit: usize,
+    /// Whether the execution using the [`CallStack`] may be paused by the debugger.
+    #[cfg(feature = "debugger")]
+    debug: bool,
 }
 
 impl Default for CallStack {
@@ -60,13 +85,33 @@ impl CallStack {
         Self {
             frames: Vec::new(),
             recursion_limit,
+            #[cfg(feature = "debugger")]
+            debug: false,
         }
     }
 
-    /// Initializes the [`CallStack`] given the Wasm function.
-    pub fn init(&mut self, ip: InstructionPtr, instance: &Instance) {
-        self.reset();
-        self.frames.push(FuncFrame::new(ip, instance));
+    /// Initializes the [`CallStack`] given the [`FuncFrame`] of the Wasm function.
+    pub fn init(&mut self, frame: FuncFrame) {
+        self.frames.clear();
+        self.frames.push(frame);
+    }
+
+    /// Sets whether the execution using the [`CallStack`] may be paused by the debugger.
+    #[cfg(feature = "debugger")]
+    pub fn set_debug(&mut self, debug: bool) {
+        self.debug = debug;
+    }
+
+    /// Returns `true` if the execution using the [`CallStack`] may be paused by the debugger.
+    #[cfg(feature = "debugger")]
+    #[inline]
+    pub fn is_debug(&self) -> bool {
+        self.debug
+    }
+
+    /// Returns the [`FuncFrame`]s on the [`CallStack`] from outermost to innermost.
+    pub fn frames(&self) -> &[FuncFrame] {
+        &self.frames
     }
 
     /// Pushes a Wasm caller function onto the [`CallStack`].
@@ -79,6 +124,11 @@ impl CallStack {
         Ok(())
     }
 
+    /// Pushes the [`FuncFrame`] onto the [`CallStack`] regardless of its recursion limit.
+    pub fn push_unchecked(&mut self, frame: FuncFrame) {
+        self.frames.push(frame);
+    }
+
     /// Pops the last [`FuncFrame`] from the [`CallStack`] if any.
     #[inline]
     pub fn pop(&mut self) -> Option<FuncFrame> {
@@ -93,7 +143,7 @@ impl CallStack {
 
     /// Returns the amount of function frames on the [`CallStack`].
     #[inline]
-    fn len(&self) -> usize {
+    pub fn len(&self) -> usize {
         self.frames.len()
     }
 
@@ -107,5 +157,7 @@ impl CallStack {
     /// function execution happens.
     pub fn reset(&mut self) {
         self.frames.clear();
+        #[cfg(feature = "debugger")]
+        self.set_debug(false);
     }
 }
@@ -10,6 +10,8 @@ use crate::{
     engine::{code_map::CodeMap, func_types::FuncTypeRegistry, FuncParams},
     func::{HostFuncEntity, WasmFuncEntity},
     AsContext,
+    AsContextMut,
+    CallHook,
     Instance,
     StoreContextMut,
 };
@@ -97,6 +99,28 @@ impl Default for StackLimits {
     }
 }
 
+/// The error of a host function call via [`Stack::call_host_impl`].
+#[derive(Debug)]
+pub enum HostCallError {
+    /// The host function returned a trap or its parameters and results overflowed the value stack.
+    ///
+    /// Executions that called the host function from Wasm may be resumed.
+    Host(Trap),
+    /// The call hook of the store returned a trap.
+    ///
+    /// Executions must not be resumed since the trap did not originate from the host function.
+    Hook(Trap),
+}
+
+impl HostCallError {
+    /// Returns the [`Trap`] of the [`HostCallError`].
+    pub fn into_trap(self) -> Trap {
+        match self {
+            Self::Host(trap) | Self::Hook(trap) => trap,
+        }
+    }
+}
+
 /// Data structure that combines both value stack and call stack.
 #[derive(Debug, Default)]
 pub struct Stack {
@@ -147,10 +171,14 @@ impl Stack {
         code_map: &CodeMap,
     ) -> Result<(), TrapCode> {
         let header = code_map.header(wasm_func.func_body());
+        #[cfg(feature = "debugger")]
+        let params_end = self.values.len();
         self.values.prepare_wasm_call(header)?;
         let ip = code_map.instr_ptr(header.iref());
-        let instance = wasm_func.instance();
-        self.frames.init(ip, instance);
+        let frame = FuncFrame::new(ip, wasm_func.instance());
+        #[cfg(feature = "debugger")]
+        let frame = frame.with_params_end(params_end);
+        self.frames.init(frame);
         Ok(())
     }
 
@@ -162,6 +190,43 @@ impl Stack {
         func_types: &FuncTypeRegistry,
     ) -> Result<(), Trap> {
         self.call_host_impl(ctx, host_func, None, func_types)
+            .map_err(HostCallError::into_trap)
+    }
+
+    /// Charges the [`HostFuel`] costs of the given host function if any.
+    ///
+    /// # Note
+    ///
+    /// - This does nothing if fuel metering is disabled or if the host function
+    ///   has not been registered with [`HostFuel`] costs.
+    /// - The parameters of the host function call must be on top of the value stack.
+    ///
+    /// # Errors
+    ///
+    /// If there is not enough fuel left to call the host function.
+    ///
+    /// [`HostFuel`]: crate::HostFuel
+    #[inline(always)]
+    pub fn consume_host_fuel<T>(
+        &mut self,
+        ctx: &mut StoreContextMut<T>,
+        host_func: &HostFuncEntity,
+        func_types: &FuncTypeRegistry,
+    ) -> Result<(), TrapCode> {
+        if !ctx.store.engine().config().get_consume_fuel() {
+            return Ok(());
+        }
+        let fuel = match ctx.store.resolve_trampoline(host_func.trampoline()).fuel() {
+            Some(fuel) => fuel,
+            None => return Ok(()),
+        };
+        let len_inputs = func_types
+            .resolve_func_type(host_func.ty_dedup())
+            .params()
+            .len();
+        let delta = fuel.costs(self.values.peek_as_slice_mut(len_inputs));
+        ctx.store.inner.fuel_mut().consume_fuel(delta)?;
+        Ok(())
     }
 
     /// Executes the given host function.
@@ -170,14 +235,21 @@ impl Stack {
     ///
     /// - If the host function returns a host side error or trap.
     /// - If the value stack overflowed upon pushing parameters or results.
+    /// - If the call hook of the store returns a trap.
+    ///
+    /// The trap of the call hook is returned as [`HostCallError::Hook`]
+    /// and all other traps as [`HostCallError::Host`].
     #[inline(always)]
     pub fn call_host_impl<T>(
         &mut self,
-        ctx: StoreContextMut<T>,
+        mut ctx: StoreContextMut<T>,
         host_func: HostFuncEntity,
         instance: Option<&Instance>,
         func_types: &FuncTypeRegistry,
-    ) -> Result<(), Trap> {
+    ) -> Result<(), HostCallError> {
+        ctx.store
+            .invoke_call_hook(CallHook::CallingHost)
+            .map_err(HostCallError::Hook)?;
         // The host function signature is required for properly
         // adjusting, inspecting and manipulating the value stack.
         let (input_types, output_types) = func_types
@@ -188,7 +260,9 @@ impl Stack {
         let len_inputs = input_types.len();
         let len_outputs = output_types.len();
         let max_inout = len_inputs.max(len_outputs);
-        self.values.reserve(max_inout)?;
+        self.values
+            .reserve(max_inout)
+            .map_err(|trap_code| HostCallError::Host(trap_code.into()))?;
         let delta = if len_outputs > len_inputs {
             // Note: We have to save the delta of values pushed
             //       so that we can drop them in case the host
@@ -212,17 +286,23 @@ impl Stack {
             .store
             .resolve_trampoline(host_func.trampoline())
             .clone();
-        trampoline
-            .call(ctx, instance, params_results)
-            .map_err(|error| {
-                // Note: We drop the values that have been temporarily added to
-                //       the stack to act as parameter and result buffer for the
-                //       called host function. Since the host function failed we
-                //       need to clean up the temporary buffer values here.
-                //       This is required for resumable calls to work properly.
-                self.values.drop(delta);
-                error
-            })?;
+        let result = trampoline
+            .call(ctx.as_context_mut(), instance, params_results)
+            .map(|_| ())
+            .map_err(HostCallError::Host);
+        let hook_result = ctx
+            .store
+            .invoke_call_hook(CallHook::ReturningFromHost)
+            .map_err(HostCallError::Hook);
+        result.and(hook_result).map_err(|error| {
+            // Note: We drop the values that have been temporarily added to
+            //       the stack to act as parameter and result buffer for the
+            //       called host function. Since the host function failed we
+            //       need to clean up the temporary buffer values here.
+            //       This is required for resumable calls to work properly.
+            self.values.drop(delta);
+            error
+        })?;
         // If the host functions returns fewer results than it receives parameters
         // the value stack needs to be shrinked for the delta.
         if len_outputs < len_inputs {
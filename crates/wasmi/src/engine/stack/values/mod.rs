@@ -214,7 +214,7 @@ impl ValueStack {
     }
 
     /// Returns the current length of the [`ValueStack`].
-    fn len(&self) -> usize {
+    pub fn len(&self) -> usize {
         self.stack_ptr
     }
 
@@ -268,6 +268,18 @@ impl ValueStack {
         &mut self.entries[start..end]
     }
 
+    /// Returns a shared slice over all live values of the [`ValueStack`].
+    #[cfg(feature = "trace")]
+    pub fn as_slice(&self) -> &[UntypedValue] {
+        &self.entries[..self.stack_ptr]
+    }
+
+    /// Returns an exclusive slice over all live values of the [`ValueStack`].
+    #[cfg(feature = "debugger")]
+    pub fn as_slice_mut(&mut self) -> &mut [UntypedValue] {
+        &mut self.entries[..self.stack_ptr]
+    }
+
     /// Clears the [`ValueStack`] entirely.
     ///
     /// # Note
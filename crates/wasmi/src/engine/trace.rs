@@ -0,0 +1,134 @@
+//! Instruction-level execution tracing.
+//!
+//! # Note
+//!
+//! This is only available with the `trace` crate feature enabled.
+
+use super::bytecode::Instruction;
+use alloc::boxed::Box;
+use core::{fmt, fmt::Debug};
+use wasmi_core::UntypedValue;
+
+/// An executed `wasmi` bytecode instruction reported to the trace hook of a [`Store`].
+///
+/// The [`TraceEvent`] is reported right before its instruction is executed.
+///
+/// [`Store`]: crate::Store
+#[derive(Debug)]
+pub struct TraceEvent<'a> {
+    /// The instruction that is about to be executed.
+    instr: TraceInstr<'a>,
+    /// The index of the executed function within the function index space of its module.
+    func_index: Option<u32>,
+    /// The offset of the instruction within its compiled function.
+    offset: Option<usize>,
+    /// The Wasm bytecode offset of the Wasm operator the instruction originates from.
+    wasm_offset: Option<usize>,
+    /// The live values on the value stack.
+    stack: &'a [UntypedValue],
+}
+
+impl<'a> TraceEvent<'a> {
+    /// Creates a new [`TraceEvent`].
+    pub(crate) fn new(
+        instr: &'a Instruction,
+        func_index: Option<u32>,
+        offset: Option<usize>,
+        wasm_offset: Option<usize>,
+        stack: &'a [UntypedValue],
+    ) -> Self {
+        Self {
+            instr: TraceInstr { instr },
+            func_index,
+            offset,
+            wasm_offset,
+            stack,
+        }
+    }
+
+    /// Returns the [`TraceInstr`] that is about to be executed.
+    pub fn instr(&self) -> TraceInstr<'a> {
+        self.instr
+    }
+
+    /// Returns the index of the executed function within the function index space of its module.
+    ///
+    /// Returns `None` if the executed function could not be resolved.
+    pub fn func_index(&self) -> Option<u32> {
+        self.func_index
+    }
+
+    /// Returns the offset of the [`TraceInstr`] within the `wasmi` bytecode of its function.
+    ///
+    /// Returns `None` if the executed function could not be resolved.
+    pub fn offset(&self) -> Option<usize> {
+        self.offset
+    }
+
+    /// Returns the Wasm bytecode offset of the Wasm operator the [`TraceInstr`] originates from.
+    ///
+    /// Returns `None` if the instruction has no associated Wasm operator
+    /// or if the executed function could not be resolved.
+    pub fn wasm_offset(&self) -> Option<usize> {
+        self.wasm_offset
+    }
+
+    /// Returns the live values on the value stack.
+    ///
+    /// # Note
+    ///
+    /// The last value of the returned slice is the top most value of the stack.
+    /// The values of all functions on the call stack are included.
+    pub fn stack(&self) -> &'a [UntypedValue] {
+        self.stack
+    }
+}
+
+/// A `wasmi` bytecode instruction reported by a [`TraceEvent`].
+///
+/// # Note
+///
+/// The `wasmi` bytecode is an implementation detail that may change at any time
+/// which is why the instruction can only be inspected via its [`Debug`] output.
+#[derive(Copy, Clone)]
+pub struct TraceInstr<'a> {
+    /// The underlying `wasmi` bytecode instruction.
+    instr: &'a Instruction,
+}
+
+impl Debug for TraceInstr<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Debug::fmt(self.instr, f)
+    }
+}
+
+/// The boxed closure of a [`TraceHook`].
+type TraceFn = dyn FnMut(&TraceEvent) + Send + Sync + 'static;
+
+/// A user provided hook that is called for every executed `wasmi` bytecode instruction.
+pub struct TraceHook {
+    hook: Box<TraceFn>,
+}
+
+impl Debug for TraceHook {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TraceHook").finish()
+    }
+}
+
+impl TraceHook {
+    /// Creates a new [`TraceHook`] from the given closure.
+    pub fn new<F>(hook: F) -> Self
+    where
+        F: FnMut(&TraceEvent) + Send + Sync + 'static,
+    {
+        Self {
+            hook: Box::new(hook),
+        }
+    }
+
+    /// Calls the [`TraceHook`] with the given [`TraceEvent`].
+    pub fn call(&mut self, event: &TraceEvent) {
+        (self.hook)(event)
+    }
+}
@@ -1,6 +1,6 @@
 use crate::{value::WithType, Value};
 use core::{iter, slice};
-use wasmi_core::UntypedValue;
+use wasmi_core::{Trap, UntypedValue};
 
 /// Types implementing this trait may be used as parameters for function execution.
 ///
@@ -68,19 +68,24 @@ pub trait CallResults {
 
     /// Feeds the result values back to the caller.
     ///
+    /// # Errors
+    ///
+    /// If the given `results` cannot be decoded into [`CallResults::Results`].
+    ///
     /// # Panics
     ///
     /// If the given `results` do not match the expected amount.
-    fn call_results(self, results: &[UntypedValue]) -> Self::Results;
+    fn call_results(self, results: &[UntypedValue]) -> Result<Self::Results, Trap>;
 }
 
 impl<'a> CallResults for &'a mut [Value] {
     type Results = ();
 
-    fn call_results(self, results: &[UntypedValue]) -> Self::Results {
+    fn call_results(self, results: &[UntypedValue]) -> Result<Self::Results, Trap> {
         assert_eq!(self.len(), results.len());
         self.iter_mut().zip(results).for_each(|(dst, src)| {
             *dst = src.with_type(dst.ty());
-        })
+        });
+        Ok(())
     }
 }
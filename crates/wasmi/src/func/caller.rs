@@ -1,5 +1,5 @@
 use super::super::{AsContext, AsContextMut, StoreContext, StoreContextMut};
-use crate::{store::FuelError, Engine, Extern, Instance};
+use crate::{store::FuelError, Engine, Extern, Instance, MemoryView};
 
 /// Represents the caller’s context when creating a host function via [`Func::wrap`].
 ///
@@ -34,6 +34,18 @@ impl<'a, T> Caller<'a, T> {
             .and_then(|instance| instance.get_export(self, name))
     }
 
+    /// Returns a [`MemoryView`] into the linear memory exported by the caller under `name`,
+    /// and an exclusive reference to the user provided host data.
+    ///
+    /// The [`MemoryView`] allows many accesses to the linear memory while resolving it only once.
+    ///
+    /// Returns `None` if there is no associated [`Instance`] of the caller
+    /// or if the caller does not export a linear memory under the name `name`.
+    pub fn memory_view(&mut self, name: &str) -> Option<(MemoryView<'_>, &mut T)> {
+        let memory = self.get_export(name)?.into_memory()?;
+        Some(memory.view_and_store_mut(self))
+    }
+
     /// Returns a shared reference to the user provided host data.
     pub fn data(&self) -> &T {
         self.ctx.store.data()
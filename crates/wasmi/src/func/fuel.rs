@@ -0,0 +1,85 @@
+use crate::core::UntypedValue;
+use alloc::sync::Arc;
+use core::{fmt, fmt::Debug};
+
+/// Computes the fuel costs of a host function call from its parameters.
+type DynamicFuelFn = dyn Fn(&[UntypedValue]) -> u64 + Send + Sync + 'static;
+
+/// The fuel costs charged for every call to a host function.
+///
+/// # Note
+///
+/// - Fuel costs are only charged if fuel metering is enabled via [`Config::consume_fuel`].
+/// - The fuel is charged before the host function is invoked.
+///   If there is not enough fuel left the call traps with [`TrapCode::OutOfFuel`]
+///   and the host function is not invoked at all.
+///
+/// [`Config::consume_fuel`]: crate::Config::consume_fuel
+/// [`TrapCode::OutOfFuel`]: crate::core::TrapCode::OutOfFuel
+#[derive(Clone)]
+pub struct HostFuel {
+    kind: HostFuelKind,
+}
+
+/// The internal representation of [`HostFuel`].
+#[derive(Clone)]
+enum HostFuelKind {
+    /// The same amount of fuel is charged for every call.
+    Fixed(u64),
+    /// The amount of fuel charged is computed from the call parameters.
+    Dynamic(Arc<DynamicFuelFn>),
+}
+
+impl Debug for HostFuel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            HostFuelKind::Fixed(costs) => f.debug_tuple("Fixed").field(costs).finish(),
+            HostFuelKind::Dynamic(_) => f.debug_tuple("Dynamic").finish(),
+        }
+    }
+}
+
+impl From<u64> for HostFuel {
+    fn from(costs: u64) -> Self {
+        Self::fixed(costs)
+    }
+}
+
+impl HostFuel {
+    /// Creates a [`HostFuel`] that charges `costs` fuel for every call.
+    pub fn fixed(costs: u64) -> Self {
+        Self {
+            kind: HostFuelKind::Fixed(costs),
+        }
+    }
+
+    /// Creates a [`HostFuel`] that computes the fuel costs from the call parameters.
+    ///
+    /// The `costs` closure is handed the parameters of the host function call
+    /// in the order of the host function signature.
+    ///
+    /// # Example
+    ///
+    /// Charge one unit of fuel per byte of a `(ptr: i32, len: i32)` buffer parameter.
+    ///
+    /// ```
+    /// # use wasmi::HostFuel;
+    /// let fuel = HostFuel::dynamic(|params| u64::from(u32::from(params[1])));
+    /// ```
+    pub fn dynamic<F>(costs: F) -> Self
+    where
+        F: Fn(&[UntypedValue]) -> u64 + Send + Sync + 'static,
+    {
+        Self {
+            kind: HostFuelKind::Dynamic(Arc::new(costs)),
+        }
+    }
+
+    /// Returns the fuel costs for a host function call with the given `params`.
+    pub(crate) fn costs(&self, params: &[UntypedValue]) -> u64 {
+        match &self.kind {
+            HostFuelKind::Fixed(costs) => *costs,
+            HostFuelKind::Dynamic(costs) => costs(params),
+        }
+    }
+}
@@ -10,8 +10,9 @@ use crate::{
     FuncRef,
     FuncType,
 };
+use alloc::format;
 use core::{array, iter::FusedIterator};
-use wasmi_core::{DecodeUntypedSlice, EncodeUntypedSlice, UntypedValue};
+use wasmi_core::UntypedValue;
 
 /// Closures and functions that can be used as host functions.
 pub trait IntoFunc<T, Params, Results>: Send + Sync + 'static {
@@ -76,7 +77,7 @@ macro_rules! impl_into_func {
                 );
                 let trampoline = TrampolineEntity::new(
                     move |caller: Caller<T>, params_results: FuncParams| -> Result<FuncFinished, Trap> {
-                        let (($($tuple,)*), func_results): (Self::Params, FuncResults) = params_results.decode_params();
+                        let (($($tuple,)*), func_results): (Self::Params, FuncResults) = params_results.decode_params()?;
                         let results: Self::Results =
                             (self)(caller, $($tuple),*).into_fallible()?;
                         Ok(func_results.encode_results(results))
@@ -156,10 +157,35 @@ macro_rules! impl_wasm_return_type {
 for_each_tuple!(impl_wasm_return_type);
 
 /// Types that can be used as parameters or results of host functions.
-pub trait WasmType: From<UntypedValue> + Into<UntypedValue> + Send {
+///
+/// # Note
+///
+/// - Use `#[derive(WasmType)]` of the `macros` crate feature to implement
+///   this trait for `#[repr(transparent)]` newtypes of other [`WasmType`] types.
+/// - Decoding a [`WasmType`] fails if its [`UntypedValue`] is no valid representation,
+///   e.g. for a [`char`] that is no Unicode scalar value. Host functions receiving
+///   such a parameter and [`TypedFunc`] calls returning such a result trap instead.
+/// - The untyped representation of a [`WasmType`] must be valid for its [`ValueType`]
+///   since typed handles such as [`TypedTable`] store it without further checks.
+///
+/// [`TypedFunc`]: crate::TypedFunc
+/// [`TypedTable`]: crate::TypedTable
+pub trait WasmType: Send + Sized {
     /// Returns the value type of the Wasm type.
     #[doc(hidden)]
     fn ty() -> ValueType;
+
+    /// Decodes the Wasm type from its [`UntypedValue`] representation.
+    ///
+    /// # Errors
+    ///
+    /// If `untyped` is no valid representation of the Wasm type.
+    #[doc(hidden)]
+    fn from_untyped(untyped: UntypedValue) -> Result<Self, Trap>;
+
+    /// Encodes the Wasm type into its [`UntypedValue`] representation.
+    #[doc(hidden)]
+    fn into_untyped(self) -> UntypedValue;
 }
 
 macro_rules! impl_wasm_type {
@@ -170,21 +196,81 @@ macro_rules! impl_wasm_type {
                 fn ty() -> ValueType {
                     ValueType::$wasmi_type
                 }
+
+                #[inline]
+                fn from_untyped(untyped: UntypedValue) -> Result<Self, Trap> {
+                    Ok(<$rust_type as From<UntypedValue>>::from(untyped))
+                }
+
+                #[inline]
+                fn into_untyped(self) -> UntypedValue {
+                    <$rust_type as Into<UntypedValue>>::into(self)
+                }
             }
         )*
     };
 }
 impl_wasm_type! {
+    type bool = I32;
     type u32 = I32;
     type u64 = I64;
     type i32 = I32;
     type i64 = I64;
+    type f32 = F32;
+    type f64 = F64;
     type F32 = F32;
     type F64 = F64;
     type FuncRef = FuncRef;
     type ExternRef = ExternRef;
 }
 
+/// # Note
+///
+/// A [`char`] is represented by its Unicode scalar value as `i32`.
+/// Decoding an `i32` that is no Unicode scalar value fails.
+impl WasmType for char {
+    #[inline]
+    fn ty() -> ValueType {
+        ValueType::I32
+    }
+
+    #[inline]
+    fn from_untyped(untyped: UntypedValue) -> Result<Self, Trap> {
+        let value = u32::from(untyped);
+        char::from_u32(value)
+            .ok_or_else(|| Trap::new(format!("invalid Unicode scalar value: {value:#x}")))
+    }
+
+    #[inline]
+    fn into_untyped(self) -> UntypedValue {
+        UntypedValue::from(u32::from(self))
+    }
+}
+
+/// # Note
+///
+/// The `null` [`ExternRef`] is represented as `None`.
+impl WasmType for Option<ExternRef> {
+    #[inline]
+    fn ty() -> ValueType {
+        ValueType::ExternRef
+    }
+
+    #[inline]
+    fn from_untyped(untyped: UntypedValue) -> Result<Self, Trap> {
+        let externref = ExternRef::from(untyped);
+        match externref.is_null() {
+            true => Ok(None),
+            false => Ok(Some(externref)),
+        }
+    }
+
+    #[inline]
+    fn into_untyped(self) -> UntypedValue {
+        UntypedValue::from(self.unwrap_or_else(ExternRef::null))
+    }
+}
+
 /// A list of [`WasmType`] types.
 ///
 /// # Note
@@ -195,7 +281,7 @@ impl_wasm_type! {
 /// - Write host function results into a region of the value stack.
 /// - Iterate over the value types of the Wasm type sequence
 ///     - This is useful to construct host function signatures.
-pub trait WasmTypeList: DecodeUntypedSlice + EncodeUntypedSlice + Sized + Send {
+pub trait WasmTypeList: Sized + Send {
     /// The number of Wasm types in the list.
     #[doc(hidden)]
     const LEN: usize;
@@ -234,11 +320,17 @@ pub trait WasmTypeList: DecodeUntypedSlice + EncodeUntypedSlice + Sized + Send {
     #[doc(hidden)]
     fn values(self) -> Self::Values;
 
-    /// Consumes the [`UntypedValue`] iterator and creates `Self` if possible.
+    /// Decodes `Self` from the [`UntypedValue`] sequence.
+    ///
+    /// # Errors
     ///
-    /// Returns `None` if construction of `Self` is impossible.
+    /// If any of the `values` is no valid representation of its [`WasmType`].
+    ///
+    /// # Panics
+    ///
+    /// If the number of `values` does not match [`WasmTypeList::LEN`].
     #[doc(hidden)]
-    fn from_values(values: &[UntypedValue]) -> Option<Self>;
+    fn from_values(values: &[UntypedValue]) -> Result<Self, Trap>;
 }
 
 impl<T1> WasmTypeList for T1
@@ -259,15 +351,15 @@ where
 
     #[inline]
     fn values(self) -> Self::Values {
-        [<T1 as Into<UntypedValue>>::into(self)]
+        [<T1 as WasmType>::into_untyped(self)]
     }
 
     #[inline]
-    fn from_values(values: &[UntypedValue]) -> Option<Self> {
-        if let [value] = *values {
-            return Some(value.into());
+    fn from_values(values: &[UntypedValue]) -> Result<Self, Trap> {
+        match *values {
+            [value] => <T1 as WasmType>::from_untyped(value),
+            _ => panic!("expected 1 value but found {}", values.len()),
         }
-        None
     }
 }
 
@@ -298,19 +390,19 @@ macro_rules! impl_wasm_type_list {
             fn values(self) -> Self::Values {
                 let ($($tuple,)*) = self;
                 [$(
-                    <$tuple as Into<UntypedValue>>::into($tuple)
+                    <$tuple as WasmType>::into_untyped($tuple)
                 ),*]
             }
 
             #[inline]
             #[allow(non_snake_case)]
-            fn from_values(values: &[UntypedValue]) -> Option<Self> {
-                if let [$($tuple),*] = *values {
-                    return Some(
-                        ( $( Into::into($tuple), )* )
-                    )
+            fn from_values(values: &[UntypedValue]) -> Result<Self, Trap> {
+                match *values {
+                    [$($tuple),*] => Ok(
+                        ( $( <$tuple as WasmType>::from_untyped($tuple)?, )* )
+                    ),
+                    _ => panic!("expected {} values but found {}", $n, values.len()),
                 }
-                None
             }
         }
     };
@@ -363,5 +455,29 @@ mod tests {
         assert!(implements_wasm_results!(Result<i32, Trap>));
         assert!(implements_wasm_results!(Result<(i32,), Trap>));
         assert!(implements_wasm_results!(Result<(i32, u32, i64, u64, F32, F64), Trap>));
+        assert!(implements_wasm_results!((bool, f32, f64)));
+        assert!(implements_wasm_results!(Option<ExternRef>));
+        assert!(implements_wasm_results!(
+            Result<(FuncRef, Option<ExternRef>), Trap>
+        ));
+    }
+
+    #[test]
+    fn wasm_type_roundtrips() {
+        fn roundtrip<T: WasmType>(value: T) -> T {
+            T::from_untyped(value.into_untyped()).unwrap()
+        }
+        assert!(roundtrip(true));
+        assert!(!roundtrip(false));
+        assert!(<bool as WasmType>::from_untyped(UntypedValue::from(42_i32)).unwrap());
+        assert_eq!(roundtrip(-1.5_f32), -1.5);
+        assert_eq!(roundtrip(2.5_f64), 2.5);
+        assert_eq!(roundtrip('λ'), 'λ');
+        assert!(<char as WasmType>::from_untyped(UntypedValue::from(0xD800_u32)).is_err());
+        assert!(<char as WasmType>::from_untyped(UntypedValue::from(-1_i32)).is_err());
+        assert!(roundtrip(<Option<ExternRef>>::None).is_none());
+        assert!(<Option<ExternRef>>::from_untyped(ExternRef::null().into())
+            .unwrap()
+            .is_none());
     }
 }
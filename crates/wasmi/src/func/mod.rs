@@ -1,5 +1,6 @@
 mod caller;
 mod error;
+mod fuel;
 mod func_type;
 mod funcref;
 mod into_func;
@@ -9,6 +10,7 @@ pub(crate) use self::typed_func::CallResultsTuple;
 pub use self::{
     caller::Caller,
     error::FuncError,
+    fuel::HostFuel,
     func_type::FuncType,
     funcref::FuncRef,
     into_func::{IntoFunc, WasmRet, WasmType, WasmTypeList},
@@ -20,8 +22,11 @@ use super::{
     AsContextMut,
     Instance,
     StoreContext,
+    StoreInner,
     Stored,
 };
+#[cfg(feature = "debugger")]
+use crate::engine::DebugCall;
 use crate::{core::Trap, engine::ResumableCall, Engine, Error, Value};
 use alloc::{boxed::Box, sync::Arc};
 use core::{fmt, fmt::Debug, num::NonZeroU32};
@@ -144,15 +149,23 @@ pub struct WasmFuncEntity {
     body: CompiledFunc,
     /// The instance associated to the Wasm function.
     instance: Instance,
+    /// The index of the Wasm function within the function index space of its [`Instance`].
+    index: u32,
 }
 
 impl WasmFuncEntity {
     /// Creates a new Wasm function from the given raw parts.
-    pub fn new(signature: DedupFuncType, body: CompiledFunc, instance: Instance) -> Self {
+    pub fn new(
+        signature: DedupFuncType,
+        body: CompiledFunc,
+        instance: Instance,
+        index: u32,
+    ) -> Self {
         Self {
             ty: signature,
             body,
             instance,
+            index,
         }
     }
 
@@ -170,6 +183,11 @@ impl WasmFuncEntity {
     pub fn func_body(&self) -> CompiledFunc {
         self.body
     }
+
+    /// Returns the index of the [`Func`] within the function index space of its [`Instance`].
+    pub fn index(&self) -> u32 {
+        self.index
+    }
 }
 
 /// A host function instance.
@@ -232,6 +250,12 @@ impl<T> HostFuncTrampolineEntity<T> {
         Self { ty, trampoline }
     }
 
+    /// Charges the given [`HostFuel`] costs for every call to the host function.
+    pub fn with_fuel(mut self, fuel: HostFuel) -> Self {
+        self.trampoline = self.trampoline.with_fuel(fuel);
+        self
+    }
+
     /// Returns the signature of the host function.
     pub fn ty_dedup(&self) -> &DedupFuncType {
         &self.ty
@@ -248,6 +272,8 @@ type TrampolineFn<T> =
 
 pub struct TrampolineEntity<T> {
     closure: Arc<TrampolineFn<T>>,
+    /// The fuel costs charged before calling the host function if any.
+    fuel: Option<HostFuel>,
 }
 
 impl<T> Debug for TrampolineEntity<T> {
@@ -264,9 +290,21 @@ impl<T> TrampolineEntity<T> {
     {
         Self {
             closure: Arc::new(trampoline),
+            fuel: None,
         }
     }
 
+    /// Charges the given [`HostFuel`] costs for every call to the host function.
+    pub fn with_fuel(mut self, fuel: HostFuel) -> Self {
+        self.fuel = Some(fuel);
+        self
+    }
+
+    /// Returns the [`HostFuel`] costs of the host function if any.
+    pub fn fuel(&self) -> Option<&HostFuel> {
+        self.fuel.as_ref()
+    }
+
     /// Calls the host function trampoline with the given inputs.
     ///
     /// The result is written back into the `outputs` buffer.
@@ -285,11 +323,19 @@ impl<T> Clone for TrampolineEntity<T> {
     fn clone(&self) -> Self {
         Self {
             closure: self.closure.clone(),
+            fuel: self.fuel.clone(),
         }
     }
 }
 
 /// A Wasm or host function reference.
+///
+/// # Note
+///
+/// The [`Debug`] implementation cannot access the [`Store`] and thus only
+/// prints the function reference. Use [`Func::name`] to query the name of the function.
+///
+/// [`Store`]: crate::Store
 #[derive(Debug, Copy, Clone)]
 #[repr(transparent)]
 pub struct Func(Stored<FuncIdx>);
@@ -360,6 +406,73 @@ impl Func {
             .alloc_func(HostFuncEntity::new(ty_dedup, func).into())
     }
 
+    /// Creates a new host function from the given closure that charges `fuel` per call.
+    ///
+    /// For information how to use this API see [`Func::wrap`].
+    ///
+    /// # Note
+    ///
+    /// The fuel is charged before the host function is invoked and only
+    /// if fuel metering is enabled. If there is not enough fuel left the call
+    /// traps with [`TrapCode::OutOfFuel`] without invoking the host function.
+    ///
+    /// [`TrapCode::OutOfFuel`]: crate::core::TrapCode::OutOfFuel
+    pub fn wrap_with_fuel<T, Params, Results>(
+        mut ctx: impl AsContextMut<UserState = T>,
+        fuel: impl Into<HostFuel>,
+        func: impl IntoFunc<T, Params, Results>,
+    ) -> Self {
+        let engine = ctx.as_context().store.engine();
+        let host_func = HostFuncTrampolineEntity::wrap(engine, func).with_fuel(fuel.into());
+        let ty_dedup = *host_func.ty_dedup();
+        let trampoline = host_func.trampoline().clone();
+        let func = ctx.as_context_mut().store.alloc_trampoline(trampoline);
+        ctx.as_context_mut()
+            .store
+            .inner
+            .alloc_func(HostFuncEntity::new(ty_dedup, func).into())
+    }
+
+    /// Returns the name of the function if any.
+    ///
+    /// # Note
+    ///
+    /// - The name of a Wasm function is taken from the `name` custom section of its
+    ///   [`Module`] and falls back to the name under which its [`Instance`] exports it.
+    /// - Host functions have no name.
+    ///
+    /// [`Module`]: crate::Module
+    pub fn name<'a, T: 'a>(&self, ctx: impl Into<StoreContext<'a, T>>) -> Option<&'a str> {
+        self.resolve_name(&ctx.into().store.inner)
+    }
+
+    /// Returns the name of the function stored in `store` if any.
+    ///
+    /// For more information see [`Func::name`].
+    pub(crate) fn resolve_name<'a>(&self, store: &'a StoreInner) -> Option<&'a str> {
+        let wasm_func = match store.resolve_func(self) {
+            FuncEntity::Wasm(wasm_func) => wasm_func,
+            FuncEntity::Host(_) => return None,
+        };
+        let instance = store.resolve_instance(wasm_func.instance());
+        if let Some(name) = instance.names().func(wasm_func.index()) {
+            return Some(name);
+        }
+        instance.exports().find_map(|export| {
+            let name = export.name();
+            let func = export.into_func()?;
+            match store.resolve_func(&func) {
+                FuncEntity::Wasm(exported)
+                    if exported.instance() == wasm_func.instance()
+                        && exported.index() == wasm_func.index() =>
+                {
+                    Some(name)
+                }
+                _ => None,
+            }
+        })
+    }
+
     /// Returns the signature of the function.
     pub(crate) fn ty_dedup<'a, T: 'a>(
         &self,
@@ -446,6 +559,48 @@ impl Func {
             .map(ResumableCall::new)
     }
 
+    /// Calls the Wasm or host function with the given inputs under the debugger.
+    ///
+    /// The result is written back into the `outputs` buffer.
+    ///
+    /// Returns a handle to the paused execution upon hitting a breakpoint set via
+    /// [`Store::set_breakpoint`]. The handle allows to inspect the call stack, locals,
+    /// operands, linear memories and global variables and to resume or single-step
+    /// the execution.
+    ///
+    /// # Note
+    ///
+    /// - Only executions started via this method are paused by the debugger.
+    /// - This is only available with the `debugger` crate feature enabled.
+    ///
+    /// # Errors
+    ///
+    /// - If the function returned a [`Trap`].
+    /// - If the types of the `inputs` do not match the expected types for the
+    ///   function signature of `self`.
+    /// - If the number of input values does not match the expected number of
+    ///   inputs required by the function signature of `self`.
+    /// - If the number of output values does not match the expected number of
+    ///   outputs required by the function signature of `self`.
+    ///
+    /// [`Store::set_breakpoint`]: crate::Store::set_breakpoint
+    #[cfg(feature = "debugger")]
+    pub fn call_debug<T>(
+        &self,
+        mut ctx: impl AsContextMut<UserState = T>,
+        inputs: &[Value],
+        outputs: &mut [Value],
+    ) -> Result<DebugCall, Error> {
+        self.verify_and_prepare_inputs_outputs(ctx.as_context(), inputs, outputs)?;
+        // Note: Cloning an [`Engine`] is intentionally a cheap operation.
+        ctx.as_context()
+            .store
+            .engine()
+            .clone()
+            .execute_func_debug(ctx.as_context_mut(), self, inputs, outputs)
+            .map_err(Into::into)
+    }
+
     /// Verify that the `inputs` and `outputs` value types match the function signature.
     ///
     /// Since [`Func`] is a dynamically typed function instance there is
@@ -183,9 +183,8 @@ where
 {
     type Results = Results;
 
-    fn call_results(self, results: &[UntypedValue]) -> Self::Results {
+    fn call_results(self, results: &[UntypedValue]) -> Result<Self::Results, Trap> {
         <Results as WasmTypeList>::from_values(results)
-            .expect("unable to construct typed results from call results")
     }
 }
 
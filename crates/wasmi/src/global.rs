@@ -1,6 +1,11 @@
 use super::{AsContext, AsContextMut, Stored};
-use crate::{core::ValueType, value::WithType, Value};
-use core::{fmt, fmt::Display, ptr::NonNull};
+use crate::{core::ValueType, value::WithType, Value, WasmType};
+use core::{
+    fmt,
+    fmt::{Debug, Display},
+    marker::PhantomData,
+    ptr::NonNull,
+};
 use wasmi_arena::ArenaIndex;
 use wasmi_core::UntypedValue;
 
@@ -27,11 +32,11 @@ impl ArenaIndex for GlobalIdx {
 pub enum GlobalError {
     /// Occurs when trying to write to an immutable global variable.
     ImmutableWrite,
-    /// Occurs when trying writing a value with mismatching type to a global variable.
+    /// Occurs when trying to access a global variable with a mismatching type.
     TypeMismatch {
         /// The type of the global variable.
         expected: ValueType,
-        /// The type of the new value that mismatches the type of the global variable.
+        /// The encountered type that mismatches the type of the global variable.
         encountered: ValueType,
     },
     /// Occurs when a global type does not satisfy the constraints of another.
@@ -53,7 +58,7 @@ impl Display for GlobalError {
             } => {
                 write!(
                     f,
-                    "type mismatch upon writing global variable. \
+                    "type mismatch upon accessing global variable. \
                     expected {expected:?} but encountered {encountered:?}.",
                 )
             }
@@ -269,4 +274,133 @@ impl Global {
     pub fn get(&self, ctx: impl AsContext) -> Value {
         ctx.as_context().store.inner.resolve_global(self).get()
     }
+    /// Creates a new [`TypedGlobal`] from this [`Global`].
+    ///
+    /// # Note
+    ///
+    /// This performs a static type check given `T` as the value type of
+    /// the [`Global`] so that those type checks can be avoided when
+    /// accessing the created [`TypedGlobal`].
+    ///
+    /// # Errors
+    ///
+    /// If the value type of `self` does not match `T`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ctx` does not own this [`Global`].
+    pub fn typed<T>(&self, ctx: impl AsContext) -> Result<TypedGlobal<T>, GlobalError>
+    where
+        T: WasmType,
+    {
+        TypedGlobal::new(ctx, *self)
+    }
+}
+
+/// A typed [`Global`] variable.
+///
+/// # Note
+///
+/// This allows for more efficient accesses by avoiding type checks
+/// upon accessing the global variable since those type checks are performed
+/// upon [`TypedGlobal`] construction and enforced by the Rust type system.
+#[repr(transparent)]
+pub struct TypedGlobal<T> {
+    /// The value type of the global variable encoded in the Rust type system.
+    ty: PhantomData<fn() -> T>,
+    /// The underlying [`Global`] variable.
+    global: Global,
+}
+
+impl<T> Debug for TypedGlobal<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TypedGlobal")
+            .field("ty", &self.ty)
+            .field("global", &self.global)
+            .finish()
+    }
+}
+
+impl<T> Copy for TypedGlobal<T> {}
+
+impl<T> Clone for TypedGlobal<T> {
+    fn clone(&self) -> TypedGlobal<T> {
+        *self
+    }
+}
+
+impl<T> TypedGlobal<T> {
+    /// Returns the underlying [`Global`].
+    ///
+    /// # Note
+    ///
+    /// This loses the static type information in the process.
+    pub fn global(&self) -> &Global {
+        &self.global
+    }
+}
+
+impl<T> TypedGlobal<T>
+where
+    T: WasmType,
+{
+    /// Creates a new [`TypedGlobal`] for the given [`Global`] using the static typing.
+    ///
+    /// # Errors
+    ///
+    /// If the value type of `global` does not match `T`.
+    pub(crate) fn new(ctx: impl AsContext, global: Global) -> Result<Self, GlobalError> {
+        let expected = global.ty(ctx).content();
+        let encountered = <T as WasmType>::ty();
+        if expected != encountered {
+            return Err(GlobalError::TypeMismatch {
+                expected,
+                encountered,
+            });
+        }
+        Ok(Self {
+            ty: PhantomData,
+            global,
+        })
+    }
+
+    /// Returns the current value of the global variable.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `ctx` does not own this [`TypedGlobal`].
+    /// - Panics if the value is no valid representation of `T`, e.g. an `i32`
+    ///   that is no Unicode scalar value for a [`char`] global variable.
+    pub fn get(&self, ctx: impl AsContext) -> T {
+        let value = ctx
+            .as_context()
+            .store
+            .inner
+            .resolve_global(&self.global)
+            .get_untyped();
+        T::from_untyped(value)
+            .unwrap_or_else(|error| panic!("invalid value of typed global variable: {error}"))
+    }
+
+    /// Sets a new value to the global variable.
+    ///
+    /// # Errors
+    ///
+    /// If the global variable is immutable.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ctx` does not own this [`TypedGlobal`].
+    pub fn set(&self, mut ctx: impl AsContextMut, new_value: T) -> Result<(), GlobalError> {
+        let global = ctx
+            .as_context_mut()
+            .store
+            .inner
+            .resolve_global_mut(&self.global);
+        if !global.ty().mutability().is_mut() {
+            return Err(GlobalError::ImmutableWrite);
+        }
+        global.set_untyped(new_value.into_untyped());
+        Ok(())
+    }
 }
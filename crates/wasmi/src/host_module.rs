@@ -0,0 +1,64 @@
+//! Runtime support for host modules generated by the `host_module` attribute.
+//!
+//! # Note
+//!
+//! This is only available with the `macros` crate feature enabled.
+//! The items are not part of the stable API and are only used by generated code.
+
+use crate::{
+    core::{Trap, TrapCode},
+    Caller,
+    Extern,
+    Memory,
+};
+
+/// Returns the linear memory exported as `memory` by the instance of the `caller`.
+///
+/// # Errors
+///
+/// If the instance of the `caller` does not export a linear memory named `memory`.
+pub fn caller_memory<T>(caller: &Caller<'_, T>) -> Result<Memory, Trap> {
+    match caller.get_export("memory") {
+        Some(Extern::Memory(memory)) => Ok(memory),
+        _ => Err(Trap::new("missing required memory export: `memory`")),
+    }
+}
+
+/// Returns the `len` bytes of `memory` starting at `ptr`.
+///
+/// # Errors
+///
+/// If the bytes are out of bounds of `memory`.
+pub fn slice(memory: &[u8], ptr: i32, len: i32) -> Result<&[u8], Trap> {
+    let start = ptr as u32 as usize;
+    let len = len as u32 as usize;
+    memory
+        .get(start..)
+        .and_then(|memory| memory.get(..len))
+        .ok_or_else(|| Trap::from(TrapCode::MemoryOutOfBounds))
+}
+
+/// Returns the `len` bytes of `memory` starting at `ptr` mutably.
+///
+/// # Errors
+///
+/// If the bytes are out of bounds of `memory`.
+pub fn slice_mut(memory: &mut [u8], ptr: i32, len: i32) -> Result<&mut [u8], Trap> {
+    let start = ptr as u32 as usize;
+    let len = len as u32 as usize;
+    memory
+        .get_mut(start..)
+        .and_then(|memory| memory.get_mut(..len))
+        .ok_or_else(|| Trap::from(TrapCode::MemoryOutOfBounds))
+}
+
+/// Returns the UTF-8 string of `len` bytes of `memory` starting at `ptr`.
+///
+/// # Errors
+///
+/// - If the bytes are out of bounds of `memory`.
+/// - If the bytes are not valid UTF-8.
+pub fn str(memory: &[u8], ptr: i32, len: i32) -> Result<&str, Trap> {
+    core::str::from_utf8(slice(memory, ptr, len)?)
+        .map_err(|_| Trap::new("encountered invalid UTF-8 string argument"))
+}
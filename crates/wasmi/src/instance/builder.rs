@@ -1,4 +1,6 @@
 use super::InstanceEntity;
+#[cfg(feature = "dwarf")]
+use crate::module::DebugInfo;
 use crate::{
     engine::DedupFuncType,
     memory::DataSegment,
@@ -10,6 +12,7 @@ use crate::{
     Global,
     Memory,
     Module,
+    ModuleNames,
     Table,
 };
 use alloc::{boxed::Box, collections::BTreeMap, sync::Arc, vec::Vec};
@@ -26,6 +29,9 @@ pub struct InstanceEntityBuilder {
     exports: BTreeMap<Box<str>, Extern>,
     data_segments: Vec<DataSegment>,
     elem_segments: Vec<ElementSegment>,
+    names: Arc<ModuleNames>,
+    #[cfg(feature = "dwarf")]
+    debug_info: Option<Arc<DebugInfo>>,
 }
 
 impl InstanceEntityBuilder {
@@ -66,6 +72,9 @@ impl InstanceEntityBuilder {
             exports: BTreeMap::default(),
             data_segments: Vec::new(),
             elem_segments: Vec::new(),
+            names: module.names_cloned(),
+            #[cfg(feature = "dwarf")]
+            debug_info: module.debug_info_cloned(),
         }
     }
 
@@ -193,6 +202,9 @@ impl InstanceEntityBuilder {
             exports: self.exports,
             data_segments: self.data_segments.into(),
             elem_segments: self.elem_segments.into(),
+            names: self.names,
+            #[cfg(feature = "dwarf")]
+            debug_info: self.debug_info,
         }
     }
 }
@@ -11,11 +11,14 @@ use super::{
     Stored,
     Table,
 };
+#[cfg(feature = "dwarf")]
+use crate::module::DebugInfo;
 use crate::{
     func::FuncError,
     memory::DataSegment,
     ElementSegment,
     Error,
+    ModuleNames,
     TypedFunc,
     WasmParams,
     WasmResults,
@@ -55,6 +58,9 @@ pub struct InstanceEntity {
     exports: BTreeMap<Box<str>, Extern>,
     data_segments: Box<[DataSegment]>,
     elem_segments: Box<[ElementSegment]>,
+    names: Arc<ModuleNames>,
+    #[cfg(feature = "dwarf")]
+    debug_info: Option<Arc<DebugInfo>>,
 }
 
 impl InstanceEntity {
@@ -70,6 +76,9 @@ impl InstanceEntity {
             exports: BTreeMap::new(),
             data_segments: [].into(),
             elem_segments: [].into(),
+            names: Arc::default(),
+            #[cfg(feature = "dwarf")]
+            debug_info: None,
         }
     }
 
@@ -129,6 +138,17 @@ impl InstanceEntity {
     pub fn exports(&self) -> ExportsIter {
         ExportsIter::new(self.exports.iter())
     }
+
+    /// Returns the names of the instantiated [`Module`] and its entities.
+    pub fn names(&self) -> &ModuleNames {
+        &self.names
+    }
+
+    /// Returns the DWARF debug information of the instantiated [`Module`] if any.
+    #[cfg(feature = "dwarf")]
+    pub fn debug_info(&self) -> Option<&DebugInfo> {
+        self.debug_info.as_deref()
+    }
 }
 
 /// An instantiated WebAssembly [`Module`].
@@ -87,11 +87,15 @@ extern crate std as alloc;
 #[macro_use]
 mod foreach_tuple;
 
+#[cfg(feature = "bindgen")]
+mod canonical_abi;
 mod engine;
 mod error;
 mod externref;
 mod func;
 mod global;
+#[cfg(feature = "macros")]
+mod host_module;
 mod instance;
 mod limits;
 mod linker;
@@ -101,6 +105,34 @@ mod store;
 mod table;
 mod value;
 
+/// Runtime support for code generated by the [`host_module`] attribute and the `bindgen` macro.
+#[cfg(feature = "macros")]
+#[doc(hidden)]
+pub mod __private {
+    #[cfg(feature = "bindgen")]
+    pub use super::canonical_abi::{
+        caller_realloc,
+        lift_char,
+        lift_f32,
+        lift_f64,
+        lift_list,
+        lift_string,
+        load_pair,
+        lower_f32,
+        lower_f64,
+        lower_list,
+        lower_string,
+        optional_typed_func,
+        required,
+        store_pair,
+        Realloc,
+        Scalar,
+    };
+    pub use super::host_module::{caller_memory, slice, slice_mut, str};
+    #[cfg(feature = "bindgen")]
+    pub use alloc::{string::String, vec::Vec};
+}
+
 /// Definitions from the `wasmi_core` crate.
 #[doc(inline)]
 pub use wasmi_core as core;
@@ -108,6 +140,7 @@ pub use wasmi_core as core;
 /// Defines some errors that may occur upon interaction with `wasmi`.
 pub mod errors {
     pub use super::{
+        engine::FuelCostsError,
         func::FuncError,
         global::GlobalError,
         linker::LinkerError,
@@ -116,13 +149,28 @@ pub mod errors {
         store::FuelError,
         table::TableError,
     };
+
+    #[cfg(feature = "debugger")]
+    pub use super::engine::DebuggerError;
 }
 
+#[cfg(feature = "trace")]
+pub use self::engine::{TraceEvent, TraceInstr};
+#[cfg(feature = "debugger")]
+pub use self::engine::{DebugCall, DebugFrame, PausedInvocation};
+#[cfg(feature = "profiler")]
+pub use self::engine::{FuncProfile, Profile};
+#[cfg(feature = "debugger")]
+pub use self::memory::{WatchKind, Watchpoint, WatchpointHit};
 pub use self::{
     engine::{
+        BlockCoverage,
         Config,
+        CoverageReport,
         Engine,
         FuelConsumptionMode,
+        FuelCosts,
+        FuncCoverage,
         ResumableCall,
         ResumableInvocation,
         StackLimits,
@@ -136,6 +184,7 @@ pub use self::{
         Func,
         FuncRef,
         FuncType,
+        HostFuel,
         IntoFunc,
         TypedFunc,
         WasmParams,
@@ -144,11 +193,11 @@ pub use self::{
         WasmType,
         WasmTypeList,
     },
-    global::{Global, GlobalType, Mutability},
+    global::{Global, GlobalType, Mutability, TypedGlobal},
     instance::{Export, ExportsIter, Extern, ExternType, Instance},
     limits::{ResourceLimiter, StoreLimits, StoreLimitsBuilder},
-    linker::Linker,
-    memory::{Memory, MemoryType},
+    linker::{LinkedModule, Linker},
+    memory::{Memory, MemoryType, MemoryValue, MemoryView, WasmPtr, WasmSlice},
     module::{
         ExportType,
         ImportType,
@@ -156,10 +205,19 @@ pub use self::{
         Module,
         ModuleExportsIter,
         ModuleImportsIter,
+        ModuleNames,
         Read,
     },
-    store::{AsContext, AsContextMut, Store, StoreContext, StoreContextMut},
-    table::{Table, TableType},
+    store::{
+        AsContext,
+        AsContextMut,
+        CallHook,
+        InterruptHandle,
+        Store,
+        StoreContext,
+        StoreContextMut,
+    },
+    table::{Table, TableType, TypedTable},
     value::Value,
 };
 use self::{
@@ -170,3 +228,9 @@ use self::{
     store::{StoreInner, Stored},
     table::{ElementSegment, ElementSegmentEntity, ElementSegmentIdx, TableEntity, TableIdx},
 };
+#[cfg(feature = "bindgen")]
+pub use wasmi_macros::bindgen;
+#[cfg(feature = "macros")]
+pub use wasmi_macros::host_module;
+#[cfg(feature = "macros")]
+pub use wasmi_macros::WasmType;
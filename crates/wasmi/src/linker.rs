@@ -12,6 +12,8 @@ use crate::{
     Func,
     FuncType,
     GlobalType,
+    HostFuel,
+    Instance,
     InstancePre,
     IntoFunc,
     MemoryType,
@@ -21,6 +23,8 @@ use crate::{
 };
 use alloc::{
     collections::{btree_map::Entry, BTreeMap},
+    format,
+    string::{String, ToString},
     sync::Arc,
     vec::Vec,
 };
@@ -30,6 +34,7 @@ use core::{
     num::NonZeroUsize,
     ops::Deref,
 };
+use wasmi_core::ValueType;
 
 /// An error that may occur upon operating with [`Linker`] instances.
 #[derive(Debug)]
@@ -39,6 +44,11 @@ pub enum LinkerError {
         /// The duplicate import name of the definition.
         import_name: ImportName,
     },
+    /// Encountered when aliasing or removing a name that has not been defined.
+    UnknownDefinition {
+        /// The name that has not been defined.
+        import_name: ImportName,
+    },
     /// Encountered when no definition for an import is found.
     MissingDefinition {
         /// The name of the import for which no definition was found.
@@ -160,6 +170,9 @@ impl Display for LinkerError {
                     "encountered duplicate definition with name `{import_name}`",
                 )
             }
+            Self::UnknownDefinition { import_name } => {
+                write!(f, "cannot find definition with name `{import_name}`")
+            }
             Self::MissingDefinition { name, ty } => {
                 write!(
                     f,
@@ -286,6 +299,14 @@ impl StringInterner {
     }
 }
 
+/// Converts the `error` of a call or instantiation within a host function into a [`Trap`].
+fn into_trap(error: Error) -> Trap {
+    match error {
+        Error::Trap(trap) => trap,
+        error => Trap::new(error.to_string()),
+    }
+}
+
 /// Wasm import keys.
 #[derive(Debug, Copy, Clone, PartialOrd, Ord, PartialEq, Eq)]
 struct ImportKey {
@@ -429,6 +450,9 @@ impl<'a, T> Debug for DebugHostFuncEntity<'a, T> {
     }
 }
 
+/// The resolver consulted by a [`Linker`] for imports that it does not define.
+type FallbackFn = dyn Fn(&ImportType) -> Option<Extern> + Send + Sync + 'static;
+
 /// A linker used to define module imports and instantiate module instances.
 pub struct Linker<T> {
     /// The underlying [`Engine`] for the [`Linker`].
@@ -442,6 +466,10 @@ pub struct Linker<T> {
     strings: StringInterner,
     /// Stores the definitions given their names.
     definitions: BTreeMap<ImportKey, Definition<T>>,
+    /// The resolver for imports without definition if any.
+    fallback: Option<Arc<FallbackFn>>,
+    /// Whether new definitions replace existing definitions under the same name.
+    allow_shadowing: bool,
 }
 
 impl<T> Debug for Linker<T> {
@@ -449,6 +477,8 @@ impl<T> Debug for Linker<T> {
         f.debug_struct("Linker")
             .field("strings", &self.strings)
             .field("definitions", &DebugDefinitions::new(self))
+            .field("fallback", &self.fallback.is_some())
+            .field("allow_shadowing", &self.allow_shadowing)
             .finish()
     }
 }
@@ -459,6 +489,8 @@ impl<T> Clone for Linker<T> {
             engine: self.engine.clone(),
             strings: self.strings.clone(),
             definitions: self.definitions.clone(),
+            fallback: self.fallback.clone(),
+            allow_shadowing: self.allow_shadowing,
         }
     }
 }
@@ -476,6 +508,8 @@ impl<T> Linker<T> {
             engine: engine.clone(),
             strings: StringInterner::default(),
             definitions: BTreeMap::default(),
+            fallback: None,
+            allow_shadowing: false,
         }
     }
 
@@ -488,7 +522,8 @@ impl<T> Linker<T> {
     ///
     /// # Errors
     ///
-    /// If there already is a definition under the same name for this [`Linker`].
+    /// If there already is a definition under the same name for this [`Linker`]
+    /// and shadowing is not allowed.
     pub fn define(
         &mut self,
         module: &str,
@@ -500,13 +535,111 @@ impl<T> Linker<T> {
         Ok(self)
     }
 
+    /// Configures whether new definitions replace existing definitions under the same name.
+    ///
+    /// # Note
+    ///
+    /// This is disabled by default so that defining the same name twice
+    /// fails with [`LinkerError::DuplicateDefinition`].
+    pub fn allow_shadowing(&mut self, allow: bool) -> &mut Self {
+        self.allow_shadowing = allow;
+        self
+    }
+
+    /// Defines the item defined as `module` and `name` also as `as_module` and `as_name`.
+    ///
+    /// # Errors
+    ///
+    /// - If there is no definition for `module` and `name` in this [`Linker`].
+    /// - If there already is a definition for `as_module` and `as_name`
+    ///   in this [`Linker`] and shadowing is not allowed.
+    pub fn alias(
+        &mut self,
+        module: &str,
+        name: &str,
+        as_module: &str,
+        as_name: &str,
+    ) -> Result<&mut Self, LinkerError> {
+        let definition = self.definition(module, name).cloned().ok_or_else(|| {
+            LinkerError::UnknownDefinition {
+                import_name: ImportName::new(module, name),
+            }
+        })?;
+        let key = self.import_key(as_module, as_name);
+        self.insert(key, definition)?;
+        Ok(self)
+    }
+
+    /// Defines all items defined in `module` also in `as_module` under the same names.
+    ///
+    /// # Errors
+    ///
+    /// If there already is a definition for any of the aliased names
+    /// in `as_module` in this [`Linker`] and shadowing is not allowed.
+    /// In this case no item is aliased.
+    pub fn alias_module(
+        &mut self,
+        module: &str,
+        as_module: &str,
+    ) -> Result<&mut Self, LinkerError> {
+        let module = match self.strings.get(module) {
+            Some(module) => module,
+            None => return Ok(self),
+        };
+        let as_module = self.strings.get_or_intern(as_module);
+        let aliased = self
+            .definitions
+            .iter()
+            .filter(|(key, _)| key.module == module)
+            .map(|(key, definition)| {
+                let key = ImportKey {
+                    module: as_module,
+                    name: key.name,
+                };
+                (key, definition.clone())
+            })
+            .collect::<Vec<_>>();
+        self.insert_all(aliased)?;
+        Ok(self)
+    }
+
+    /// Removes the definition for `module` and `name` from this [`Linker`].
+    ///
+    /// Returns `true` if there was such a definition.
+    pub fn remove(&mut self, module: &str, name: &str) -> bool {
+        let key = match (self.strings.get(module), self.strings.get(name)) {
+            (Some(module), Some(name)) => ImportKey { module, name },
+            _ => return false,
+        };
+        self.definitions.remove(&key).is_some()
+    }
+
+    /// Returns an iterator over the module name, item name and type of all definitions.
+    ///
+    /// # Panics
+    ///
+    /// If the [`Engine`] of this [`Linker`] and the [`Engine`] of `context` are not the same.
+    pub fn iter<'a>(
+        &'a self,
+        context: impl AsContext<UserState = T> + 'a,
+    ) -> impl Iterator<Item = (&'a str, &'a str, ExternType)> + 'a {
+        assert!(Engine::same(self.engine(), context.as_context().engine()));
+        self.definitions.iter().map(move |(key, definition)| {
+            let (module, name) = self
+                .resolve_import_key(*key)
+                .unwrap_or_else(|| panic!("encountered missing import names for key {key:?}"));
+            (module, name, definition.ty(&context))
+        })
+    }
+
     /// Creates a new named [`Func::new`]-style host [`Func`] for this [`Linker`].
     ///
     /// For more information see [`Linker::func_wrap`].
     ///
     /// # Errors
     ///
-    /// If there already is a definition under the same name for this [`Linker`].
+    /// If there already is a definition under the same name for this [`Linker`]
+    /// and shadowing is not allowed.
     pub fn func_new(
         &mut self,
         module: &str,
@@ -538,7 +671,8 @@ impl<T> Linker<T> {
     ///
     /// # Errors
     ///
-    /// If there already is a definition under the same name for this [`Linker`].
+    /// If there already is a definition under the same name for this [`Linker`]
+    /// and shadowing is not allowed.
     ///
     /// [`Store`]: crate::Store
     pub fn func_wrap<Params, Args>(
@@ -553,6 +687,259 @@ impl<T> Linker<T> {
         Ok(self)
     }
 
+    /// Creates a new named [`Func::wrap`]-style host [`Func`] that charges `fuel` per call.
+    ///
+    /// For information how to use this API see [`Linker::func_wrap`] and [`Func::wrap_with_fuel`].
+    ///
+    /// # Errors
+    ///
+    /// If there already is a definition under the same name for this [`Linker`]
+    /// and shadowing is not allowed.
+    pub fn func_wrap_with_fuel<Params, Args>(
+        &mut self,
+        module: &str,
+        name: &str,
+        fuel: impl Into<HostFuel>,
+        func: impl IntoFunc<T, Params, Args>,
+    ) -> Result<&mut Self, LinkerError> {
+        let func = HostFuncTrampolineEntity::wrap(&self.engine, func).with_fuel(fuel.into());
+        let key = self.import_key(module, name);
+        self.insert(key, Definition::HostFunc(func))?;
+        Ok(self)
+    }
+
+    /// Defines all exports of the `instance` under the `module_name` in this [`Linker`].
+    ///
+    /// # Note
+    ///
+    /// This allows to link other modules against the exports of the `instance`.
+    ///
+    /// # Errors
+    ///
+    /// If there already is a definition under the same name as one of the
+    /// exports of the `instance` for this [`Linker`] and shadowing is not allowed.
+    /// In this case none of the exports of the `instance` are defined.
+    ///
+    /// # Panics
+    ///
+    /// If the [`Engine`] of this [`Linker`] and the [`Engine`] of `context` are not the same.
+    pub fn instance(
+        &mut self,
+        context: impl AsContext<UserState = T>,
+        module_name: &str,
+        instance: Instance,
+    ) -> Result<&mut Self, LinkerError> {
+        assert!(Engine::same(self.engine(), context.as_context().engine()));
+        let exports = instance
+            .exports(context.as_context())
+            .map(|export| {
+                let key = self.import_key(module_name, export.name());
+                (key, Definition::Extern(export.into_extern()))
+            })
+            .collect::<Vec<_>>();
+        self.insert_all(exports)?;
+        Ok(self)
+    }
+
+    /// Defines the exports of the `module` under the module `name` in this [`Linker`].
+    ///
+    /// The `module` is treated according to the WASI command and reactor conventions:
+    ///
+    /// - A command is a [`Module`] that exports a `_start` function.
+    ///   Every exported function of a command is defined as a host function that
+    ///   creates a fresh instance of the `module` for every call before calling
+    ///   the export on it. All other exports of a command are ignored.
+    /// - A reactor is any other [`Module`]. It is instantiated once within `context`
+    ///   and its `_initialize` function is called if exported. Afterwards all
+    ///   exports of the instance are defined as if by [`Linker::instance`].
+    ///
+    /// # Note
+    ///
+    /// The imports of the `module` are resolved using the definitions of
+    /// this [`Linker`] at the time this method is called.
+    ///
+    /// # Errors
+    ///
+    /// - If there already is a definition under the same name as one of the
+    ///   exports of the `module` for this [`Linker`] and shadowing is not allowed.
+    ///   In this case none of the exports of the `module` are defined and
+    ///   a reactor is neither instantiated nor initialized.
+    /// - If a reactor fails to instantiate or its `_initialize` function traps.
+    ///
+    /// # Panics
+    ///
+    /// If the [`Engine`] of this [`Linker`] and the [`Engine`] of `context` are not the same.
+    pub fn module(
+        &mut self,
+        mut context: impl AsContextMut<UserState = T>,
+        name: &str,
+        module: &Module,
+    ) -> Result<&mut Self, Error>
+    where
+        T: 'static,
+    {
+        assert!(Engine::same(self.engine(), context.as_context().engine()));
+        if !matches!(module.get_export("_start"), Some(ExternType::Func(_))) {
+            self.ensure_undefined_exports(name, module)?;
+            let instance = self
+                .instantiate(&mut context, module)?
+                .start(&mut context)?;
+            if let Some(initialize) = instance.get_func(&context, "_initialize") {
+                initialize
+                    .typed::<(), ()>(&context)?
+                    .call(&mut context, ())?;
+            }
+            self.instance(&context, name, instance)?;
+            return Ok(self);
+        }
+        let linker = self.clone();
+        let commands = module
+            .exports()
+            .filter_map(|export| match export.ty() {
+                ExternType::Func(func_type) => Some((export.name().to_string(), func_type.clone())),
+                _ => None,
+            })
+            .collect::<Vec<(String, FuncType)>>();
+        let definitions = commands
+            .into_iter()
+            .map(|(export, func_type)| {
+                let linker = linker.clone();
+                let module = module.clone();
+                let key = self.import_key(name, &export);
+                let func = HostFuncTrampolineEntity::new(
+                    &self.engine,
+                    func_type,
+                    move |mut caller: Caller<'_, T>, params: &[Value], results: &mut [Value]| {
+                        let instance = linker
+                            .instantiate(&mut caller, &module)
+                            .and_then(|instance| instance.start(&mut caller))
+                            .map_err(into_trap)?;
+                        instance
+                            .get_func(&caller, &export)
+                            .unwrap_or_else(|| panic!("missing exported function: {export}"))
+                            .call(&mut caller, params, results)
+                            .map_err(into_trap)
+                    },
+                );
+                (key, Definition::HostFunc(func))
+            })
+            .collect::<Vec<_>>();
+        self.insert_all(definitions)?;
+        Ok(self)
+    }
+
+    /// Defines all function imports of `module` that are not yet defined as trapping host functions.
+    ///
+    /// # Note
+    ///
+    /// This allows to instantiate modules that import optional functions
+    /// which trap only once they are actually called.
+    ///
+    /// # Errors
+    ///
+    /// This never fails since only imports without definition are defined.
+    ///
+    /// # Panics
+    ///
+    /// If the [`Engine`] of this [`Linker`] and the [`Engine`] of `module` are not the same.
+    pub fn define_unknown_imports_as_traps(
+        &mut self,
+        module: &Module,
+    ) -> Result<&mut Self, LinkerError> {
+        self.define_unknown_imports(module, |import, _| {
+            let message = format!(
+                "unknown import: `{}::{}` has not been defined",
+                import.module(),
+                import.name()
+            );
+            move |_: Caller<'_, T>, _: &[Value], _: &mut [Value]| Err(Trap::new(message.clone()))
+        })
+    }
+
+    /// Defines all function imports of `module` that are not yet defined as host
+    /// functions that return the default values of their result types.
+    ///
+    /// # Note
+    ///
+    /// The default values are zero for numbers and null for references.
+    ///
+    /// # Errors
+    ///
+    /// This never fails since only imports without definition are defined.
+    ///
+    /// # Panics
+    ///
+    /// If the [`Engine`] of this [`Linker`] and the [`Engine`] of `module` are not the same.
+    pub fn define_unknown_imports_as_default_values(
+        &mut self,
+        module: &Module,
+    ) -> Result<&mut Self, LinkerError> {
+        self.define_unknown_imports(module, |_, func_type| {
+            let result_types = Arc::<[ValueType]>::from(func_type.results());
+            move |_: Caller<'_, T>, _: &[Value], results: &mut [Value]| {
+                for (result, ty) in results.iter_mut().zip(result_types.iter()) {
+                    *result = Value::default(*ty);
+                }
+                Ok(())
+            }
+        })
+    }
+
+    /// Defines all function imports of `module` that are not yet defined as host functions.
+    ///
+    /// The host function of every such import is created by `make_func` from the import and its type.
+    fn define_unknown_imports<F>(
+        &mut self,
+        module: &Module,
+        mut make_func: impl FnMut(&ImportType, &FuncType) -> F,
+    ) -> Result<&mut Self, LinkerError>
+    where
+        F: Fn(Caller<'_, T>, &[Value], &mut [Value]) -> Result<(), Trap> + Send + Sync + 'static,
+    {
+        assert!(Engine::same(self.engine(), module.engine()));
+        for import in module.imports() {
+            let func_type = match import.ty() {
+                ExternType::Func(func_type) => func_type.clone(),
+                _ => continue,
+            };
+            if self.definition(import.module(), import.name()).is_some() {
+                continue;
+            }
+            let func = make_func(&import, &func_type);
+            self.func_new(import.module(), import.name(), func_type, func)?;
+        }
+        Ok(self)
+    }
+
+    /// Sets the resolver that is consulted for imports without definition in this [`Linker`].
+    ///
+    /// # Note
+    ///
+    /// - The resolved item must satisfy the type of the import and belong to the
+    ///   [`Store`] into which the [`Module`] is instantiated.
+    /// - Returning `None` reports the import as missing.
+    /// - This replaces the previous resolver if any.
+    ///
+    /// [`Store`]: crate::Store
+    pub fn set_fallback(
+        &mut self,
+        fallback: impl Fn(&ImportType) -> Option<Extern> + Send + Sync + 'static,
+    ) -> &mut Self {
+        self.fallback = Some(Arc::new(fallback));
+        self
+    }
+
+    /// Resolves the [`Definition`] of the `import` by name or via the fallback resolver if any.
+    ///
+    /// Returns `None` if the `import` is neither defined nor resolved by the fallback.
+    fn resolve_import(&self, import: &ImportType) -> Option<Definition<T>> {
+        if let Some(definition) = self.definition(import.module(), import.name()) {
+            return Some(definition.clone());
+        }
+        let fallback = self.fallback.as_ref()?;
+        fallback(import).map(Definition::Extern)
+    }
+
     /// Returns the import key for the module name and item name.
     fn import_key(&mut self, module: &str, name: &str) -> ImportKey {
         ImportKey {
@@ -572,15 +959,15 @@ impl<T> Linker<T> {
     ///
     /// # Errors
     ///
-    /// If there already is a definition for the import key for this [`Linker`].
+    /// If there already is a definition for the import key for this [`Linker`]
+    /// and shadowing is not allowed.
     fn insert(&mut self, key: ImportKey, item: Definition<T>) -> Result<(), LinkerError> {
         match self.definitions.entry(key) {
+            Entry::Occupied(mut o) if self.allow_shadowing => {
+                o.insert(item);
+            }
             Entry::Occupied(_) => {
-                let (module_name, field_name) = self
-                    .resolve_import_key(key)
-                    .unwrap_or_else(|| panic!("encountered missing import names for key {key:?}"));
-                let import_name = ImportName::new(module_name, field_name);
-                return Err(LinkerError::DuplicateDefinition { import_name });
+                return Err(self.duplicate_definition(key));
             }
             Entry::Vacant(v) => {
                 v.insert(item);
@@ -589,6 +976,64 @@ impl<T> Linker<T> {
         Ok(())
     }
 
+    /// Inserts all `items` into the [`Linker`] or none of them.
+    ///
+    /// # Errors
+    ///
+    /// If any of the `items` is already defined in the [`Linker`] and shadowing is not allowed.
+    /// In this case the [`Linker`] remains unchanged.
+    fn insert_all(&mut self, items: Vec<(ImportKey, Definition<T>)>) -> Result<(), LinkerError> {
+        if !self.allow_shadowing {
+            let duplicate = items
+                .iter()
+                .map(|(key, _)| *key)
+                .find(|key| self.definitions.contains_key(key));
+            if let Some(key) = duplicate {
+                return Err(self.duplicate_definition(key));
+            }
+        }
+        self.definitions.extend(items);
+        Ok(())
+    }
+
+    /// Ensures that none of the exports of `module` is defined under `module_name` yet.
+    ///
+    /// # Note
+    ///
+    /// This allows to reject a [`Module`] before it is instantiated.
+    ///
+    /// # Errors
+    ///
+    /// If there already is a definition under the same name as one of the
+    /// exports of the `module` for this [`Linker`] and shadowing is not allowed.
+    fn ensure_undefined_exports(
+        &self,
+        module_name: &str,
+        module: &Module,
+    ) -> Result<(), LinkerError> {
+        if self.allow_shadowing {
+            return Ok(());
+        }
+        match module
+            .exports()
+            .find(|export| self.definition(module_name, export.name()).is_some())
+        {
+            Some(export) => Err(LinkerError::DuplicateDefinition {
+                import_name: ImportName::new(module_name, export.name()),
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// Returns the [`LinkerError::DuplicateDefinition`] for the already defined `key`.
+    fn duplicate_definition(&self, key: ImportKey) -> LinkerError {
+        let (module_name, field_name) = self
+            .resolve_import_key(key)
+            .unwrap_or_else(|| panic!("encountered missing import names for key {key:?}"));
+        let import_name = ImportName::new(module_name, field_name);
+        LinkerError::DuplicateDefinition { import_name }
+    }
+
     /// Looks up a defined [`Extern`] by name in this [`Linker`].
     ///
     /// - Returns `None` if this name was not previously defined in this [`Linker`].
@@ -626,6 +1071,13 @@ impl<T> Linker<T> {
             context.as_context().store.engine(),
             self.engine()
         ));
+        self.definition(module, name)
+    }
+
+    /// Looks up a [`Definition`] by name in this [`Linker`] without checking its [`Engine`].
+    ///
+    /// Returns `None` if this name was not previously defined in this [`Linker`].
+    fn definition(&self, module: &str, name: &str) -> Option<&Definition<T>> {
         let key = ImportKey {
             module: self.strings.get(module)?,
             name: self.strings.get(name)?,
@@ -658,6 +1110,68 @@ impl<T> Linker<T> {
         module.instantiate(context, externals)
     }
 
+    /// Resolves and type checks the imports of `module` once for many instantiations.
+    ///
+    /// The returned [`LinkedModule`] can be instantiated into many different [`Store`]s
+    /// of the same [`Engine`] via [`LinkedModule::instantiate`] without resolving
+    /// and type checking the imports of `module` again.
+    ///
+    /// # Note
+    ///
+    /// - Host functions defined in this [`Linker`] are materialized per [`Store`] upon instantiation.
+    /// - Items defined via [`Linker::define`] or [`Linker::instance`] belong to a single
+    ///   [`Store`] and are type checked upon instantiation since their types may change.
+    ///   A [`LinkedModule`] that imports such items can only be instantiated into their [`Store`].
+    /// - Later changes to this [`Linker`] do not affect the returned [`LinkedModule`].
+    ///
+    /// # Errors
+    ///
+    /// - If the linker does not define imports of the [`Module`].
+    /// - If any imported host function does not satisfy its type requirements.
+    ///
+    /// # Panics
+    ///
+    /// If the [`Engine`] of the [`Linker`] and the [`Engine`] of `module` are not the same.
+    ///
+    /// [`Store`]: crate::Store
+    pub fn instantiate_pre(&self, module: &Module) -> Result<LinkedModule<T>, Error> {
+        assert!(Engine::same(self.engine(), module.engine()));
+        let definitions = module
+            .imports()
+            .map(|import| {
+                let resolved = self
+                    .resolve_import(&import)
+                    .ok_or_else(|| LinkerError::missing_definition(&import))?;
+                if let Definition::HostFunc(host_func) = &resolved {
+                    let found_type = self
+                        .engine
+                        .resolve_func_type(host_func.ty_dedup(), FuncType::clone);
+                    match import.ty() {
+                        ExternType::Func(expected_type) if &found_type == expected_type => {}
+                        ExternType::Func(expected_type) => {
+                            return Err(LinkerError::func_type_mismatch(
+                                import.import_name(),
+                                expected_type,
+                                &found_type,
+                            ))
+                        }
+                        _ => {
+                            return Err(LinkerError::invalid_type_definition(
+                                &import,
+                                &ExternType::Func(found_type),
+                            ))
+                        }
+                    }
+                }
+                Ok(resolved)
+            })
+            .collect::<Result<Arc<[Definition<T>]>, LinkerError>>()?;
+        Ok(LinkedModule {
+            module: module.clone(),
+            definitions,
+        })
+    }
+
     /// Processes a single [`Module`] import.
     ///
     /// # Panics
@@ -669,78 +1183,169 @@ impl<T> Linker<T> {
     /// If the imported item does not satisfy constraints set by the [`Module`].
     fn process_import(
         &self,
-        mut context: impl AsContextMut<UserState = T>,
+        context: impl AsContextMut<UserState = T>,
         import: ImportType,
     ) -> Result<Extern, Error> {
         assert!(Engine::same(self.engine(), context.as_context().engine()));
-        let import_name = import.import_name();
-        let module_name = import.module();
-        let field_name = import.name();
         let resolved = self
-            .get_definition(context.as_context(), module_name, field_name)
+            .resolve_import(&import)
             .ok_or_else(|| LinkerError::missing_definition(&import))?;
-        let invalid_type = || LinkerError::invalid_type_definition(&import, &resolved.ty(&context));
-        match import.ty() {
-            ExternType::Func(expected_type) => {
-                let found_type = resolved
-                    .ty(&context)
-                    .func()
-                    .cloned()
-                    .ok_or_else(invalid_type)?;
-                if &found_type != expected_type {
-                    return Err(LinkerError::func_type_mismatch(
-                        import_name,
-                        expected_type,
-                        &found_type,
-                    ))
-                    .map_err(Into::into);
+        process_definition(context, &import, &resolved)
+    }
+}
+
+/// A [`Module`] with all of its imports resolved and type checked by a [`Linker`].
+///
+/// Created via [`Linker::instantiate_pre`].
+///
+/// # Note
+///
+/// Cloning a [`LinkedModule`] is cheap since it only clones references to its contents.
+pub struct LinkedModule<T> {
+    /// The [`Module`] to instantiate.
+    module: Module,
+    /// The resolved definitions of the imports of the [`Module`] in order.
+    definitions: Arc<[Definition<T>]>,
+}
+
+impl<T> Clone for LinkedModule<T> {
+    fn clone(&self) -> Self {
+        Self {
+            module: self.module.clone(),
+            definitions: self.definitions.clone(),
+        }
+    }
+}
+
+impl<T> Debug for LinkedModule<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LinkedModule")
+            .field("module", &self.module)
+            .field("definitions", &self.definitions.len())
+            .finish()
+    }
+}
+
+impl<T> LinkedModule<T> {
+    /// Returns the [`Module`] of the [`LinkedModule`].
+    pub fn module(&self) -> &Module {
+        &self.module
+    }
+
+    /// Instantiates the [`Module`] into the `context` using the resolved imports.
+    ///
+    /// # Errors
+    ///
+    /// - If an imported item defined via [`Linker::define`] no longer satisfies its type requirements.
+    /// - If the instantiation of the [`Module`] fails.
+    ///
+    /// # Panics
+    ///
+    /// - If the [`Engine`] of the [`Module`] and `context` are not the same.
+    /// - If an imported item defined via [`Linker::define`] does not belong to `context`.
+    pub fn instantiate(
+        &self,
+        mut context: impl AsContextMut<UserState = T>,
+    ) -> Result<InstancePre, Error> {
+        assert!(Engine::same(
+            self.module.engine(),
+            context.as_context().engine()
+        ));
+        let externals = self
+            .module
+            .imports()
+            .zip(self.definitions.iter())
+            .map(|(import, definition)| match definition {
+                Definition::HostFunc(_) => {
+                    let func = definition
+                        .as_func(&mut context)
+                        .expect("host function definitions are functions");
+                    Ok(Extern::Func(func))
                 }
-                let func = resolved
-                    .as_func(&mut context)
-                    .expect("already asserted that `resolved` is a function");
-                Ok(Extern::Func(func))
-            }
-            ExternType::Table(expected_type) => {
-                let table = resolved
-                    .as_extern()
-                    .copied()
-                    .and_then(Extern::into_table)
-                    .ok_or_else(invalid_type)?;
-                let found_type = table.dynamic_ty(context);
-                found_type.is_subtype_or_err(expected_type).map_err(|_| {
-                    LinkerError::table_type_mismatch(import_name, expected_type, &found_type)
-                })?;
-                Ok(Extern::Table(table))
-            }
-            ExternType::Memory(expected_type) => {
-                let memory = resolved
-                    .as_extern()
-                    .copied()
-                    .and_then(Extern::into_memory)
-                    .ok_or_else(invalid_type)?;
-                let found_type = memory.dynamic_ty(context);
-                found_type.is_subtype_or_err(expected_type).map_err(|_| {
-                    LinkerError::invalid_memory_subtype(import_name, expected_type, &found_type)
-                })?;
-                Ok(Extern::Memory(memory))
+                Definition::Extern(_) => process_definition(&mut context, &import, definition),
+            })
+            .collect::<Result<Vec<Extern>, Error>>()?;
+        self.module.instantiate_resolved(context, externals)
+    }
+}
+
+/// Type checks the `resolved` [`Definition`] of the `import` and returns it as [`Extern`].
+///
+/// # Note
+///
+/// This allocates a new [`Func`] on the `context` if `resolved` is a [`Linker`]
+/// defined host function.
+///
+/// # Errors
+///
+/// If the `resolved` definition does not satisfy the type of the `import`.
+fn process_definition<T>(
+    mut context: impl AsContextMut<UserState = T>,
+    import: &ImportType,
+    resolved: &Definition<T>,
+) -> Result<Extern, Error> {
+    let import_name = import.import_name();
+    let invalid_type = || LinkerError::invalid_type_definition(import, &resolved.ty(&context));
+    match import.ty() {
+        ExternType::Func(expected_type) => {
+            let found_type = resolved
+                .ty(&context)
+                .func()
+                .cloned()
+                .ok_or_else(invalid_type)?;
+            if &found_type != expected_type {
+                return Err(LinkerError::func_type_mismatch(
+                    import_name,
+                    expected_type,
+                    &found_type,
+                ))
+                .map_err(Into::into);
             }
-            ExternType::Global(expected_type) => {
-                let global = resolved
-                    .as_extern()
-                    .copied()
-                    .and_then(Extern::into_global)
-                    .ok_or_else(invalid_type)?;
-                let found_type = global.ty(context);
-                if &found_type != expected_type {
-                    return Err(LinkerError::global_type_mismatch(
-                        import_name,
-                        expected_type,
-                        &found_type,
-                    ))
-                    .map_err(Into::into);
-                }
-                Ok(Extern::Global(global))
+            let func = resolved
+                .as_func(&mut context)
+                .expect("already asserted that `resolved` is a function");
+            Ok(Extern::Func(func))
+        }
+        ExternType::Table(expected_type) => {
+            let table = resolved
+                .as_extern()
+                .copied()
+                .and_then(Extern::into_table)
+                .ok_or_else(invalid_type)?;
+            let found_type = table.dynamic_ty(context);
+            found_type.is_subtype_or_err(expected_type).map_err(|_| {
+                LinkerError::table_type_mismatch(import_name, expected_type, &found_type)
+            })?;
+            Ok(Extern::Table(table))
+        }
+        ExternType::Memory(expected_type) => {
+            let memory = resolved
+                .as_extern()
+                .copied()
+                .and_then(Extern::into_memory)
+                .ok_or_else(invalid_type)?;
+            let found_type = memory.dynamic_ty(context);
+            found_type.is_subtype_or_err(expected_type).map_err(|_| {
+                LinkerError::invalid_memory_subtype(import_name, expected_type, &found_type)
+            })?;
+            Ok(Extern::Memory(memory))
+        }
+        ExternType::Global(expected_type) => {
+            let global = resolved
+                .as_extern()
+                .copied()
+                .and_then(Extern::into_global)
+                .ok_or_else(invalid_type)?;
+            let found_type = global.ty(context);
+            if &found_type != expected_type {
+                return Err(LinkerError::global_type_mismatch(
+                    import_name,
+                    expected_type,
+                    &found_type,
+                ))
+                .map_err(Into::into);
             }
+            Ok(Extern::Global(global))
         }
     }
 }
@@ -750,7 +1355,7 @@ mod tests {
     use wasmi_core::ValueType;
 
     use super::*;
-    use crate::Store;
+    use crate::{Global, Mutability, Store};
 
     struct HostState {
         a: i32,
@@ -851,4 +1456,517 @@ mod tests {
         wasm_set_b.call(&mut store, 200).unwrap();
         assert_eq!(wasm_get_b.call(&mut store, ()).unwrap(), 200);
     }
+
+    /// Compiles the `wat` text format module using `engine`.
+    fn compile(engine: &Engine, wat: &str) -> Module {
+        let wasm = wat::parse_str(wat).unwrap();
+        Module::new(engine, &mut &wasm[..]).unwrap()
+    }
+
+    /// A module that imports `double` from the `lib` module.
+    const CLIENT: &str = r#"
+        (module
+            (import "lib" "double" (func $double (param i32) (result i32)))
+            (func (export "quadruple") (param i32) (result i32)
+                (call $double (call $double (local.get 0)))
+            )
+        )
+    "#;
+
+    #[test]
+    fn linker_instance_works() {
+        let engine = Engine::default();
+        let mut store = <Store<()>>::new(&engine, ());
+        let mut linker = <Linker<()>>::new(&engine);
+        let lib = compile(
+            &engine,
+            r#"
+                (module
+                    (memory (export "memory") 1)
+                    (global (export "answer") i32 (i32.const 42))
+                    (func (export "double") (param i32) (result i32)
+                        (i32.add (local.get 0) (local.get 0))
+                    )
+                )
+            "#,
+        );
+        let lib = linker
+            .instantiate(&mut store, &lib)
+            .unwrap()
+            .start(&mut store)
+            .unwrap();
+        linker.instance(&store, "lib", lib).unwrap();
+        assert!(matches!(
+            linker.get(&store, "lib", "memory"),
+            Some(Extern::Memory(_))
+        ));
+        assert!(matches!(
+            linker.get(&store, "lib", "answer"),
+            Some(Extern::Global(_))
+        ));
+        let client = compile(&engine, CLIENT);
+        let quadruple = linker
+            .instantiate(&mut store, &client)
+            .unwrap()
+            .start(&mut store)
+            .unwrap()
+            .get_typed_func::<i32, i32>(&store, "quadruple")
+            .unwrap();
+        assert_eq!(quadruple.call(&mut store, 5).unwrap(), 20);
+        assert!(matches!(
+            linker.instance(&store, "lib", lib),
+            Err(LinkerError::DuplicateDefinition { .. })
+        ));
+    }
+
+    #[test]
+    fn linker_module_reactor_works() {
+        let engine = Engine::default();
+        let mut store = <Store<()>>::new(&engine, ());
+        let mut linker = <Linker<()>>::new(&engine);
+        let reactor = compile(
+            &engine,
+            r#"
+                (module
+                    (global $factor (mut i32) (i32.const 0))
+                    (func (export "_initialize")
+                        (global.set $factor (i32.const 2))
+                    )
+                    (func (export "double") (param i32) (result i32)
+                        (i32.mul (local.get 0) (global.get $factor))
+                    )
+                )
+            "#,
+        );
+        linker.module(&mut store, "lib", &reactor).unwrap();
+        assert!(matches!(
+            linker.get(&store, "lib", "_initialize"),
+            Some(Extern::Func(_))
+        ));
+        let client = compile(&engine, CLIENT);
+        let quadruple = linker
+            .instantiate(&mut store, &client)
+            .unwrap()
+            .start(&mut store)
+            .unwrap()
+            .get_typed_func::<i32, i32>(&store, "quadruple")
+            .unwrap();
+        assert_eq!(quadruple.call(&mut store, 5).unwrap(), 20);
+    }
+
+    #[test]
+    fn linker_module_reactor_duplicate_is_not_initialized() {
+        let engine = Engine::default();
+        let mut store = <Store<()>>::new(&engine, ());
+        let mut linker = <Linker<()>>::new(&engine);
+        linker
+            .func_wrap("lib", "double", |value: i32| value * 2)
+            .unwrap();
+        let reactor = compile(
+            &engine,
+            r#"
+                (module
+                    (func (export "_initialize")
+                        (unreachable)
+                    )
+                    (func (export "double") (param i32) (result i32)
+                        (i32.add (local.get 0) (local.get 0))
+                    )
+                )
+            "#,
+        );
+        // The duplicate is reported instead of the trap of `_initialize`.
+        assert!(matches!(
+            linker.module(&mut store, "lib", &reactor),
+            Err(Error::Linker(LinkerError::DuplicateDefinition { .. }))
+        ));
+        assert!(linker.get(&store, "lib", "_initialize").is_none());
+        linker.allow_shadowing(true);
+        assert!(matches!(
+            linker.module(&mut store, "lib", &reactor),
+            Err(Error::Trap(_))
+        ));
+    }
+
+    #[test]
+    fn linker_module_command_works() {
+        let engine = Engine::default();
+        let mut store = <Store<()>>::new(&engine, ());
+        let mut linker = <Linker<()>>::new(&engine);
+        let command = compile(
+            &engine,
+            r#"
+                (module
+                    (global $calls (mut i32) (i32.const 0))
+                    (memory (export "memory") 1)
+                    (func (export "_start"))
+                    (func (export "double") (param i32) (result i32)
+                        (global.set $calls (i32.add (global.get $calls) (i32.const 1)))
+                        (i32.mul
+                            (i32.add (local.get 0) (local.get 0))
+                            (global.get $calls)
+                        )
+                    )
+                )
+            "#,
+        );
+        linker.module(&mut store, "lib", &command).unwrap();
+        // Non-function exports of commands are not defined.
+        assert!(linker.get(&store, "lib", "memory").is_none());
+        let client = compile(&engine, CLIENT);
+        let quadruple = linker
+            .instantiate(&mut store, &client)
+            .unwrap()
+            .start(&mut store)
+            .unwrap()
+            .get_typed_func::<i32, i32>(&store, "quadruple")
+            .unwrap();
+        // Every call creates a fresh instance so that `$calls` is always 1.
+        assert_eq!(quadruple.call(&mut store, 5).unwrap(), 20);
+        assert_eq!(quadruple.call(&mut store, 5).unwrap(), 20);
+    }
+
+    #[test]
+    fn linker_instantiate_pre_works() {
+        let engine = Engine::default();
+        let mut linker = <Linker<HostState>>::new(&engine);
+        linker
+            .func_wrap("host", "get_a", |ctx: Caller<HostState>| ctx.data().a)
+            .unwrap();
+        let module = compile(
+            &engine,
+            r#"
+                (module
+                    (import "host" "get_a" (func $get_a (result i32)))
+                    (global $calls (mut i32) (i32.const 0))
+                    (func (export "run") (result i32)
+                        (global.set $calls (i32.add (global.get $calls) (i32.const 1)))
+                        (i32.add (call $get_a) (global.get $calls))
+                    )
+                )
+            "#,
+        );
+        let linked = linker.instantiate_pre(&module).unwrap();
+        // Later changes to the linker do not affect the linked module.
+        linker
+            .func_wrap("host", "get_b", |ctx: Caller<HostState>| ctx.data().b)
+            .unwrap();
+        for a in [10, 20] {
+            let mut store = <Store<HostState>>::new(&engine, HostState { a, b: 0 });
+            let run = linked
+                .clone()
+                .instantiate(&mut store)
+                .unwrap()
+                .start(&mut store)
+                .unwrap()
+                .get_typed_func::<(), i32>(&store, "run")
+                .unwrap();
+            assert_eq!(run.call(&mut store, ()).unwrap(), a + 1);
+            assert_eq!(run.call(&mut store, ()).unwrap(), a + 2);
+            // Instances of the same store do not share their state.
+            let run = linked
+                .instantiate(&mut store)
+                .unwrap()
+                .start(&mut store)
+                .unwrap()
+                .get_typed_func::<(), i32>(&store, "run")
+                .unwrap();
+            assert_eq!(run.call(&mut store, ()).unwrap(), a + 1);
+        }
+    }
+
+    #[test]
+    fn linker_instantiate_pre_errors() {
+        let engine = Engine::default();
+        let mut linker = <Linker<()>>::new(&engine);
+        linker.func_wrap("host", "f", |_: i64| {}).unwrap();
+        let missing = compile(&engine, r#"(module (import "host" "g" (func)))"#);
+        assert!(matches!(
+            linker.instantiate_pre(&missing),
+            Err(Error::Linker(LinkerError::MissingDefinition { .. }))
+        ));
+        let mismatch = compile(
+            &engine,
+            r#"(module (import "host" "f" (func (param i32))))"#,
+        );
+        assert!(matches!(
+            linker.instantiate_pre(&mismatch),
+            Err(Error::Linker(LinkerError::FuncTypeMismatch { .. }))
+        ));
+        let invalid = compile(&engine, r#"(module (import "host" "f" (global i32)))"#);
+        assert!(matches!(
+            linker.instantiate_pre(&invalid),
+            Err(Error::Linker(LinkerError::InvalidTypeDefinition { .. }))
+        ));
+    }
+
+    #[test]
+    fn linked_module_checks_imports_once() {
+        let engine = Engine::default();
+        let mut store = <Store<()>>::new(&engine, ());
+        let module = compile(
+            &engine,
+            r#"(module (import "host" "f" (func (param i32))))"#,
+        );
+        let mismatch = Func::wrap(&mut store, |_: i64| {});
+        assert!(matches!(
+            module.instantiate(&mut store, [Extern::Func(mismatch)]),
+            Err(Error::Instantiation(_))
+        ));
+        // Instantiating a `LinkedModule` does not check its already checked imports again.
+        assert!(module
+            .instantiate_resolved(&mut store, [Extern::Func(mismatch)])
+            .is_ok());
+        let mut linker = <Linker<()>>::new(&engine);
+        linker.func_wrap("host", "f", |_: i32| {}).unwrap();
+        linker
+            .instantiate_pre(&module)
+            .unwrap()
+            .instantiate(&mut store)
+            .unwrap()
+            .ensure_no_start(&mut store)
+            .unwrap();
+    }
+
+    /// A module importing two functions from the optional `env` module.
+    const OPTIONAL: &str = r#"
+        (module
+            (import "env" "get" (func $get (result i32 i64 f32 externref)))
+            (import "env" "log" (func $log (param i32)))
+            (func (export "get") (result i32 i64 f32 externref)
+                (call $get)
+            )
+            (func (export "log")
+                (call $log (i32.const 1))
+            )
+        )
+    "#;
+
+    #[test]
+    fn linker_unknown_imports_as_traps() {
+        let engine = Engine::default();
+        let mut store = <Store<()>>::new(&engine, ());
+        let mut linker = <Linker<()>>::new(&engine);
+        let module = compile(&engine, OPTIONAL);
+        assert!(linker.instantiate(&mut store, &module).is_err());
+        linker.func_wrap("env", "log", |_: i32| {}).unwrap();
+        linker.define_unknown_imports_as_traps(&module).unwrap();
+        let instance = linker
+            .instantiate(&mut store, &module)
+            .unwrap()
+            .start(&mut store)
+            .unwrap();
+        let log = instance.get_typed_func::<(), ()>(&store, "log").unwrap();
+        log.call(&mut store, ()).unwrap();
+        let get = instance.get_func(&store, "get").unwrap();
+        let mut results = [
+            Value::I32(0),
+            Value::I64(0),
+            Value::F32(0.0.into()),
+            Value::default(ValueType::ExternRef),
+        ];
+        let error = get.call(&mut store, &[], &mut results).unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("unknown import: `env::get` has not been defined"));
+    }
+
+    #[test]
+    fn linker_unknown_imports_as_default_values() {
+        let engine = Engine::default();
+        let mut store = <Store<()>>::new(&engine, ());
+        let mut linker = <Linker<()>>::new(&engine);
+        let module = compile(&engine, OPTIONAL);
+        linker
+            .define_unknown_imports_as_default_values(&module)
+            .unwrap();
+        let instance = linker
+            .instantiate(&mut store, &module)
+            .unwrap()
+            .start(&mut store)
+            .unwrap();
+        instance
+            .get_typed_func::<(), ()>(&store, "log")
+            .unwrap()
+            .call(&mut store, ())
+            .unwrap();
+        let get = instance.get_func(&store, "get").unwrap();
+        let mut results = [
+            Value::I32(1),
+            Value::I64(1),
+            Value::F32(1.0.into()),
+            Value::I32(1),
+        ];
+        get.call(&mut store, &[], &mut results).unwrap();
+        assert_eq!(results[0].i32(), Some(0));
+        assert_eq!(results[1].i64(), Some(0));
+        assert_eq!(results[2].f32(), Some(0.0.into()));
+        assert!(matches!(&results[3], Value::ExternRef(value) if value.is_null()));
+    }
+
+    #[test]
+    fn linker_fallback_works() {
+        let engine = Engine::default();
+        let mut store = <Store<()>>::new(&engine, ());
+        let mut linker = <Linker<()>>::new(&engine);
+        let module = compile(
+            &engine,
+            r#"
+                (module
+                    (import "env" "base" (global $base i32))
+                    (import "env" "double" (func $double (param i32) (result i32)))
+                    (func (export "run") (result i32)
+                        (call $double (global.get $base))
+                    )
+                )
+            "#,
+        );
+        let base = Global::new(&mut store, Value::I32(21), Mutability::Const);
+        let double = Func::wrap(&mut store, |x: i32| x * 2);
+        linker.set_fallback(move |import| match import.name() {
+            "base" => Some(base.into()),
+            "double" => Some(double.into()),
+            _ => None,
+        });
+        let linked = linker.instantiate_pre(&module).unwrap();
+        let run = linked
+            .instantiate(&mut store)
+            .unwrap()
+            .start(&mut store)
+            .unwrap()
+            .get_typed_func::<(), i32>(&store, "run")
+            .unwrap();
+        assert_eq!(run.call(&mut store, ()).unwrap(), 42);
+        // Definitions take precedence over the fallback.
+        linker.func_wrap("env", "double", |x: i32| x * 3).unwrap();
+        let run = linker
+            .instantiate(&mut store, &module)
+            .unwrap()
+            .start(&mut store)
+            .unwrap()
+            .get_typed_func::<(), i32>(&store, "run")
+            .unwrap();
+        assert_eq!(run.call(&mut store, ()).unwrap(), 63);
+        // Items resolved by the fallback are type checked.
+        linker.set_fallback(move |_| Some(double.into()));
+        assert!(matches!(
+            linker.instantiate(&mut store, &module),
+            Err(Error::Linker(LinkerError::InvalidTypeDefinition { .. }))
+        ));
+    }
+
+    #[test]
+    fn linker_shadowing_works() {
+        let engine = Engine::default();
+        let mut store = <Store<()>>::new(&engine, ());
+        let mut linker = <Linker<()>>::new(&engine);
+        linker.func_wrap("env", "value", || 1_i32).unwrap();
+        assert!(matches!(
+            linker.func_wrap("env", "value", || 2_i32),
+            Err(LinkerError::DuplicateDefinition { .. })
+        ));
+        linker.allow_shadowing(true);
+        linker.func_wrap("env", "value", || 2_i32).unwrap();
+        let module = compile(
+            &engine,
+            r#"
+                (module
+                    (import "env" "value" (func $value (result i32)))
+                    (func (export "run") (result i32) (call $value))
+                )
+            "#,
+        );
+        let run = linker
+            .instantiate(&mut store, &module)
+            .unwrap()
+            .start(&mut store)
+            .unwrap()
+            .get_typed_func::<(), i32>(&store, "run")
+            .unwrap();
+        assert_eq!(run.call(&mut store, ()).unwrap(), 2);
+    }
+
+    #[test]
+    fn linker_alias_and_remove_work() {
+        let engine = Engine::default();
+        let store = <Store<()>>::new(&engine, ());
+        let mut linker = <Linker<()>>::new(&engine);
+        linker
+            .func_wrap("env", "f", |x: i32| x)
+            .unwrap()
+            .func_wrap("env", "g", || {})
+            .unwrap();
+        linker.alias("env", "f", "other", "h").unwrap();
+        assert!(matches!(
+            linker.alias("env", "missing", "other", "h"),
+            Err(LinkerError::UnknownDefinition { .. })
+        ));
+        assert!(matches!(
+            linker.alias("env", "g", "other", "h"),
+            Err(LinkerError::DuplicateDefinition { .. })
+        ));
+        linker.alias_module("env", "copy").unwrap();
+        linker.alias_module("missing", "copy").unwrap();
+        let mut names = linker
+            .iter(&store)
+            .map(|(module, name, ty)| (module, name, ty.func().unwrap().params().len()))
+            .collect::<Vec<_>>();
+        names.sort();
+        assert_eq!(
+            names,
+            [
+                ("copy", "f", 1),
+                ("copy", "g", 0),
+                ("env", "f", 1),
+                ("env", "g", 0),
+                ("other", "h", 1),
+            ]
+        );
+        assert!(linker.remove("env", "f"));
+        assert!(!linker.remove("env", "f"));
+        assert!(!linker.remove("unknown", "f"));
+        assert_eq!(linker.iter(&store).count(), 4);
+        linker.func_wrap("env", "f", || {}).unwrap();
+    }
+
+    #[test]
+    fn linker_duplicates_leave_linker_unchanged() {
+        let engine = Engine::default();
+        let mut store = <Store<()>>::new(&engine, ());
+        let mut linker = <Linker<()>>::new(&engine);
+        linker
+            .func_wrap("env", "f", || {})
+            .unwrap()
+            .func_wrap("env", "g", || {})
+            .unwrap()
+            .func_wrap("partial", "g", || {})
+            .unwrap();
+        assert!(matches!(
+            linker.alias_module("env", "partial"),
+            Err(LinkerError::DuplicateDefinition { .. })
+        ));
+        assert!(linker.get(&store, "partial", "f").is_none());
+        assert_eq!(linker.iter(&store).count(), 3);
+        let lib = compile(
+            &engine,
+            r#"
+                (module
+                    (global (export "a") i32 (i32.const 1))
+                    (global (export "b") i32 (i32.const 2))
+                )
+            "#,
+        );
+        let lib = linker
+            .instantiate(&mut store, &lib)
+            .unwrap()
+            .start(&mut store)
+            .unwrap();
+        linker.alias("env", "g", "lib", "b").unwrap();
+        assert!(matches!(
+            linker.instance(&store, "lib", lib),
+            Err(LinkerError::DuplicateDefinition { .. })
+        ));
+        assert!(linker.get(&store, "lib", "a").is_none());
+        assert_eq!(linker.iter(&store).count(), 4);
+    }
 }
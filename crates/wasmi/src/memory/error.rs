@@ -22,6 +22,8 @@ pub enum MemoryError {
     },
     /// Tried to create too many memories
     TooManyMemories,
+    /// Tried to read a string of invalid UTF-8 from linear memory.
+    InvalidUtf8,
 }
 
 impl Display for MemoryError {
@@ -45,6 +47,9 @@ impl Display for MemoryError {
             Self::TooManyMemories => {
                 write!(f, "too many memories")
             }
+            Self::InvalidUtf8 => {
+                write!(f, "invalid UTF-8 string in linear memory")
+            }
         }
     }
 }
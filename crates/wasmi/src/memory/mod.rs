@@ -1,6 +1,10 @@
 mod buffer;
 mod data;
 mod error;
+mod ptr;
+mod view;
+#[cfg(feature = "debugger")]
+mod watch;
 
 #[cfg(test)]
 mod tests;
@@ -8,11 +12,19 @@ mod tests;
 use crate::{engine::executor::EntityGrowError, store::ResourceLimiterRef};
 
 use self::buffer::ByteBuffer;
+#[cfg(feature = "debugger")]
+pub(crate) use self::watch::MemoryWatchpoints;
+#[cfg(feature = "debugger")]
+pub use self::watch::{WatchKind, Watchpoint, WatchpointHit};
 pub use self::{
     data::{DataSegment, DataSegmentEntity, DataSegmentIdx},
     error::MemoryError,
+    ptr::{MemoryValue, WasmPtr, WasmSlice},
+    view::MemoryView,
 };
 use super::{AsContext, AsContextMut, StoreContext, StoreContextMut, Stored};
+#[cfg(feature = "debugger")]
+use core::ops::Range;
 use wasmi_arena::ArenaIndex;
 use wasmi_core::{Pages, TrapCode};
 
@@ -125,6 +137,8 @@ pub struct MemoryEntity {
     bytes: ByteBuffer,
     memory_type: MemoryType,
     current_pages: Pages,
+    #[cfg(feature = "debugger")]
+    watchpoints: MemoryWatchpoints,
 }
 
 impl MemoryEntity {
@@ -152,6 +166,8 @@ impl MemoryEntity {
                 bytes: ByteBuffer::new(initial_len),
                 memory_type,
                 current_pages: initial_pages,
+                #[cfg(feature = "debugger")]
+                watchpoints: MemoryWatchpoints::default(),
             };
             Ok(memory)
         } else {
@@ -288,6 +304,18 @@ impl MemoryEntity {
         slice.copy_from_slice(buffer);
         Ok(())
     }
+
+    /// Returns an exclusive reference to the watchpoints of the linear memory.
+    #[cfg(feature = "debugger")]
+    pub fn watchpoints_mut(&mut self) -> &mut MemoryWatchpoints {
+        &mut self.watchpoints
+    }
+
+    /// Returns a shared reference to the watchpoints of the linear memory.
+    #[cfg(feature = "debugger")]
+    pub fn watchpoints(&self) -> &MemoryWatchpoints {
+        &self.watchpoints
+    }
 }
 
 /// A Wasm linear memory reference.
@@ -421,6 +449,29 @@ impl Memory {
         (memory.data_mut(), store)
     }
 
+    /// Returns a [`MemoryView`] into the bytes underlying the [`Memory`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ctx` does not own this [`Memory`].
+    pub fn view<'a, T: 'a>(&self, ctx: impl Into<StoreContextMut<'a, T>>) -> MemoryView<'a> {
+        MemoryView::new(self.data_mut(ctx))
+    }
+
+    /// Returns a [`MemoryView`] into the bytes underlying the [`Memory`], and an exclusive
+    /// reference to the user provided state.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ctx` does not own this [`Memory`].
+    pub fn view_and_store_mut<'a, T: 'a>(
+        &self,
+        ctx: impl Into<StoreContextMut<'a, T>>,
+    ) -> (MemoryView<'a>, &'a mut T) {
+        let (data, store) = self.data_and_store_mut(ctx);
+        (MemoryView::new(data), store)
+    }
+
     /// Reads `n` bytes from `memory[offset..offset+n]` into `buffer`
     /// where `n` is the length of `buffer`.
     ///
@@ -466,4 +517,60 @@ impl Memory {
             .resolve_memory_mut(self)
             .write(offset, buffer)
     }
+
+    /// Adds a watchpoint observing `kind` accesses of Wasm executions to the address `range`.
+    ///
+    /// # Note
+    ///
+    /// - Loads, stores, `memory.fill`, `memory.copy` and `memory.init` that touch
+    ///   the `range` are reported to the hook set via [`Store::set_watchpoint_hook`].
+    ///   Executions under the debugger additionally pause before the access.
+    /// - Accesses by the host, e.g. via [`Memory::write`] or [`Memory::data_mut`],
+    ///   are not observed by watchpoints.
+    /// - This is only available with the `debugger` crate feature enabled.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ctx` does not own this [`Memory`].
+    ///
+    /// [`Store::set_watchpoint_hook`]: crate::Store::set_watchpoint_hook
+    #[cfg(feature = "debugger")]
+    pub fn add_watchpoint(
+        &self,
+        mut ctx: impl AsContextMut,
+        range: Range<usize>,
+        kind: WatchKind,
+    ) -> Watchpoint {
+        let inner = &mut ctx.as_context_mut().store.inner;
+        let watchpoint = inner.debugger_mut().add_watchpoint();
+        inner
+            .resolve_memory_mut(self)
+            .watchpoints_mut()
+            .add(watchpoint, range, kind);
+        watchpoint
+    }
+
+    /// Removes the `watchpoint` from the linear memory.
+    ///
+    /// Returns `true` if the `watchpoint` existed on this [`Memory`].
+    ///
+    /// # Note
+    ///
+    /// This is only available with the `debugger` crate feature enabled.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ctx` does not own this [`Memory`].
+    #[cfg(feature = "debugger")]
+    pub fn remove_watchpoint(&self, mut ctx: impl AsContextMut, watchpoint: Watchpoint) -> bool {
+        let inner = &mut ctx.as_context_mut().store.inner;
+        let removed = inner
+            .resolve_memory_mut(self)
+            .watchpoints_mut()
+            .remove(watchpoint);
+        if removed {
+            inner.debugger_mut().remove_watchpoint();
+        }
+        removed
+    }
 }
@@ -0,0 +1,422 @@
+use super::{MemoryError, MemoryView};
+use crate::{
+    core::{Trap, UntypedValue, ValueType, F32, F64},
+    WasmType,
+};
+use alloc::vec::Vec;
+use core::{fmt, marker::PhantomData, ops::Range};
+
+/// Types with a fixed little-endian representation in linear memory.
+///
+/// Every bit pattern of [`MemoryValue::SIZE`] bytes must be a valid value.
+///
+/// # Example
+///
+/// Structs are stored as the concatenation of their fields without padding:
+///
+/// ```
+/// use wasmi::MemoryValue;
+///
+/// #[derive(Debug, Copy, Clone)]
+/// struct Point {
+///     x: i32,
+///     y: i32,
+/// }
+///
+/// impl MemoryValue for Point {
+///     const SIZE: usize = 8;
+///
+///     fn from_bytes(bytes: &[u8]) -> Self {
+///         Self {
+///             x: i32::from_bytes(&bytes[..4]),
+///             y: i32::from_bytes(&bytes[4..]),
+///         }
+///     }
+///
+///     fn to_bytes(self, bytes: &mut [u8]) {
+///         self.x.to_bytes(&mut bytes[..4]);
+///         self.y.to_bytes(&mut bytes[4..]);
+///     }
+/// }
+/// ```
+pub trait MemoryValue: Copy {
+    /// The number of bytes of the representation in linear memory.
+    const SIZE: usize;
+
+    /// Decodes the value from its representation in `bytes`.
+    ///
+    /// # Panics
+    ///
+    /// If `bytes` is not [`MemoryValue::SIZE`] bytes long.
+    fn from_bytes(bytes: &[u8]) -> Self;
+
+    /// Encodes the value into its representation in `bytes`.
+    ///
+    /// # Panics
+    ///
+    /// If `bytes` is not [`MemoryValue::SIZE`] bytes long.
+    fn to_bytes(self, bytes: &mut [u8]);
+}
+
+macro_rules! impl_memory_value {
+    ( $( $ty:ty ),* $(,)? ) => {
+        $(
+            impl MemoryValue for $ty {
+                const SIZE: usize = core::mem::size_of::<$ty>();
+
+                fn from_bytes(bytes: &[u8]) -> Self {
+                    let mut buffer = [0x00_u8; core::mem::size_of::<$ty>()];
+                    buffer.copy_from_slice(bytes);
+                    <$ty>::from_le_bytes(buffer)
+                }
+
+                fn to_bytes(self, bytes: &mut [u8]) {
+                    bytes.copy_from_slice(&self.to_le_bytes());
+                }
+            }
+        )*
+    };
+}
+impl_memory_value!(u8, i8, u16, i16, u32, i32, u64, i64, u128, i128, f32, f64);
+
+impl MemoryValue for F32 {
+    const SIZE: usize = 4;
+
+    fn from_bytes(bytes: &[u8]) -> Self {
+        Self::from_bits(u32::from_bytes(bytes))
+    }
+
+    fn to_bytes(self, bytes: &mut [u8]) {
+        self.to_bits().to_bytes(bytes)
+    }
+}
+
+impl MemoryValue for F64 {
+    const SIZE: usize = 8;
+
+    fn from_bytes(bytes: &[u8]) -> Self {
+        Self::from_bits(u64::from_bytes(bytes))
+    }
+
+    fn to_bytes(self, bytes: &mut [u8]) {
+        self.to_bits().to_bytes(bytes)
+    }
+}
+
+impl<T, const N: usize> MemoryValue for [T; N]
+where
+    T: MemoryValue,
+{
+    const SIZE: usize = T::SIZE * N;
+
+    fn from_bytes(bytes: &[u8]) -> Self {
+        assert_eq!(bytes.len(), Self::SIZE);
+        core::array::from_fn(|index| T::from_bytes(&bytes[index * T::SIZE..][..T::SIZE]))
+    }
+
+    fn to_bytes(self, bytes: &mut [u8]) {
+        assert_eq!(bytes.len(), Self::SIZE);
+        for (index, value) in self.into_iter().enumerate() {
+            value.to_bytes(&mut bytes[index * T::SIZE..][..T::SIZE]);
+        }
+    }
+}
+
+/// A typed pointer into linear memory.
+///
+/// A [`WasmPtr`] is a 32-bit offset and can be used as parameter or result of host functions.
+/// Accesses are bounds checked and use the little-endian representation of [`MemoryValue`].
+pub struct WasmPtr<T> {
+    /// The offset into linear memory in bytes.
+    offset: u32,
+    /// The type of the pointee.
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<T> fmt::Debug for WasmPtr<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("WasmPtr").field(&self.offset).finish()
+    }
+}
+
+impl<T> Copy for WasmPtr<T> {}
+
+impl<T> Clone for WasmPtr<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> PartialEq for WasmPtr<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.offset == other.offset
+    }
+}
+
+impl<T> Eq for WasmPtr<T> {}
+
+impl<T> WasmPtr<T> {
+    /// Creates a new [`WasmPtr`] at the given `offset` in bytes.
+    pub const fn new(offset: u32) -> Self {
+        Self {
+            offset,
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns the offset of the [`WasmPtr`] in bytes.
+    pub fn offset(self) -> u32 {
+        self.offset
+    }
+
+    /// Returns `true` if the [`WasmPtr`] is the null pointer at offset 0.
+    pub fn is_null(self) -> bool {
+        self.offset == 0
+    }
+
+    /// Casts the [`WasmPtr`] to a pointer of another type at the same offset.
+    pub fn cast<U>(self) -> WasmPtr<U> {
+        WasmPtr::new(self.offset)
+    }
+
+    /// Returns the [`WasmSlice`] of `len` values starting at the [`WasmPtr`].
+    pub fn slice(self, len: u32) -> WasmSlice<T> {
+        WasmSlice::new(self, len)
+    }
+}
+
+impl<T: MemoryValue> WasmPtr<T> {
+    /// Returns the [`WasmPtr`] to the value `count` values after the [`WasmPtr`].
+    ///
+    /// Returns `None` if the offset overflows.
+    pub fn advance(self, count: u32) -> Option<Self> {
+        let size = u32::try_from(T::SIZE).ok()?;
+        count
+            .checked_mul(size)
+            .and_then(|delta| self.offset.checked_add(delta))
+            .map(Self::new)
+    }
+
+    /// Returns the byte range of the pointee.
+    fn range(self) -> Range<usize> {
+        let start = self.offset as usize;
+        start..start.saturating_add(T::SIZE)
+    }
+
+    /// Reads the pointee from the `view`.
+    ///
+    /// # Errors
+    ///
+    /// If the pointee is out of bounds of the `view`.
+    pub fn read(self, view: &MemoryView) -> Result<T, MemoryError> {
+        view.bytes(self.range()).map(T::from_bytes)
+    }
+
+    /// Writes `value` to the pointee in the `view`.
+    ///
+    /// # Errors
+    ///
+    /// If the pointee is out of bounds of the `view`.
+    pub fn write(self, view: &mut MemoryView, value: T) -> Result<(), MemoryError> {
+        view.bytes_mut(self.range())
+            .map(|bytes| value.to_bytes(bytes))
+    }
+}
+
+impl<T> MemoryValue for WasmPtr<T> {
+    const SIZE: usize = 4;
+
+    fn from_bytes(bytes: &[u8]) -> Self {
+        Self::new(u32::from_bytes(bytes))
+    }
+
+    fn to_bytes(self, bytes: &mut [u8]) {
+        self.offset.to_bytes(bytes)
+    }
+}
+
+impl<T> From<UntypedValue> for WasmPtr<T> {
+    fn from(value: UntypedValue) -> Self {
+        Self::new(u32::from(value))
+    }
+}
+
+impl<T> From<WasmPtr<T>> for UntypedValue {
+    fn from(ptr: WasmPtr<T>) -> Self {
+        Self::from(ptr.offset)
+    }
+}
+
+impl<T> WasmType for WasmPtr<T> {
+    fn ty() -> ValueType {
+        ValueType::I32
+    }
+
+    fn from_untyped(untyped: UntypedValue) -> Result<Self, Trap> {
+        Ok(Self::from(untyped))
+    }
+
+    fn into_untyped(self) -> UntypedValue {
+        self.into()
+    }
+}
+
+/// A typed slice of consecutive values in linear memory.
+pub struct WasmSlice<T> {
+    /// The pointer to the first value.
+    ptr: WasmPtr<T>,
+    /// The number of values.
+    len: u32,
+}
+
+impl<T> fmt::Debug for WasmSlice<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WasmSlice")
+            .field("ptr", &self.ptr)
+            .field("len", &self.len)
+            .finish()
+    }
+}
+
+impl<T> Copy for WasmSlice<T> {}
+
+impl<T> Clone for WasmSlice<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> WasmSlice<T> {
+    /// Creates a new [`WasmSlice`] of `len` values starting at `ptr`.
+    pub fn new(ptr: WasmPtr<T>, len: u32) -> Self {
+        Self { ptr, len }
+    }
+
+    /// Returns the [`WasmPtr`] to the first value of the [`WasmSlice`].
+    pub fn ptr(self) -> WasmPtr<T> {
+        self.ptr
+    }
+
+    /// Returns the number of values of the [`WasmSlice`].
+    pub fn len(self) -> u32 {
+        self.len
+    }
+
+    /// Returns `true` if the [`WasmSlice`] has no values.
+    pub fn is_empty(self) -> bool {
+        self.len == 0
+    }
+}
+
+impl<T: MemoryValue> WasmSlice<T> {
+    /// Returns the [`WasmPtr`] to the value at `index` if it is in bounds of the [`WasmSlice`].
+    pub fn get(self, index: u32) -> Option<WasmPtr<T>> {
+        if index >= self.len {
+            return None;
+        }
+        self.ptr.advance(index)
+    }
+
+    /// Returns the byte range of the [`WasmSlice`].
+    ///
+    /// # Errors
+    ///
+    /// If the byte range overflows.
+    fn range(self) -> Result<Range<usize>, MemoryError> {
+        let start = self.ptr.offset as usize;
+        (self.len as usize)
+            .checked_mul(T::SIZE)
+            .and_then(|size| start.checked_add(size))
+            .map(|end| start..end)
+            .ok_or(MemoryError::OutOfBoundsAccess)
+    }
+
+    /// Reads the value at `index` from the `view`.
+    ///
+    /// # Errors
+    ///
+    /// If `index` is out of bounds of the [`WasmSlice`] or the value is out of bounds of the `view`.
+    pub fn read(self, view: &MemoryView, index: u32) -> Result<T, MemoryError> {
+        self.get(index)
+            .ok_or(MemoryError::OutOfBoundsAccess)?
+            .read(view)
+    }
+
+    /// Writes `value` to the value at `index` in the `view`.
+    ///
+    /// # Errors
+    ///
+    /// If `index` is out of bounds of the [`WasmSlice`] or the value is out of bounds of the `view`.
+    pub fn write(self, view: &mut MemoryView, index: u32, value: T) -> Result<(), MemoryError> {
+        self.get(index)
+            .ok_or(MemoryError::OutOfBoundsAccess)?
+            .write(view, value)
+    }
+
+    /// Reads all values of the [`WasmSlice`] from the `view`.
+    ///
+    /// # Errors
+    ///
+    /// If the [`WasmSlice`] is out of bounds of the `view`.
+    pub fn read_to_vec(self, view: &MemoryView) -> Result<Vec<T>, MemoryError> {
+        // Note: We do not use `chunks_exact` since it panics for zero-sized values.
+        let bytes = view.bytes(self.range()?)?;
+        let values = (0..self.len as usize)
+            .map(|index| T::from_bytes(&bytes[index * T::SIZE..][..T::SIZE]))
+            .collect();
+        Ok(values)
+    }
+
+    /// Writes all `values` to the [`WasmSlice`] in the `view`.
+    ///
+    /// # Errors
+    ///
+    /// If the [`WasmSlice`] is out of bounds of the `view`.
+    ///
+    /// # Panics
+    ///
+    /// If the number of `values` does not match the length of the [`WasmSlice`].
+    pub fn write_slice(self, view: &mut MemoryView, values: &[T]) -> Result<(), MemoryError> {
+        assert_eq!(
+            values.len(),
+            self.len as usize,
+            "mismatched number of values for WasmSlice of length {}",
+            self.len
+        );
+        let bytes = view.bytes_mut(self.range()?)?;
+        for (index, value) in values.iter().enumerate() {
+            value.to_bytes(&mut bytes[index * T::SIZE..][..T::SIZE]);
+        }
+        Ok(())
+    }
+}
+
+impl WasmSlice<u8> {
+    /// Returns the bytes of the [`WasmSlice`] in the `view`.
+    ///
+    /// # Errors
+    ///
+    /// If the [`WasmSlice`] is out of bounds of the `view`.
+    pub fn bytes<'a>(self, view: &'a MemoryView) -> Result<&'a [u8], MemoryError> {
+        view.bytes(self.range()?)
+    }
+
+    /// Returns the bytes of the [`WasmSlice`] in the `view` mutably.
+    ///
+    /// # Errors
+    ///
+    /// If the [`WasmSlice`] is out of bounds of the `view`.
+    pub fn bytes_mut<'a>(self, view: &'a mut MemoryView) -> Result<&'a mut [u8], MemoryError> {
+        view.bytes_mut(self.range()?)
+    }
+
+    /// Returns the UTF-8 string of the [`WasmSlice`] in the `view`.
+    ///
+    /// # Errors
+    ///
+    /// - If the [`WasmSlice`] is out of bounds of the `view`.
+    /// - If the bytes of the [`WasmSlice`] are not valid UTF-8.
+    pub fn read_str<'a>(self, view: &'a MemoryView) -> Result<&'a str, MemoryError> {
+        core::str::from_utf8(self.bytes(view)?).map_err(|_| MemoryError::InvalidUtf8)
+    }
+}
@@ -0,0 +1,95 @@
+use super::MemoryError;
+use core::ops::Range;
+
+/// An exclusive view into the bytes of a linear memory.
+///
+/// Created via [`Memory::view`], [`Memory::view_and_store_mut`] or [`Caller::memory_view`]
+/// and used to access linear memory through [`WasmPtr`] and [`WasmSlice`] without
+/// resolving the [`Memory`] for every access.
+///
+/// [`Memory`]: super::Memory
+/// [`Caller::memory_view`]: crate::Caller::memory_view
+/// [`Memory::view`]: super::Memory::view
+/// [`Memory::view_and_store_mut`]: super::Memory::view_and_store_mut
+/// [`WasmPtr`]: super::WasmPtr
+/// [`WasmSlice`]: super::WasmSlice
+#[derive(Debug)]
+pub struct MemoryView<'a> {
+    /// The bytes of the viewed linear memory.
+    data: &'a mut [u8],
+}
+
+impl<'a> MemoryView<'a> {
+    /// Creates a new [`MemoryView`] into the bytes of a linear memory.
+    pub(crate) fn new(data: &'a mut [u8]) -> Self {
+        Self { data }
+    }
+
+    /// Returns the size of the viewed linear memory in bytes.
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Returns `true` if the viewed linear memory is empty.
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Returns a shared slice to the bytes of the viewed linear memory.
+    pub fn data(&self) -> &[u8] {
+        self.data
+    }
+
+    /// Returns an exclusive slice to the bytes of the viewed linear memory.
+    pub fn data_mut(&mut self) -> &mut [u8] {
+        self.data
+    }
+
+    /// Returns the bytes in `range` of the viewed linear memory.
+    ///
+    /// # Errors
+    ///
+    /// If `range` is out of bounds of the viewed linear memory.
+    pub fn bytes(&self, range: Range<usize>) -> Result<&[u8], MemoryError> {
+        self.data.get(range).ok_or(MemoryError::OutOfBoundsAccess)
+    }
+
+    /// Returns the bytes in `range` of the viewed linear memory mutably.
+    ///
+    /// # Errors
+    ///
+    /// If `range` is out of bounds of the viewed linear memory.
+    pub fn bytes_mut(&mut self, range: Range<usize>) -> Result<&mut [u8], MemoryError> {
+        self.data
+            .get_mut(range)
+            .ok_or(MemoryError::OutOfBoundsAccess)
+    }
+
+    /// Reads `n` bytes from `memory[offset..offset+n]` into `buffer`
+    /// where `n` is the length of `buffer`.
+    ///
+    /// # Errors
+    ///
+    /// If this operation accesses out of bounds linear memory.
+    pub fn read(&self, offset: usize, buffer: &mut [u8]) -> Result<(), MemoryError> {
+        let end = offset
+            .checked_add(buffer.len())
+            .ok_or(MemoryError::OutOfBoundsAccess)?;
+        buffer.copy_from_slice(self.bytes(offset..end)?);
+        Ok(())
+    }
+
+    /// Writes `n` bytes to `memory[offset..offset+n]` from `buffer`
+    /// where `n` is the length of `buffer`.
+    ///
+    /// # Errors
+    ///
+    /// If this operation accesses out of bounds linear memory.
+    pub fn write(&mut self, offset: usize, buffer: &[u8]) -> Result<(), MemoryError> {
+        let end = offset
+            .checked_add(buffer.len())
+            .ok_or(MemoryError::OutOfBoundsAccess)?;
+        self.bytes_mut(offset..end)?.copy_from_slice(buffer);
+        Ok(())
+    }
+}
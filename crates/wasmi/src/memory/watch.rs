@@ -0,0 +1,130 @@
+//! Watchpoints on the address ranges of linear memories.
+//!
+//! # Note
+//!
+//! This is only available with the `debugger` crate feature enabled.
+
+use super::Memory;
+use alloc::vec::Vec;
+use core::ops::Range;
+
+/// The kind of linear memory accesses observed by a [`Watchpoint`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum WatchKind {
+    /// Observes reads such as loads or the source of `memory.copy`.
+    Read,
+    /// Observes writes such as stores, `memory.fill`, `memory.init` or the destination of `memory.copy`.
+    Write,
+    /// Observes both reads and writes.
+    ReadWrite,
+}
+
+impl WatchKind {
+    /// Returns `true` if the [`WatchKind`] observes `access`.
+    ///
+    /// The `access` is either [`WatchKind::Read`] or [`WatchKind::Write`].
+    fn observes(self, access: WatchKind) -> bool {
+        self == WatchKind::ReadWrite || self == access
+    }
+}
+
+/// A watchpoint on an address range of a [`Memory`].
+///
+/// Created via [`Memory::add_watchpoint`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Watchpoint(u32);
+
+impl Watchpoint {
+    /// Creates a new [`Watchpoint`] from its unique `id` within its [`Store`].
+    ///
+    /// [`Store`]: crate::Store
+    pub(crate) fn new(id: u32) -> Self {
+        Self(id)
+    }
+}
+
+/// An access of a Wasm execution to an address range observed by a [`Watchpoint`].
+#[derive(Debug, Clone)]
+pub struct WatchpointHit {
+    /// The watchpoint that observed the access.
+    watchpoint: Watchpoint,
+    /// The accessed linear memory.
+    memory: Memory,
+    /// The kind of the access which is either [`WatchKind::Read`] or [`WatchKind::Write`].
+    access: WatchKind,
+    /// The accessed address range.
+    range: Range<usize>,
+}
+
+impl WatchpointHit {
+    /// Returns the [`Watchpoint`] that observed the access.
+    pub fn watchpoint(&self) -> Watchpoint {
+        self.watchpoint
+    }
+
+    /// Returns the accessed [`Memory`].
+    pub fn memory(&self) -> Memory {
+        self.memory
+    }
+
+    /// Returns [`WatchKind::Read`] for reads and [`WatchKind::Write`] for writes.
+    pub fn access(&self) -> WatchKind {
+        self.access
+    }
+
+    /// Returns the accessed address range.
+    ///
+    /// # Note
+    ///
+    /// This is the entire range of the access which might exceed the watched range.
+    pub fn range(&self) -> Range<usize> {
+        self.range.clone()
+    }
+}
+
+/// The watchpoints of a single linear memory.
+#[derive(Debug, Default)]
+pub struct MemoryWatchpoints {
+    /// The watchpoints with their watched address range and [`WatchKind`].
+    watchpoints: Vec<(Watchpoint, Range<usize>, WatchKind)>,
+}
+
+impl MemoryWatchpoints {
+    /// Adds the `watchpoint` observing `kind` accesses to the address `range`.
+    pub fn add(&mut self, watchpoint: Watchpoint, range: Range<usize>, kind: WatchKind) {
+        self.watchpoints.push((watchpoint, range, kind));
+    }
+
+    /// Removes the `watchpoint`.
+    ///
+    /// Returns `true` if the `watchpoint` existed.
+    pub fn remove(&mut self, watchpoint: Watchpoint) -> bool {
+        let len = self.watchpoints.len();
+        self.watchpoints
+            .retain(|(other, _, _)| *other != watchpoint);
+        self.watchpoints.len() != len
+    }
+
+    /// Returns the [`WatchpointHit`] of the first watchpoint observing the `access` to `range` if any.
+    pub fn hit(
+        &self,
+        memory: Memory,
+        access: WatchKind,
+        range: Range<usize>,
+    ) -> Option<WatchpointHit> {
+        if range.is_empty() {
+            return None;
+        }
+        self.watchpoints
+            .iter()
+            .find(|(_, watched, kind)| {
+                kind.observes(access) && range.start < watched.end && watched.start < range.end
+            })
+            .map(|(watchpoint, _, _)| WatchpointHit {
+                watchpoint: *watchpoint,
+                memory,
+                access,
+                range,
+            })
+    }
+}
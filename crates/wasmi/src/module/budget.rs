@@ -0,0 +1,36 @@
+/// The fuel budget for compiling or instantiating a single Wasm module.
+///
+/// # Note
+///
+/// An unbounded [`Budget`] never runs out of fuel.
+#[derive(Debug, Copy, Clone)]
+pub struct Budget {
+    /// The remaining fuel of the [`Budget`] or `None` if unbounded.
+    remaining: Option<u64>,
+}
+
+/// Signals that a [`Budget`] ran out of fuel.
+#[derive(Debug, Copy, Clone)]
+pub struct BudgetExhausted;
+
+impl Budget {
+    /// Creates a new [`Budget`] with the given amount of `fuel`.
+    ///
+    /// Creates an unbounded [`Budget`] if `fuel` is `None`.
+    pub fn new(fuel: Option<u64>) -> Self {
+        Self { remaining: fuel }
+    }
+
+    /// Consumes `delta` fuel from the [`Budget`].
+    ///
+    /// # Errors
+    ///
+    /// If the [`Budget`] has less than `delta` fuel remaining.
+    /// In this case no fuel is consumed.
+    pub fn consume(&mut self, delta: u64) -> Result<(), BudgetExhausted> {
+        if let Some(remaining) = &mut self.remaining {
+            *remaining = remaining.checked_sub(delta).ok_or(BudgetExhausted)?;
+        }
+        Ok(())
+    }
+}
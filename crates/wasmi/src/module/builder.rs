@@ -1,4 +1,7 @@
+#[cfg(feature = "dwarf")]
+use super::DwarfSections;
 use super::{
+    custom_section::CustomSection,
     export::ExternIdx,
     import::FuncTypeIdx,
     ConstExpr,
@@ -11,6 +14,7 @@ use super::{
     Import,
     ImportName,
     Module,
+    ModuleNames,
 };
 use crate::{
     engine::{CompiledFunc, DedupFuncType},
@@ -21,7 +25,7 @@ use crate::{
     MemoryType,
     TableType,
 };
-use alloc::{boxed::Box, collections::BTreeMap, vec::Vec};
+use alloc::{boxed::Box, collections::BTreeMap, sync::Arc, vec::Vec};
 
 /// A builder for a WebAssembly [`Module`].
 #[derive(Debug)]
@@ -39,6 +43,10 @@ pub struct ModuleBuilder<'engine> {
     pub compiled_funcs: Vec<CompiledFunc>,
     pub element_segments: Vec<ElementSegment>,
     pub data_segments: Vec<DataSegment>,
+    pub names: ModuleNames,
+    pub custom_sections: Vec<CustomSection>,
+    #[cfg(feature = "dwarf")]
+    pub dwarf: DwarfSections,
 }
 
 /// The import names of the [`Module`] imports.
@@ -139,6 +147,10 @@ impl<'engine> ModuleBuilder<'engine> {
             compiled_funcs: Vec::new(),
             element_segments: Vec::new(),
             data_segments: Vec::new(),
+            names: ModuleNames::default(),
+            custom_sections: Vec::new(),
+            #[cfg(feature = "dwarf")]
+            dwarf: DwarfSections::default(),
         }
     }
 
@@ -232,8 +244,9 @@ impl<'engine> ModuleBuilder<'engine> {
         for func in funcs {
             let func_type_idx = func?;
             let func_type = self.func_types[func_type_idx.into_u32() as usize];
+            let func_index = self.funcs.len() as u32;
             self.funcs.push(func_type);
-            self.compiled_funcs.push(self.engine.alloc_func());
+            self.compiled_funcs.push(self.engine.alloc_func(func_index));
         }
         Ok(())
     }
@@ -390,6 +403,20 @@ impl<'engine> ModuleBuilder<'engine> {
         Ok(())
     }
 
+    /// Sets the names of the [`Module`] under construction.
+    ///
+    /// # Note
+    ///
+    /// This replaces the names of a previously processed `name` custom section.
+    pub fn set_names(&mut self, names: ModuleNames) {
+        self.names = names;
+    }
+
+    /// Pushes the custom section with `name` and `data` to the [`Module`] under construction.
+    pub fn push_custom_section(&mut self, name: &str, data: Arc<[u8]>) {
+        self.custom_sections.push(CustomSection::new(name, data));
+    }
+
     /// Finishes construction of the WebAssembly [`Module`].
     pub fn finish(self) -> Module {
         Module::from_builder(self)
@@ -1,5 +1,5 @@
 pub use self::block_type::BlockType;
-use super::{parser::ReusableAllocations, FuncIdx, ModuleResources};
+use super::{parser::ReusableAllocations, Budget, FuncIdx, ModuleResources};
 use crate::{
     engine::{CompiledFunc, FuncBuilder, FuncTranslatorAllocations},
     errors::ModuleError,
@@ -17,10 +17,12 @@ mod block_type;
 ///   the incoming Wasm bytecode stream.
 /// - Uses the given module resources `res` as shared immutable data of the
 ///   already parsed and validated module parts required for the translation.
+/// - Consumes one unit of `fuel` per emitted `wasmi` bytecode instruction.
 ///
 /// # Errors
 ///
-/// If the function body fails to validate.
+/// - If the function body fails to validate.
+/// - If the translation runs out of `fuel`.
 pub fn translate<'parser>(
     func: FuncIdx,
     compiled_func: CompiledFunc,
@@ -28,8 +30,10 @@ pub fn translate<'parser>(
     validator: FuncValidator<ValidatorResources>,
     res: ModuleResources<'parser>,
     allocations: FuncTranslatorAllocations,
+    fuel: &mut Budget,
 ) -> Result<ReusableAllocations, ModuleError> {
-    FunctionTranslator::new(func, compiled_func, func_body, validator, res, allocations).translate()
+    FunctionTranslator::new(func, compiled_func, func_body, validator, res, allocations)
+        .translate(fuel)
 }
 
 /// Translates Wasm bytecode into `wasmi` bytecode for a single Wasm function.
@@ -58,9 +62,9 @@ impl<'parser> FunctionTranslator<'parser> {
     }
 
     /// Starts translation of the Wasm stream into `wasmi` bytecode.
-    fn translate(mut self) -> Result<ReusableAllocations, ModuleError> {
+    fn translate(mut self, fuel: &mut Budget) -> Result<ReusableAllocations, ModuleError> {
         self.translate_locals()?;
-        let offset = self.translate_operators()?;
+        let offset = self.translate_operators(fuel)?;
         let allocations = self.finish(offset)?;
         Ok(allocations)
     }
@@ -87,12 +91,22 @@ impl<'parser> FunctionTranslator<'parser> {
     /// Translates the Wasm operators of the Wasm function.
     ///
     /// Returns the offset of the `End` Wasm operator.
-    fn translate_operators(&mut self) -> Result<usize, ModuleError> {
+    ///
+    /// # Note
+    ///
+    /// The `fuel` is consumed after every Wasm operator for the `wasmi` bytecode
+    /// instructions it emitted so that the translation of huge function bodies
+    /// stops as soon as the `fuel` is exhausted.
+    fn translate_operators(&mut self, fuel: &mut Budget) -> Result<usize, ModuleError> {
         let mut reader = self.func_body.get_operators_reader()?;
+        let mut charged = self.func_builder.len_instrs();
         while !reader.eof() {
             let pos = reader.original_position();
             self.func_builder.update_pos(pos);
             reader.visit_operator(&mut self.func_builder)??;
+            let len_instrs = self.func_builder.len_instrs();
+            fuel.consume((len_instrs - charged) as u64)?;
+            charged = len_instrs;
         }
         reader.ensure_end()?;
         Ok(reader.original_position())
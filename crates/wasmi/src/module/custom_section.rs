@@ -0,0 +1,37 @@
+use alloc::{boxed::Box, sync::Arc};
+
+/// A custom section of a Wasm module retained during parsing.
+#[derive(Debug)]
+pub struct CustomSection {
+    /// The name of the custom section.
+    name: Box<str>,
+    /// The raw payload of the custom section following its name.
+    ///
+    /// # Note
+    ///
+    /// The payload is shared with the DWARF sections of the [`Module`]
+    /// if this is a `.debug_*` section and the `dwarf` crate feature is enabled.
+    ///
+    /// [`Module`]: crate::Module
+    data: Arc<[u8]>,
+}
+
+impl CustomSection {
+    /// Creates a new [`CustomSection`] with the given `name` and `data`.
+    pub fn new(name: &str, data: Arc<[u8]>) -> Self {
+        Self {
+            name: name.into(),
+            data,
+        }
+    }
+
+    /// Returns the name of the [`CustomSection`].
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the raw payload of the [`CustomSection`].
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+}
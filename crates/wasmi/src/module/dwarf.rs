@@ -0,0 +1,134 @@
+use crate::core::FrameSymbol;
+use addr2line::Context;
+use alloc::{boxed::Box, collections::BTreeMap, sync::Arc, vec::Vec};
+use core::fmt;
+use gimli::{Dwarf, EndianArcSlice, LittleEndian, SectionId};
+use spin::Mutex;
+
+/// The reader used to parse the DWARF sections of a [`Module`].
+///
+/// [`Module`]: crate::Module
+type Reader = EndianArcSlice<LittleEndian>;
+
+/// The `.debug_*` custom sections of a [`Module`] under construction.
+///
+/// [`Module`]: crate::Module
+#[derive(Debug, Default)]
+pub struct DwarfSections {
+    /// The payloads of the `.debug_*` custom sections by their names.
+    sections: BTreeMap<Box<str>, Arc<[u8]>>,
+    /// The offset of the code section within the Wasm binary.
+    code_offset: usize,
+}
+
+impl DwarfSections {
+    /// Returns `true` if the custom section with `name` is a `.debug_*` section.
+    pub fn is_debug_section(name: &str) -> bool {
+        name.starts_with(".debug_")
+    }
+
+    /// Pushes the `.debug_*` custom section with `name` and `data`.
+    pub fn push(&mut self, name: &str, data: Arc<[u8]>) {
+        debug_assert!(Self::is_debug_section(name));
+        self.sections.insert(name.into(), data);
+    }
+
+    /// Sets the offset of the code section within the Wasm binary.
+    ///
+    /// # Note
+    ///
+    /// DWARF addresses of Wasm modules are relative to the code section.
+    pub fn set_code_offset(&mut self, code_offset: usize) {
+        self.code_offset = code_offset;
+    }
+
+    /// Finishes the [`DwarfSections`] into the [`DebugInfo`] of the [`Module`].
+    ///
+    /// Returns `None` if there is no `.debug_info` section or if the DWARF is malformed.
+    ///
+    /// [`Module`]: crate::Module
+    pub fn finish(self) -> Option<DebugInfo> {
+        if !self.sections.contains_key(".debug_info") {
+            return None;
+        }
+        let dwarf = Dwarf::load(|id: SectionId| -> Result<Reader, gimli::Error> {
+            let data = self
+                .sections
+                .get(id.name())
+                .cloned()
+                .unwrap_or_else(|| Arc::new([]));
+            Ok(EndianArcSlice::new(data, LittleEndian))
+        })
+        .ok()?;
+        let context = Context::from_dwarf(dwarf).ok()?;
+        Some(DebugInfo {
+            context: Mutex::new(context),
+            code_offset: self.code_offset,
+        })
+    }
+}
+
+/// The DWARF debug information of a [`Module`].
+///
+/// Used to map Wasm offsets to their source files, lines and inlined functions.
+///
+/// [`Module`]: crate::Module
+pub struct DebugInfo {
+    /// The lazily populated lookup context.
+    ///
+    /// # Note
+    ///
+    /// The context caches parsed DWARF units internally and
+    /// thus must not be accessed concurrently.
+    context: Mutex<Context<Reader>>,
+    /// The offset of the code section within the Wasm binary.
+    code_offset: usize,
+}
+
+impl fmt::Debug for DebugInfo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("DebugInfo")
+            .field("code_offset", &self.code_offset)
+            .finish_non_exhaustive()
+    }
+}
+
+impl DebugInfo {
+    /// Returns the source level symbols of the Wasm operator at `wasm_offset`.
+    ///
+    /// # Note
+    ///
+    /// - The symbols are ordered from the innermost inlined function to the
+    ///   outermost function. Inlined functions are located at their call sites
+    ///   within their callers.
+    /// - Returns an empty `Vec` if `wasm_offset` is not covered by the DWARF.
+    pub fn symbols(&self, wasm_offset: usize) -> Vec<FrameSymbol> {
+        let mut symbols = Vec::new();
+        let probe = match wasm_offset.checked_sub(self.code_offset) {
+            Some(probe) => probe as u64,
+            None => return symbols,
+        };
+        let context = self.context.lock();
+        let mut frames = match context.find_frames(probe).skip_all_loads() {
+            Ok(frames) => frames,
+            Err(_) => return symbols,
+        };
+        while let Ok(Some(frame)) = frames.next() {
+            let name = frame
+                .function
+                .as_ref()
+                .and_then(|function| function.demangle().ok())
+                .map(Into::into);
+            let (file, line, column) = match frame.location {
+                Some(location) => (
+                    location.file.map(Into::into),
+                    location.line,
+                    location.column,
+                ),
+                None => (None, None, None),
+            };
+            symbols.push(FrameSymbol::new(name, file, line, column));
+        }
+        symbols
+    }
+}
@@ -133,6 +133,11 @@ impl ElementSegment {
         self.ty
     }
 
+    /// Returns the number of element items of the [`ElementSegment`].
+    pub fn len_items(&self) -> usize {
+        self.items.items().len()
+    }
+
     /// Returns the element items of the [`ElementSegment`].
     pub fn items_cloned(&self) -> ElementSegmentItems {
         self.items.clone()
@@ -1,4 +1,4 @@
-use super::ReadError;
+use super::{BudgetExhausted, ReadError};
 use crate::engine::TranslationError;
 use core::{
     fmt,
@@ -15,6 +15,12 @@ pub enum ModuleError {
     Parser(ParserError),
     /// Encountered when there is a Wasm to `wasmi` translation error.
     Translation(TranslationError),
+    /// Encountered when the compilation ran out of its configured fuel budget.
+    ///
+    /// The compilation fuel budget is set via [`Config::set_compilation_fuel`].
+    ///
+    /// [`Config::set_compilation_fuel`]: crate::Config::set_compilation_fuel
+    OutOfFuel,
 }
 
 impl Display for ModuleError {
@@ -23,6 +29,7 @@ impl Display for ModuleError {
             ModuleError::Read(error) => Display::fmt(error, f),
             ModuleError::Parser(error) => Display::fmt(error, f),
             ModuleError::Translation(error) => Display::fmt(error, f),
+            ModuleError::OutOfFuel => write!(f, "ran out of fuel while compiling the module"),
         }
     }
 }
@@ -44,3 +51,9 @@ impl From<TranslationError> for ModuleError {
         Self::Translation(error)
     }
 }
+
+impl From<BudgetExhausted> for ModuleError {
+    fn from(_error: BudgetExhausted) -> Self {
+        Self::OutOfFuel
+    }
+}
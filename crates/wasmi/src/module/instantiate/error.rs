@@ -1,6 +1,7 @@
 use crate::{
     errors::{MemoryError, TableError},
     global::GlobalError,
+    module::BudgetExhausted,
     Extern,
     ExternType,
     FuncType,
@@ -50,6 +51,12 @@ pub enum InstantiationError {
         index: u32,
     },
     TooManyInstances,
+    /// Caused when the instantiation ran out of its configured fuel budget.
+    ///
+    /// The instantiation fuel budget is set via [`Config::set_instantiation_fuel`].
+    ///
+    /// [`Config::set_instantiation_fuel`]: crate::Config::set_instantiation_fuel
+    OutOfFuel,
 }
 
 #[cfg(feature = "std")]
@@ -86,7 +93,8 @@ impl Display for InstantiationError {
             Self::Table(error) => Display::fmt(error, f),
             Self::Memory(error) => Display::fmt(error, f),
             Self::Global(error) => Display::fmt(error, f),
-            Self::TooManyInstances => write!(f, "too many instances")
+            Self::TooManyInstances => write!(f, "too many instances"),
+            Self::OutOfFuel => write!(f, "ran out of fuel while instantiating the module"),
         }
     }
 }
@@ -108,3 +116,9 @@ impl From<GlobalError> for InstantiationError {
         Self::Global(error)
     }
 }
+
+impl From<BudgetExhausted> for InstantiationError {
+    fn from(_error: BudgetExhausted) -> Self {
+        Self::OutOfFuel
+    }
+}
@@ -5,7 +5,7 @@ mod pre;
 mod tests;
 
 pub use self::{error::InstantiationError, pre::InstancePre};
-use super::{element::ElementSegmentKind, export, ConstExpr, DataSegmentKind, Module};
+use super::{element::ElementSegmentKind, export, Budget, ConstExpr, DataSegmentKind, Module};
 use crate::{
     func::WasmFuncEntity,
     memory::{DataSegment, MemoryError},
@@ -48,11 +48,71 @@ impl Module {
     /// [`Func`]: [`crate::Func`]
     pub(crate) fn instantiate<I>(
         &self,
-        mut context: impl AsContextMut,
+        context: impl AsContextMut,
         externals: I,
     ) -> Result<InstancePre, Error>
     where
         I: IntoIterator<Item = Extern>,
+    {
+        self.instantiate_with(context, |context, builder| {
+            self.extract_imports(context, builder, externals)
+                .map_err(Into::into)
+        })
+    }
+
+    /// Instantiates a new [`Instance`] from the [`Module`] with already type checked `externals`.
+    ///
+    /// The given `externals` are joined with the imports in the same order in which they occurred.
+    ///
+    /// # Note
+    ///
+    /// Unlike [`Module::instantiate`] this does not check the `externals` against the
+    /// imports of the [`Module`] since the caller already did so, e.g. a [`LinkedModule`].
+    ///
+    /// # Errors
+    ///
+    /// If the instantiation of the [`Module`] fails.
+    ///
+    /// # Panics
+    ///
+    /// If the number of `externals` does not match the number of imports of the [`Module`].
+    ///
+    /// [`LinkedModule`]: crate::LinkedModule
+    pub(crate) fn instantiate_resolved<I>(
+        &self,
+        context: impl AsContextMut,
+        externals: I,
+    ) -> Result<InstancePre, Error>
+    where
+        I: IntoIterator<Item = Extern>,
+    {
+        self.instantiate_with(context, |_, builder| {
+            let mut len_externals = 0;
+            for external in externals {
+                len_externals += 1;
+                match external {
+                    Extern::Func(func) => builder.push_func(func),
+                    Extern::Table(table) => builder.push_table(table),
+                    Extern::Memory(memory) => builder.push_memory(memory),
+                    Extern::Global(global) => builder.push_global(global),
+                }
+            }
+            assert_eq!(
+                len_externals,
+                self.imports().len(),
+                "mismatch between the number of externals and imports"
+            );
+            Ok(())
+        })
+    }
+
+    /// Instantiates a new [`Instance`] from the [`Module`] using `imports` to provide its imports.
+    ///
+    /// The `imports` closure pushes the imported entities into the [`Instance`] under construction.
+    fn instantiate_with<C, F>(&self, mut context: C, imports: F) -> Result<InstancePre, Error>
+    where
+        C: AsContextMut,
+        F: FnOnce(&mut C, &mut InstanceEntityBuilder) -> Result<(), Error>,
     {
         context
             .as_context_mut()
@@ -61,16 +121,24 @@ impl Module {
         let handle = context.as_context_mut().store.inner.alloc_instance();
         let mut builder = InstanceEntity::build(self);
 
-        self.extract_imports(&mut context, &mut builder, externals)?;
+        imports(&mut context, &mut builder)?;
         self.extract_functions(&mut context, &mut builder, handle);
+        let len_counters = self.inner.engine.len_counters();
+        context
+            .as_context_mut()
+            .store
+            .inner
+            .coverage_mut()
+            .grow(len_counters);
         self.extract_tables(&mut context, &mut builder)?;
         self.extract_memories(&mut context, &mut builder)?;
         self.extract_globals(&mut context, &mut builder);
         self.extract_exports(&mut builder);
         self.extract_start_fn(&mut builder);
 
-        self.initialize_table_elements(&mut context, &mut builder)?;
-        self.initialize_memory_data(&mut context, &mut builder)?;
+        let mut fuel = Budget::new(self.inner.engine.config().get_instantiation_fuel());
+        self.initialize_table_elements(&mut context, &mut builder, &mut fuel)?;
+        self.initialize_memory_data(&mut context, &mut builder, &mut fuel)?;
 
         // At this point the module instantiation is nearly done.
         // The only thing that is missing is to run the `start` function.
@@ -118,6 +186,7 @@ impl Module {
                 (ExternType::Func(expected_signature), Extern::Func(func)) => {
                     let actual_signature = func.ty_dedup(context.as_context());
                     let actual_signature = self
+                        .inner
                         .engine
                         .resolve_func_type(actual_signature, FuncType::clone);
                     // Note: We can compare function signatures without resolving them because
@@ -170,8 +239,9 @@ impl Module {
         builder: &mut InstanceEntityBuilder,
         handle: Instance,
     ) {
-        for (func_type, func_body) in self.internal_funcs() {
-            let wasm_func = WasmFuncEntity::new(func_type, func_body, handle);
+        let len_imported = self.inner.imports.len_funcs as u32;
+        for ((func_type, func_body), index) in self.internal_funcs().zip(len_imported..) {
+            let wasm_func = WasmFuncEntity::new(func_type, func_body, handle, index);
             let func = context
                 .as_context_mut()
                 .store
@@ -263,7 +333,7 @@ impl Module {
 
     /// Extracts the Wasm exports from the module and registers them into the [`Instance`].
     fn extract_exports(&self, builder: &mut InstanceEntityBuilder) {
-        for (field, idx) in &self.exports {
+        for (field, idx) in &self.inner.exports {
             let external = match idx {
                 export::ExternIdx::Func(func_index) => {
                     let func_index = func_index.into_u32();
@@ -292,18 +362,23 @@ impl Module {
 
     /// Extracts the optional start function for the build instance.
     fn extract_start_fn(&self, builder: &mut InstanceEntityBuilder) {
-        if let Some(start_fn) = self.start {
+        if let Some(start_fn) = self.inner.start {
             builder.set_start(start_fn)
         }
     }
 
     /// Initializes the [`Instance`] tables with the Wasm element segments of the [`Module`].
+    ///
+    /// Consumes one unit of `fuel` per element segment item.
     fn initialize_table_elements(
         &self,
         mut context: &mut impl AsContextMut,
         builder: &mut InstanceEntityBuilder,
+        fuel: &mut Budget,
     ) -> Result<(), Error> {
-        for segment in &self.element_segments[..] {
+        for segment in &self.inner.element_segments[..] {
+            fuel.consume(segment.len_items() as u64)
+                .map_err(InstantiationError::from)?;
             let element = ElementSegment::new(context.as_context_mut(), segment);
             if let ElementSegmentKind::Active(active) = segment.kind() {
                 let dst_index = u32::from(Self::eval_init_expr(
@@ -347,13 +422,18 @@ impl Module {
     }
 
     /// Initializes the [`Instance`] linear memories with the Wasm data segments of the [`Module`].
+    ///
+    /// Consumes one unit of `fuel` per data segment byte.
     fn initialize_memory_data(
         &self,
         context: &mut impl AsContextMut,
         builder: &mut InstanceEntityBuilder,
+        fuel: &mut Budget,
     ) -> Result<(), Error> {
-        for segment in &self.data_segments[..] {
+        for segment in &self.inner.data_segments[..] {
             let bytes = segment.bytes();
+            fuel.consume(bytes.len() as u64)
+                .map_err(InstantiationError::from)?;
             if let DataSegmentKind::Active(segment) = segment.kind() {
                 let offset_expr = segment.offset();
                 let offset =
@@ -1,6 +1,10 @@
+mod budget;
 mod builder;
 mod compile;
+mod custom_section;
 mod data;
+#[cfg(feature = "dwarf")]
+mod dwarf;
 mod element;
 mod error;
 mod export;
@@ -8,12 +12,17 @@ mod global;
 mod import;
 mod init_expr;
 mod instantiate;
+mod names;
 mod parser;
 mod read;
 mod utils;
 
+#[cfg(feature = "dwarf")]
+pub(crate) use self::dwarf::{DebugInfo, DwarfSections};
 use self::{
+    budget::{Budget, BudgetExhausted},
     builder::ModuleBuilder,
+    custom_section::CustomSection,
     export::ExternIdx,
     global::Global,
     import::{ExternTypeIdx, Import},
@@ -28,6 +37,7 @@ pub use self::{
     global::GlobalIdx,
     import::{FuncTypeIdx, ImportName},
     instantiate::{InstancePre, InstantiationError},
+    names::ModuleNames,
     parser::ReusableAllocations,
     read::Read,
 };
@@ -36,6 +46,10 @@ pub(crate) use self::{
     element::{ElementSegment, ElementSegmentItems, ElementSegmentKind},
     init_expr::ConstExpr,
 };
+#[cfg(feature = "dwarf")]
+use crate::core::FrameSymbol;
+#[cfg(feature = "debugger")]
+use crate::engine::DebuggerError;
 use crate::{
     engine::{CompiledFunc, DedupFuncType},
     Engine,
@@ -46,12 +60,24 @@ use crate::{
     MemoryType,
     TableType,
 };
-use alloc::{boxed::Box, collections::BTreeMap, sync::Arc};
+#[cfg(feature = "dwarf")]
+use alloc::vec::Vec;
+use alloc::{boxed::Box, collections::BTreeMap, string::String, sync::Arc};
 use core::{iter, slice::Iter as SliceIter};
 
 /// A parsed and validated WebAssembly module.
-#[derive(Debug)]
+///
+/// # Note
+///
+/// Cloning a [`Module`] is cheap since it only clones a reference to its contents.
+#[derive(Debug, Clone)]
 pub struct Module {
+    inner: Arc<ModuleInner>,
+}
+
+/// The contents of a [`Module`].
+#[derive(Debug)]
+struct ModuleInner {
     engine: Engine,
     func_types: Arc<[DedupFuncType]>,
     imports: ModuleImports,
@@ -65,6 +91,10 @@ pub struct Module {
     compiled_funcs: Box<[CompiledFunc]>,
     element_segments: Box<[ElementSegment]>,
     data_segments: Box<[DataSegment]>,
+    names: Arc<ModuleNames>,
+    custom_sections: Box<[CustomSection]>,
+    #[cfg(feature = "dwarf")]
+    debug_info: Option<Arc<DebugInfo>>,
 }
 
 /// The index of the default Wasm linear memory.
@@ -149,12 +179,12 @@ impl Module {
 
     /// Returns the [`Engine`] used during creation of the [`Module`].
     pub fn engine(&self) -> &Engine {
-        &self.engine
+        &self.inner.engine
     }
 
     /// Creates a new [`Module`] from the [`ModuleBuilder`].
     fn from_builder(builder: ModuleBuilder) -> Self {
-        Self {
+        let inner = ModuleInner {
             engine: builder.engine().clone(),
             func_types: builder.func_types.into(),
             imports: ModuleImports::from_builder(builder.imports),
@@ -168,24 +198,35 @@ impl Module {
             compiled_funcs: builder.compiled_funcs.into(),
             element_segments: builder.element_segments.into(),
             data_segments: builder.data_segments.into(),
+            names: builder.names.into(),
+            custom_sections: builder.custom_sections.into(),
+            #[cfg(feature = "dwarf")]
+            debug_info: builder.dwarf.finish().map(Arc::new),
+        };
+        Self {
+            inner: Arc::new(inner),
         }
     }
 
     /// Returns the number of non-imported functions of the [`Module`].
     pub(crate) fn len_funcs(&self) -> usize {
-        self.funcs.len()
+        self.inner.funcs.len()
+    }
+    /// Returns the number of imported functions of the [`Module`].
+    pub(crate) fn len_imported_funcs(&self) -> usize {
+        self.inner.imports.len_funcs
     }
     /// Returns the number of non-imported tables of the [`Module`].
     pub(crate) fn len_tables(&self) -> usize {
-        self.tables.len()
+        self.inner.tables.len()
     }
     /// Returns the number of non-imported linear memories of the [`Module`].
     pub(crate) fn len_memories(&self) -> usize {
-        self.memories.len()
+        self.inner.memories.len()
     }
     /// Returns the number of non-imported global variables of the [`Module`].
     pub(crate) fn len_globals(&self) -> usize {
-        self.globals.len()
+        self.inner.globals.len()
     }
 
     /// Returns a slice to the function types of the [`Module`].
@@ -194,20 +235,122 @@ impl Module {
     ///
     /// The slice is stored in a `Arc` so that this operation is very cheap.
     pub(crate) fn func_types_cloned(&self) -> Arc<[DedupFuncType]> {
-        self.func_types.clone()
+        self.inner.func_types.clone()
+    }
+
+    /// Returns the names of the [`Module`] and its entities.
+    ///
+    /// # Note
+    ///
+    /// The names are stored in a `Arc` so that this operation is very cheap.
+    pub(crate) fn names_cloned(&self) -> Arc<ModuleNames> {
+        self.inner.names.clone()
+    }
+
+    /// Returns the DWARF debug information of the [`Module`] if any.
+    ///
+    /// # Note
+    ///
+    /// The debug information is stored in a `Arc` so that this operation is very cheap.
+    #[cfg(feature = "dwarf")]
+    pub(crate) fn debug_info_cloned(&self) -> Option<Arc<DebugInfo>> {
+        self.inner.debug_info.clone()
+    }
+
+    /// Returns the source level symbols of the Wasm operator at `wasm_offset`.
+    ///
+    /// The symbols are derived from the DWARF debug information of the [`Module`]
+    /// and ordered from the innermost inlined function to the outermost function.
+    ///
+    /// # Note
+    ///
+    /// - The `wasm_offset` is the offset of the operator within the Wasm binary.
+    /// - Returns an empty `Vec` if the [`Module`] has no DWARF debug information
+    ///   or if it does not cover `wasm_offset`.
+    #[cfg(feature = "dwarf")]
+    pub fn symbols(&self, wasm_offset: usize) -> Vec<FrameSymbol> {
+        self.inner
+            .debug_info
+            .as_ref()
+            .map(|debug_info| debug_info.symbols(wasm_offset))
+            .unwrap_or_default()
+    }
+
+    /// Returns the names of the [`Module`] and its entities as defined by its `name` custom section.
+    pub fn names(&self) -> &ModuleNames {
+        &self.inner.names
+    }
+
+    /// Returns the name of the [`Module`] as defined by its `name` custom section if any.
+    pub fn name(&self) -> Option<&str> {
+        self.inner.names.module()
+    }
+
+    /// Returns the name of the function at `index` as defined by the `name` custom section if any.
+    ///
+    /// # Note
+    ///
+    /// The function index space includes imported functions.
+    pub fn func_name(&self, index: u32) -> Option<&str> {
+        self.inner.names.func(index)
+    }
+
+    /// Returns the name of the local at `local_index` of the function at `func_index`
+    /// as defined by the `name` custom section if any.
+    ///
+    /// # Note
+    ///
+    /// The local index space of a function starts with its parameters.
+    pub fn local_name(&self, func_index: u32, local_index: u32) -> Option<&str> {
+        self.inner.names.local(func_index, local_index)
+    }
+
+    /// Returns an iterator over the payloads of all custom sections of the [`Module`] named `name`.
+    ///
+    /// The payloads are yielded in the order in which they appear in the Wasm binary.
+    ///
+    /// # Note
+    ///
+    /// This yields nothing if [`Config::retain_custom_sections`] has been disabled.
+    ///
+    /// [`Config::retain_custom_sections`]: crate::Config::retain_custom_sections
+    pub fn custom_sections<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a [u8]> + 'a {
+        self.inner
+            .custom_sections
+            .iter()
+            .filter(move |section| section.name() == name)
+            .map(CustomSection::data)
+    }
+
+    /// Returns the disassembly of the `wasmi` bytecode of all internal functions of the [`Module`].
+    ///
+    /// Each function is listed with its index, name and type followed by one line per
+    /// `wasmi` bytecode instruction. Each line shows the index of the instruction within
+    /// its function, the offset of the Wasm operator it originates from if it is the
+    /// first instruction of that operator, and the instruction with its operands.
+    ///
+    /// # Note
+    ///
+    /// - Branch offsets are resolved to the index of the targeted instruction, constant
+    ///   pool references to their values and calls to their function indices and names.
+    /// - Instruction words that only store parameters of their preceding instruction,
+    ///   such as the values to drop and keep of a branch, are shown in parentheses.
+    /// - The format of the disassembly is intended for humans and not stable.
+    pub fn disassemble(&self) -> String {
+        self.inner.engine.disassemble(self)
     }
 
     /// Returns an iterator over the imports of the [`Module`].
     pub fn imports(&self) -> ModuleImportsIter {
-        let len_imported_funcs = self.imports.len_funcs;
-        let len_imported_globals = self.imports.len_globals;
+        let len_imported_funcs = self.inner.imports.len_funcs;
+        let len_imported_globals = self.inner.imports.len_globals;
         ModuleImportsIter {
-            engine: &self.engine,
-            names: self.imports.items.iter(),
-            funcs: self.funcs[..len_imported_funcs].iter(),
-            tables: self.tables.iter(),
-            memories: self.memories.iter(),
-            globals: self.globals[..len_imported_globals].iter(),
+            engine: &self.inner.engine,
+            names: self.inner.imports.items.iter(),
+            funcs: self.inner.funcs[..len_imported_funcs].iter(),
+            tables: self.inner.tables.iter(),
+            memories: self.inner.memories.iter(),
+            globals: self.inner.globals[..len_imported_globals].iter(),
         }
     }
 
@@ -215,46 +358,94 @@ impl Module {
     ///
     /// [`Func`]: [`crate::Func`]
     pub(crate) fn internal_funcs(&self) -> InternalFuncsIter {
-        let len_imported = self.imports.len_funcs;
+        let len_imported = self.inner.imports.len_funcs;
         // We skip the first `len_imported` elements in `funcs`
         // since they refer to imported and not internally defined
         // functions.
-        let funcs = &self.funcs[len_imported..];
-        let compiled_funcs = &self.compiled_funcs[..];
+        let funcs = &self.inner.funcs[len_imported..];
+        let compiled_funcs = &self.inner.compiled_funcs[..];
         assert_eq!(funcs.len(), compiled_funcs.len());
         InternalFuncsIter {
             iter: funcs.iter().zip(compiled_funcs),
         }
     }
 
+    /// Returns the index of the internal function whose body contains the Wasm bytecode `offset`.
+    ///
+    /// Returns `None` if `offset` precedes the bodies of all internal functions.
+    ///
+    /// # Note
+    ///
+    /// - The returned index is in the function index space of the [`Module`]
+    ///   and thus can be used to set breakpoints via [`Store::set_breakpoint`].
+    /// - Offsets past the body of the last function resolve to the last function.
+    /// - This is only available with the `debugger` crate feature enabled.
+    ///
+    /// [`Store::set_breakpoint`]: crate::Store::set_breakpoint
+    #[cfg(feature = "debugger")]
+    pub fn func_index_at_offset(&self, offset: usize) -> Option<u32> {
+        let index = self
+            .inner
+            .engine
+            .resolve_func_at_offset(&self.inner.compiled_funcs, offset)?;
+        Some((self.inner.imports.len_funcs + index) as u32)
+    }
+
+    /// Resolves the breakpoint at the Wasm `offset` of the function at `func_index`.
+    ///
+    /// Returns the [`CompiledFunc`] and the index of its instruction at `offset`.
+    ///
+    /// # Errors
+    ///
+    /// - If the [`Module`] does not define a function at `func_index`.
+    /// - If the function has no Wasm operator at `offset`.
+    #[cfg(feature = "debugger")]
+    pub(crate) fn resolve_breakpoint(
+        &self,
+        func_index: u32,
+        offset: usize,
+    ) -> Result<(CompiledFunc, usize), DebuggerError> {
+        let func = (func_index as usize)
+            .checked_sub(self.inner.imports.len_funcs)
+            .and_then(|index| self.inner.compiled_funcs.get(index))
+            .copied()
+            .ok_or(DebuggerError::UnknownFunction { func_index })?;
+        let index = self
+            .inner
+            .engine
+            .resolve_wasm_offset(func, offset)
+            .ok_or(DebuggerError::InvalidOffset { func_index, offset })?;
+        Ok((func, index))
+    }
+
     /// Returns an iterator over the [`MemoryType`] of internal linear memories.
     fn internal_memories(&self) -> SliceIter<MemoryType> {
-        let len_imported = self.imports.len_memories;
+        let len_imported = self.inner.imports.len_memories;
         // We skip the first `len_imported` elements in `memories`
         // since they refer to imported and not internally defined
         // linear memories.
-        let memories = &self.memories[len_imported..];
+        let memories = &self.inner.memories[len_imported..];
         memories.iter()
     }
 
     /// Returns an iterator over the [`TableType`] of internal tables.
     fn internal_tables(&self) -> SliceIter<TableType> {
-        let len_imported = self.imports.len_tables;
+        let len_imported = self.inner.imports.len_tables;
         // We skip the first `len_imported` elements in `memories`
         // since they refer to imported and not internally defined
         // linear memories.
-        let tables = &self.tables[len_imported..];
+        let tables = &self.inner.tables[len_imported..];
         tables.iter()
     }
 
     /// Returns an iterator over the internally defined [`Global`].
     fn internal_globals(&self) -> InternalGlobalsIter {
-        let len_imported = self.imports.len_globals;
+        let len_imported = self.inner.imports.len_globals;
         // We skip the first `len_imported` elements in `globals`
         // since they refer to imported and not internally defined
         // global variables.
-        let globals = self.globals[len_imported..].iter();
-        let global_inits = self.globals_init.iter();
+        let globals = self.inner.globals[len_imported..].iter();
+        let global_inits = self.inner.globals_init.iter();
         InternalGlobalsIter {
             iter: globals.zip(global_inits),
         }
@@ -273,7 +464,7 @@ impl Module {
     ///
     /// This function will return the type of an export with the given `name`.
     pub fn get_export(&self, name: &str) -> Option<ExternType> {
-        let idx = self.exports.get(name).copied()?;
+        let idx = self.inner.exports.get(name).copied()?;
         let ty = self.get_extern_type(idx);
         Some(ty)
     }
@@ -286,20 +477,20 @@ impl Module {
     fn get_extern_type(&self, idx: ExternIdx) -> ExternType {
         match idx {
             ExternIdx::Func(index) => {
-                let dedup = &self.funcs[index.into_u32() as usize];
-                let func_type = self.engine.resolve_func_type(dedup, Clone::clone);
+                let dedup = &self.inner.funcs[index.into_u32() as usize];
+                let func_type = self.inner.engine.resolve_func_type(dedup, Clone::clone);
                 ExternType::Func(func_type)
             }
             ExternIdx::Table(index) => {
-                let table_type = self.tables[index.into_u32() as usize];
+                let table_type = self.inner.tables[index.into_u32() as usize];
                 ExternType::Table(table_type)
             }
             ExternIdx::Memory(index) => {
-                let memory_type = self.memories[index.into_u32() as usize];
+                let memory_type = self.inner.memories[index.into_u32() as usize];
                 ExternType::Memory(memory_type)
             }
             ExternIdx::Global(index) => {
-                let global_type = self.globals[index.into_u32() as usize];
+                let global_type = self.inner.globals[index.into_u32() as usize];
                 ExternType::Global(global_type)
             }
         }
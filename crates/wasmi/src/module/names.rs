@@ -0,0 +1,152 @@
+use alloc::{boxed::Box, collections::BTreeMap};
+use wasmparser::{BinaryReaderError, IndirectNameMap, Name, NameMap, NameSectionReader};
+
+/// Maps the indices of entities to their names.
+type Names = BTreeMap<u32, Box<str>>;
+
+/// Maps the indices of functions to the names of their locals or labels.
+type IndirectNames = BTreeMap<u32, Names>;
+
+/// The names of a [`Module`] and its entities as defined by its `name` custom section.
+///
+/// # Note
+///
+/// All indices refer to the respective index spaces of the [`Module`]
+/// and thus include imported entities.
+///
+/// [`Module`]: crate::Module
+#[derive(Debug, Default)]
+pub struct ModuleNames {
+    module: Option<Box<str>>,
+    funcs: Names,
+    locals: IndirectNames,
+    labels: IndirectNames,
+    types: Names,
+    tables: Names,
+    memories: Names,
+    globals: Names,
+    elements: Names,
+    data: Names,
+}
+
+impl ModuleNames {
+    /// Parses the [`ModuleNames`] from the `name` custom `section`.
+    ///
+    /// # Note
+    ///
+    /// Unknown subsections are ignored.
+    ///
+    /// # Errors
+    ///
+    /// If the `name` custom `section` is malformed.
+    pub(crate) fn from_section(section: NameSectionReader) -> Result<Self, BinaryReaderError> {
+        let mut names = Self::default();
+        for subsection in section {
+            match subsection? {
+                Name::Module { name, .. } => names.module = Some(name.into()),
+                Name::Function(map) => names.funcs = parse_names(map)?,
+                Name::Local(map) => names.locals = parse_indirect_names(map)?,
+                Name::Label(map) => names.labels = parse_indirect_names(map)?,
+                Name::Type(map) => names.types = parse_names(map)?,
+                Name::Table(map) => names.tables = parse_names(map)?,
+                Name::Memory(map) => names.memories = parse_names(map)?,
+                Name::Global(map) => names.globals = parse_names(map)?,
+                Name::Element(map) => names.elements = parse_names(map)?,
+                Name::Data(map) => names.data = parse_names(map)?,
+                Name::Unknown { .. } => {}
+            }
+        }
+        Ok(names)
+    }
+
+    /// Returns the name of the [`Module`] if any.
+    ///
+    /// [`Module`]: crate::Module
+    pub fn module(&self) -> Option<&str> {
+        self.module.as_deref()
+    }
+
+    /// Returns the name of the function at `index` if any.
+    pub fn func(&self, index: u32) -> Option<&str> {
+        self.funcs.get(&index).map(AsRef::as_ref)
+    }
+
+    /// Returns the name of the local at `local_index` of the function at `func_index` if any.
+    ///
+    /// # Note
+    ///
+    /// The local index space of a function starts with its parameters.
+    pub fn local(&self, func_index: u32, local_index: u32) -> Option<&str> {
+        indirect_name(&self.locals, func_index, local_index)
+    }
+
+    /// Returns the name of the label at `label_index` of the function at `func_index` if any.
+    ///
+    /// # Note
+    ///
+    /// Labels are indexed in the order of their `block`, `loop` and `if`
+    /// operators within the body of the function.
+    pub fn label(&self, func_index: u32, label_index: u32) -> Option<&str> {
+        indirect_name(&self.labels, func_index, label_index)
+    }
+
+    /// Returns the name of the function type at `index` if any.
+    pub fn func_type(&self, index: u32) -> Option<&str> {
+        self.types.get(&index).map(AsRef::as_ref)
+    }
+
+    /// Returns the name of the table at `index` if any.
+    pub fn table(&self, index: u32) -> Option<&str> {
+        self.tables.get(&index).map(AsRef::as_ref)
+    }
+
+    /// Returns the name of the linear memory at `index` if any.
+    pub fn memory(&self, index: u32) -> Option<&str> {
+        self.memories.get(&index).map(AsRef::as_ref)
+    }
+
+    /// Returns the name of the global variable at `index` if any.
+    pub fn global(&self, index: u32) -> Option<&str> {
+        self.globals.get(&index).map(AsRef::as_ref)
+    }
+
+    /// Returns the name of the element segment at `index` if any.
+    pub fn element(&self, index: u32) -> Option<&str> {
+        self.elements.get(&index).map(AsRef::as_ref)
+    }
+
+    /// Returns the name of the data segment at `index` if any.
+    pub fn data(&self, index: u32) -> Option<&str> {
+        self.data.get(&index).map(AsRef::as_ref)
+    }
+}
+
+/// Parses the name `map` into [`Names`].
+///
+/// # Errors
+///
+/// If the name `map` is malformed.
+fn parse_names(map: NameMap) -> Result<Names, BinaryReaderError> {
+    map.into_iter()
+        .map(|naming| naming.map(|naming| (naming.index, naming.name.into())))
+        .collect()
+}
+
+/// Parses the indirect name `map` into [`IndirectNames`].
+///
+/// # Errors
+///
+/// If the indirect name `map` is malformed.
+fn parse_indirect_names(map: IndirectNameMap) -> Result<IndirectNames, BinaryReaderError> {
+    map.into_iter()
+        .map(|naming| {
+            let naming = naming?;
+            Ok((naming.index, parse_names(naming.names)?))
+        })
+        .collect()
+}
+
+/// Returns the name at `inner` of the indirect `names` at `outer` if any.
+fn indirect_name(names: &IndirectNames, outer: u32, inner: u32) -> Option<&str> {
+    names.get(&outer)?.get(&inner).map(AsRef::as_ref)
+}
@@ -1,14 +1,18 @@
+#[cfg(feature = "dwarf")]
+use super::DwarfSections;
 use super::{
     compile::translate,
     export::ExternIdx,
     global::Global,
     import::{FuncTypeIdx, Import},
+    Budget,
     DataSegment,
     ElementSegment,
     FuncIdx,
     Module,
     ModuleBuilder,
     ModuleError,
+    ModuleNames,
     ModuleResources,
     Read,
 };
@@ -19,13 +23,14 @@ use crate::{
     MemoryType,
     TableType,
 };
-use alloc::{boxed::Box, vec::Vec};
+use alloc::{boxed::Box, sync::Arc, vec::Vec};
 use core::{
     mem::{replace, take},
     ops::Range,
 };
 use wasmparser::{
     Chunk,
+    CustomSectionReader,
     DataSectionReader,
     ElementSectionReader,
     Encoding,
@@ -36,6 +41,7 @@ use wasmparser::{
     GlobalSectionReader,
     ImportSectionReader,
     MemorySectionReader,
+    NameSectionReader,
     Parser as WasmParser,
     Payload,
     TableSectionReader,
@@ -66,6 +72,8 @@ pub struct ModuleParser<'engine> {
     parser: WasmParser,
     /// The number of compiled or processed functions.
     compiled_funcs: u32,
+    /// The remaining fuel budget for the compilation.
+    fuel: Budget,
     /// Reusable allocations for validating and translation functions.
     allocations: ReusableAllocations,
 }
@@ -83,11 +91,13 @@ impl<'engine> ModuleParser<'engine> {
         let builder = ModuleBuilder::new(engine);
         let validator = Validator::new_with_features(Self::features(engine));
         let parser = WasmParser::new(0);
+        let fuel = Budget::new(engine.config().get_compilation_fuel());
         Self {
             builder,
             validator,
             parser,
             compiled_funcs: 0,
+            fuel,
             allocations: ReusableAllocations::default(),
         }
     }
@@ -178,7 +188,7 @@ impl<'engine> ModuleParser<'engine> {
             Payload::ElementSection(section) => self.process_element(section),
             Payload::DataCountSection { count, range } => self.process_data_count(count, range),
             Payload::DataSection(section) => self.process_data(section),
-            Payload::CustomSection { .. } => Ok(()),
+            Payload::CustomSection(reader) => self.process_custom_section(reader),
             Payload::CodeSectionStart { count, range, .. } => self.process_code_start(count, range),
             Payload::CodeSectionEntry(func_body) => self.process_code_entry(func_body),
             Payload::UnknownSection { id, range, .. } => self.process_unknown(id, range),
@@ -460,6 +470,48 @@ impl<'engine> ModuleParser<'engine> {
         Ok(())
     }
 
+    /// Process a custom section.
+    ///
+    /// # Note
+    ///
+    /// - The custom section is retained unless disabled via the [`Config`].
+    /// - A malformed `name` custom section is ignored since custom
+    ///   sections must not affect the validity of a Wasm module.
+    /// - The `.debug_*` custom sections are always retained
+    ///   if the `dwarf` crate feature is enabled.
+    /// - A `.debug_*` custom section that is retained for both purposes
+    ///   shares a single copy of its payload.
+    ///
+    /// # Errors
+    ///
+    /// This currently never returns an error.
+    ///
+    /// [`Config`]: crate::Config
+    fn process_custom_section(&mut self, reader: CustomSectionReader) -> Result<(), ModuleError> {
+        let retain = self.builder.engine().config().get_retain_custom_sections();
+        #[cfg(feature = "dwarf")]
+        let is_debug = DwarfSections::is_debug_section(reader.name());
+        #[cfg(not(feature = "dwarf"))]
+        let is_debug = false;
+        if retain || is_debug {
+            let data = Arc::<[u8]>::from(reader.data());
+            #[cfg(feature = "dwarf")]
+            if is_debug {
+                self.builder.dwarf.push(reader.name(), data.clone());
+            }
+            if retain {
+                self.builder.push_custom_section(reader.name(), data);
+            }
+        }
+        if reader.name() == "name" {
+            let section = NameSectionReader::new(reader.data(), reader.data_offset());
+            if let Ok(names) = ModuleNames::from_section(section) {
+                self.builder.set_names(names);
+            }
+        }
+        Ok(())
+    }
+
     /// Process module code section start.
     ///
     /// # Note
@@ -473,6 +525,8 @@ impl<'engine> ModuleParser<'engine> {
     /// If the code start section fails to validate.
     fn process_code_start(&mut self, count: u32, range: Range<usize>) -> Result<(), ModuleError> {
         self.validator.code_section_start(count, &range)?;
+        #[cfg(feature = "dwarf")]
+        self.builder.dwarf.set_code_offset(range.start);
         Ok(())
     }
 
@@ -499,9 +553,11 @@ impl<'engine> ModuleParser<'engine> {
     ///
     /// # Errors
     ///
-    /// If the function body fails to validate.
+    /// - If the function body fails to validate.
+    /// - If the compilation runs out of fuel.
     fn process_code_entry(&mut self, func_body: FunctionBody) -> Result<(), ModuleError> {
         let (func, compiled_func) = self.next_func();
+        self.fuel.consume(func_body.range().len() as u64)?;
         let validator = self.validator.code_section_entry(&func_body)?;
         let module_resources = ModuleResources::new(&self.builder);
         let allocations = take(&mut self.allocations);
@@ -512,6 +568,7 @@ impl<'engine> ModuleParser<'engine> {
             validator.into_validator(allocations.validation),
             module_resources,
             allocations.translation,
+            &mut self.fuel,
         )?;
         let _ = replace(&mut self.allocations, allocations);
         Ok(())
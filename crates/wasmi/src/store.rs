@@ -1,10 +1,19 @@
+#[cfg(feature = "debugger")]
+use crate::engine::{Debugger, DebuggerError};
+#[cfg(feature = "profiler")]
+use crate::engine::{Profile, Profiler};
+#[cfg(feature = "trace")]
+use crate::engine::{TraceEvent, TraceHook};
+#[cfg(feature = "debugger")]
+use crate::WatchpointHit;
 use crate::{
-    engine::DedupFuncType,
+    engine::{CoverageCounters, DedupFuncType},
     externref::{ExternObject, ExternObjectEntity, ExternObjectIdx},
     func::{Trampoline, TrampolineEntity, TrampolineIdx},
     memory::{DataSegment, MemoryError},
     module::InstantiationError,
     table::TableError,
+    CoverageReport,
     DataSegmentEntity,
     DataSegmentIdx,
     ElementSegment,
@@ -24,18 +33,19 @@ use crate::{
     Memory,
     MemoryEntity,
     MemoryIdx,
+    Module,
     ResourceLimiter,
     Table,
     TableEntity,
     TableIdx,
 };
-use alloc::boxed::Box;
+use alloc::{boxed::Box, sync::Arc};
 use core::{
     fmt::{self, Debug},
-    sync::atomic::{AtomicU32, Ordering},
+    sync::atomic::{AtomicU32, AtomicUsize, Ordering},
 };
 use wasmi_arena::{Arena, ArenaIndex, GuardedEntity};
-use wasmi_core::TrapCode;
+use wasmi_core::{Trap, TrapCode};
 
 /// A unique store index.
 ///
@@ -101,6 +111,50 @@ impl<T> core::fmt::Debug for ResourceLimiterQuery<T> {
     }
 }
 
+/// A wrapper around a boxed `dyn FnMut(&mut T, CallHook)` that is called
+/// whenever the execution transitions between the host and Wasm.
+///
+/// This wrapper exists to provide a `Debug` impl so that `#[derive(Debug)]`
+/// works on structs that contain it.
+struct CallHookFn<T>(Box<CallHookDyn<T>>);
+
+/// The type erased call hook closure wrapped by [`CallHookFn`].
+type CallHookDyn<T> = dyn FnMut(&mut T, CallHook) -> Result<(), Trap> + Send + Sync;
+impl<T> core::fmt::Debug for CallHookFn<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "CallHookFn(...)")
+    }
+}
+
+/// The kind of transition between the host and Wasm passed to the call hook.
+///
+/// # Note
+///
+/// The call hook is installed via [`Store::call_hook`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum CallHook {
+    /// The execution is about to enter Wasm code.
+    CallingWasm,
+    /// The execution is about to leave Wasm code.
+    ReturningFromWasm,
+    /// A host function is about to be called.
+    CallingHost,
+    /// A host function has returned.
+    ReturningFromHost,
+}
+
+impl CallHook {
+    /// Returns `true` if the execution is entering a host function.
+    pub fn entering_host(&self) -> bool {
+        matches!(self, Self::ReturningFromWasm | Self::CallingHost)
+    }
+
+    /// Returns `true` if the execution is leaving a host function.
+    pub fn exiting_host(&self) -> bool {
+        matches!(self, Self::CallingWasm | Self::ReturningFromHost)
+    }
+}
+
 /// The store that owns all data associated to Wasm modules.
 #[derive(Debug)]
 pub struct Store<T> {
@@ -118,6 +172,8 @@ pub struct Store<T> {
     /// User provided hook to retrieve a
     /// [`ResourceLimiter`](crate::ResourceLimiter).
     limiter: Option<ResourceLimiterQuery<T>>,
+    /// User provided hook called on transitions between the host and Wasm.
+    call_hook: Option<CallHookFn<T>>,
 }
 
 /// The inner store that owns all data not associated to the host state.
@@ -151,6 +207,35 @@ pub struct StoreInner {
     engine: Engine,
     /// The fuel of the [`Store`].
     fuel: Fuel,
+    /// The hits of the coverage counters of the [`Store`].
+    coverage: CoverageCounters,
+    /// The interrupt epoch shared with all [`InterruptHandle`] of the [`Store`].
+    ///
+    /// # Note
+    ///
+    /// Every interrupt request advances the epoch by one.
+    interrupt: Arc<AtomicUsize>,
+    /// The last interrupt epoch observed by the executions of the [`Store`].
+    ///
+    /// # Note
+    ///
+    /// An execution is interrupted once the shared interrupt epoch differs from it.
+    interrupt_seen: usize,
+    /// The number of Wasm executions in progress on the [`Store`].
+    ///
+    /// # Note
+    ///
+    /// This is greater than one if host functions call back into Wasm.
+    executions: usize,
+    /// The hook called for every executed `wasmi` bytecode instruction if any.
+    #[cfg(feature = "trace")]
+    trace_hook: Option<TraceHook>,
+    /// The profiler sampling the executions of the [`Store`] if any.
+    #[cfg(feature = "profiler")]
+    profiler: Option<Profiler>,
+    /// The breakpoints and pending steps of debugged executions.
+    #[cfg(feature = "debugger")]
+    debugger: Debugger,
 }
 
 #[test]
@@ -162,6 +247,8 @@ fn test_store_is_send_sync() {
         fn assert_sync<T: Sync>() {}
         let _ = assert_send::<Store<()>>;
         let _ = assert_sync::<Store<()>>;
+        let _ = assert_send::<InterruptHandle>;
+        let _ = assert_sync::<InterruptHandle>;
     };
 }
 
@@ -258,6 +345,76 @@ impl Fuel {
     }
 }
 
+/// A thread-safe handle to interrupt Wasm executions of a [`Store`].
+///
+/// # Note
+///
+/// - Created via [`Store::interrupt_handle`].
+/// - An interrupted execution traps with [`TrapCode::Interrupted`] the next time
+///   it takes a backward branch or calls a function. This guarantees that even
+///   non-terminating loops and deep recursions are eventually interrupted.
+/// - An interrupt request is consumed once it has been observed by an execution.
+/// - An interrupt request issued while no execution is in progress is discarded
+///   once the next execution of the [`Store`] starts and thus does not affect it.
+///   Since this includes requests racing with the start of an execution, callers
+///   that need a guaranteed interruption should keep issuing requests until
+///   the execution has returned.
+#[derive(Debug, Clone)]
+pub struct InterruptHandle {
+    /// The interrupt epoch shared with the [`Store`].
+    interrupt: Arc<AtomicUsize>,
+}
+
+impl InterruptHandle {
+    /// Requests the interruption of the current Wasm execution of the [`Store`].
+    pub fn interrupt(&self) {
+        self.interrupt.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+/// Tracks a Wasm execution of a [`Store`] for as long as it is alive.
+///
+/// # Note
+///
+/// Calls [`StoreInner::enter_execution`] upon construction and
+/// [`StoreInner::leave_execution`] when dropped so that the execution
+/// is left even if a host function panics and the execution unwinds.
+#[derive(Debug)]
+pub(crate) struct ExecutionGuard<'a, T> {
+    /// The store context of the guarded execution.
+    ctx: StoreContextMut<'a, T>,
+}
+
+impl<'a, T> ExecutionGuard<'a, T> {
+    /// Enters a new Wasm execution of the [`Store`] of `ctx`.
+    pub fn new(ctx: StoreContextMut<'a, T>) -> Self {
+        ctx.store.inner.enter_execution();
+        Self { ctx }
+    }
+}
+
+impl<T> Drop for ExecutionGuard<'_, T> {
+    fn drop(&mut self) {
+        self.ctx.store.inner.leave_execution();
+    }
+}
+
+impl<T> AsContext for ExecutionGuard<'_, T> {
+    type UserState = T;
+
+    #[inline]
+    fn as_context(&self) -> StoreContext<'_, Self::UserState> {
+        self.ctx.as_context()
+    }
+}
+
+impl<T> AsContextMut for ExecutionGuard<'_, T> {
+    #[inline]
+    fn as_context_mut(&mut self) -> StoreContextMut<'_, Self::UserState> {
+        self.ctx.as_context_mut()
+    }
+}
+
 impl StoreInner {
     /// Creates a new [`StoreInner`] for the given [`Engine`].
     pub fn new(engine: &Engine) -> Self {
@@ -273,6 +430,16 @@ impl StoreInner {
             elems: Arena::new(),
             extern_objects: Arena::new(),
             fuel: Fuel::default(),
+            coverage: CoverageCounters::default(),
+            interrupt: Arc::new(AtomicUsize::new(0)),
+            interrupt_seen: 0,
+            executions: 0,
+            #[cfg(feature = "trace")]
+            trace_hook: None,
+            #[cfg(feature = "profiler")]
+            profiler: None,
+            #[cfg(feature = "debugger")]
+            debugger: Debugger::default(),
         }
     }
 
@@ -291,6 +458,100 @@ impl StoreInner {
         &mut self.fuel
     }
 
+    /// Returns an exclusive reference to the [`CoverageCounters`].
+    pub fn coverage_mut(&mut self) -> &mut CoverageCounters {
+        &mut self.coverage
+    }
+
+    /// Returns a new [`InterruptHandle`] for the [`StoreInner`].
+    pub fn interrupt_handle(&self) -> InterruptHandle {
+        InterruptHandle {
+            interrupt: self.interrupt.clone(),
+        }
+    }
+
+    /// Returns `Ok` if no interruption of the current execution has been requested.
+    ///
+    /// Returns a [`TrapCode::Interrupted`] error otherwise and consumes the interrupt request.
+    #[inline]
+    pub fn check_interrupt(&mut self) -> Result<(), TrapCode> {
+        let epoch = self.interrupt.load(Ordering::Relaxed);
+        if epoch != self.interrupt_seen {
+            return self.interrupted(epoch);
+        }
+        Ok(())
+    }
+
+    /// Returns an exclusive reference to the [`TraceHook`] if any.
+    #[cfg(feature = "trace")]
+    #[inline]
+    pub fn trace_hook_mut(&mut self) -> Option<&mut TraceHook> {
+        self.trace_hook.as_mut()
+    }
+
+    /// Returns an exclusive reference to the [`Profiler`] if any.
+    #[cfg(feature = "profiler")]
+    #[inline]
+    pub fn profiler_mut(&mut self) -> Option<&mut Profiler> {
+        self.profiler.as_mut()
+    }
+
+    /// Returns a shared reference to the [`Debugger`] state.
+    #[cfg(feature = "debugger")]
+    #[inline]
+    pub fn debugger(&self) -> &Debugger {
+        &self.debugger
+    }
+
+    /// Returns an exclusive reference to the [`Debugger`] state.
+    #[cfg(feature = "debugger")]
+    #[inline]
+    pub fn debugger_mut(&mut self) -> &mut Debugger {
+        &mut self.debugger
+    }
+
+    /// Signals that a Wasm execution of the [`StoreInner`] starts.
+    ///
+    /// # Note
+    ///
+    /// Discards stale interrupt requests by observing the current interrupt epoch
+    /// if no other execution is in progress. Nested executions started by host
+    /// functions keep pending interrupt requests so that they still interrupt
+    /// the outer execution.
+    ///
+    /// Use [`ExecutionGuard`] to make sure that [`StoreInner::leave_execution`]
+    /// is called even if the execution unwinds.
+    pub fn enter_execution(&mut self) {
+        if self.executions == 0 {
+            self.interrupt_seen = self.interrupt.load(Ordering::Relaxed);
+        }
+        self.executions += 1;
+        #[cfg(feature = "profiler")]
+        if let Some(profiler) = &mut self.profiler {
+            profiler.enter();
+        }
+    }
+
+    /// Signals that a Wasm execution of the [`StoreInner`] has ended.
+    pub fn leave_execution(&mut self) {
+        self.executions -= 1;
+        #[cfg(feature = "profiler")]
+        if let Some(profiler) = &mut self.profiler {
+            profiler.leave();
+        }
+    }
+
+    /// Consumes the interrupt requests up to `epoch` and returns a [`TrapCode::Interrupted`] error.
+    ///
+    /// # Note
+    ///
+    /// This method exists to indicate that this execution path is cold.
+    #[cold]
+    fn interrupted(&mut self, epoch: usize) -> Result<(), TrapCode> {
+        self.interrupt_seen = epoch;
+        Err(TrapCode::Interrupted)
+    }
+
     /// Wraps an entitiy `Idx` (index type) as a [`Stored<Idx>`] type.
     ///
     /// # Note
@@ -729,6 +990,7 @@ impl<T> Store<T> {
             trampolines: Arena::new(),
             data,
             limiter: None,
+            call_hook: None,
         }
     }
 
@@ -762,6 +1024,49 @@ impl<T> Store<T> {
         self.limiter = Some(ResourceLimiterQuery(Box::new(limiter)))
     }
 
+    /// Installs a function into the [`Store`] that is called whenever the
+    /// execution enters or leaves Wasm and whenever a host function is called
+    /// or returns.
+    ///
+    /// Replaces the previously installed call hook if any.
+    ///
+    /// # Note
+    ///
+    /// - The hook receives the user data type `T` and the kind of [`CallHook`].
+    /// - If the hook returns a [`Trap`] the execution is aborted with it.
+    ///   Traps of the called host function or Wasm code take precedence over
+    ///   traps returned upon [`CallHook::ReturningFromHost`] or
+    ///   [`CallHook::ReturningFromWasm`].
+    /// - Traps returned by the hook never yield a resumable call since they
+    ///   did not originate from a host function.
+    /// - Resuming a resumable call or a paused debugged execution enters Wasm again
+    ///   and thus calls the hook with [`CallHook::CallingWasm`]. Likewise, a debugged
+    ///   execution that pauses leaves Wasm with [`CallHook::ReturningFromWasm`].
+    pub fn call_hook(
+        &mut self,
+        hook: impl FnMut(&mut T, CallHook) -> Result<(), Trap> + Send + Sync + 'static,
+    ) {
+        self.call_hook = Some(CallHookFn(Box::new(hook)))
+    }
+
+    /// Removes the call hook of the [`Store`] if any.
+    pub fn remove_call_hook(&mut self) {
+        self.call_hook = None;
+    }
+
+    /// Calls the call hook of the [`Store`] with `kind` if any.
+    ///
+    /// # Errors
+    ///
+    /// If the call hook returns a [`Trap`].
+    #[inline]
+    pub(crate) fn invoke_call_hook(&mut self, kind: CallHook) -> Result<(), Trap> {
+        match &mut self.call_hook {
+            Some(hook) => (hook.0)(&mut self.data, kind),
+            None => Ok(()),
+        }
+    }
+
     pub(crate) fn check_new_instances_limit(
         &mut self,
         num_new_instances: usize,
@@ -869,6 +1174,197 @@ impl<T> Store<T> {
             .map_err(|_error| FuelError::out_of_fuel())
     }
 
+    /// Returns a new thread-safe [`InterruptHandle`] for the [`Store`].
+    ///
+    /// The [`InterruptHandle`] can be used to interrupt Wasm executions of the
+    /// [`Store`] from another thread, e.g. from a watchdog thread.
+    pub fn interrupt_handle(&self) -> InterruptHandle {
+        self.inner.interrupt_handle()
+    }
+
+    /// Returns the code coverage of `module` recorded by executions of the [`Store`] so far.
+    ///
+    /// Returns `None` if code coverage is disabled.
+    ///
+    /// # Note
+    ///
+    /// The coverage of all instances of `module` within the [`Store`] is accumulated.
+    ///
+    /// # Panics
+    ///
+    /// If `module` does not originate from the same [`Engine`] as the [`Store`].
+    ///
+    /// [`Engine`]: crate::Engine
+    pub fn coverage(&self, module: &Module) -> Option<CoverageReport> {
+        assert!(
+            Engine::same(self.engine(), module.engine()),
+            "the module does not originate from the engine of the store"
+        );
+        if !self.engine().config().get_coverage() {
+            return None;
+        }
+        Some(self.engine().coverage(module, &self.inner.coverage))
+    }
+
+    /// Resets the code coverage recorded by executions of the [`Store`] so far.
+    pub fn reset_coverage(&mut self) {
+        self.inner.coverage.reset();
+    }
+
+    /// Sets the hook that is called for every executed `wasmi` bytecode instruction.
+    ///
+    /// Replaces the previously set trace hook if any.
+    ///
+    /// # Note
+    ///
+    /// This is only available with the `trace` crate feature enabled.
+    #[cfg(feature = "trace")]
+    pub fn set_trace_hook(&mut self, hook: impl FnMut(&TraceEvent) + Send + Sync + 'static) {
+        self.inner.trace_hook = Some(TraceHook::new(hook));
+    }
+
+    /// Removes the trace hook of the [`Store`] if any.
+    ///
+    /// # Note
+    ///
+    /// This is only available with the `trace` crate feature enabled.
+    #[cfg(feature = "trace")]
+    pub fn remove_trace_hook(&mut self) {
+        self.inner.trace_hook = None;
+    }
+
+    /// Starts profiling the Wasm executions of the [`Store`].
+    ///
+    /// The elapsed time is attributed to the executed Wasm call stack every `interval`
+    /// executed `wasmi` bytecode instructions and whenever a host function returns.
+    /// Restarts profiling and discards all samples recorded so far if profiling already started.
+    ///
+    /// # Note
+    ///
+    /// - Use [`Store::stop_profiling`] to retrieve the recorded [`Profile`].
+    /// - Smaller intervals attribute the elapsed time more precisely at the expense
+    ///   of a higher profiling overhead. An `interval` of `1000` is a good default.
+    /// - This is only available with the `profiler` crate feature enabled.
+    ///
+    /// # Panics
+    ///
+    /// If `interval` is zero.
+    #[cfg(feature = "profiler")]
+    pub fn start_profiling(&mut self, interval: u64) {
+        let interval = core::num::NonZeroU64::new(interval)
+            .unwrap_or_else(|| panic!("profiling interval must not be zero"));
+        self.inner.profiler = Some(Profiler::new(interval));
+    }
+
+    /// Stops profiling the Wasm executions of the [`Store`] and returns the recorded [`Profile`].
+    ///
+    /// Returns `None` if profiling has not been started via [`Store::start_profiling`].
+    ///
+    /// # Note
+    ///
+    /// This is only available with the `profiler` crate feature enabled.
+    #[cfg(feature = "profiler")]
+    pub fn stop_profiling(&mut self) -> Option<Profile> {
+        let profiler = self.inner.profiler.take()?;
+        Some(self.engine().finish_profiler(&self.inner, profiler))
+    }
+
+    /// Sets a breakpoint at the Wasm operator at `offset` of the function at `func_index` of `module`.
+    ///
+    /// Executions started via [`Func::call_debug`] pause right before the Wasm operator
+    /// is executed in any instance of `module` within the [`Store`].
+    ///
+    /// # Note
+    ///
+    /// - The `offset` is the byte offset of the Wasm operator within the Wasm binary of `module`.
+    /// - This is only available with the `debugger` crate feature enabled.
+    ///
+    /// # Errors
+    ///
+    /// - If `module` does not define a function at `func_index`.
+    /// - If the function has no Wasm operator at `offset`.
+    #[cfg(feature = "debugger")]
+    pub fn set_breakpoint(
+        &mut self,
+        module: &Module,
+        func_index: u32,
+        offset: usize,
+    ) -> Result<(), DebuggerError> {
+        let (func, index) = module.resolve_breakpoint(func_index, offset)?;
+        self.inner.debugger.set_breakpoint(func, index);
+        Ok(())
+    }
+
+    /// Removes the breakpoint at `offset` of the function at `func_index` of `module`.
+    ///
+    /// Returns `true` if there was such a breakpoint.
+    ///
+    /// # Note
+    ///
+    /// This is only available with the `debugger` crate feature enabled.
+    ///
+    /// # Errors
+    ///
+    /// - If `module` does not define a function at `func_index`.
+    /// - If the function has no Wasm operator at `offset`.
+    #[cfg(feature = "debugger")]
+    pub fn remove_breakpoint(
+        &mut self,
+        module: &Module,
+        func_index: u32,
+        offset: usize,
+    ) -> Result<bool, DebuggerError> {
+        let (func, index) = module.resolve_breakpoint(func_index, offset)?;
+        Ok(self.inner.debugger.remove_breakpoint(func, index))
+    }
+
+    /// Removes all breakpoints of the [`Store`].
+    ///
+    /// # Note
+    ///
+    /// This is only available with the `debugger` crate feature enabled.
+    #[cfg(feature = "debugger")]
+    pub fn clear_breakpoints(&mut self) {
+        self.inner.debugger.clear_breakpoints();
+    }
+
+    /// Requests the next execution started via [`Func::call_debug`] to pause before
+    /// its first Wasm instruction or the next resumed execution before its next instruction.
+    ///
+    /// # Note
+    ///
+    /// This is only available with the `debugger` crate feature enabled.
+    #[cfg(feature = "debugger")]
+    pub fn request_pause(&mut self) {
+        self.inner.debugger.request_pause();
+    }
+
+    /// Sets the hook that is called for every memory access observed by a watchpoint.
+    ///
+    /// # Note
+    ///
+    /// - Watchpoints are added via [`Memory::add_watchpoint`].
+    /// - The hook is called before the access is performed.
+    /// - This replaces the previous watchpoint hook if any.
+    /// - This is only available with the `debugger` crate feature enabled.
+    #[cfg(feature = "debugger")]
+    pub fn set_watchpoint_hook(
+        &mut self,
+        hook: impl FnMut(&WatchpointHit) + Send + Sync + 'static,
+    ) {
+        self.inner.debugger.set_watchpoint_hook(hook);
+    }
+
+    /// Removes the watchpoint hook of the [`Store`] if any.
+    ///
+    /// # Note
+    ///
+    /// This is only available with the `debugger` crate feature enabled.
+    #[cfg(feature = "debugger")]
+    pub fn remove_watchpoint_hook(&mut self) {
+        self.inner.debugger.remove_watchpoint_hook();
+    }
+
     /// Allocates a new [`TrampolineEntity`] and returns a [`Trampoline`] reference to it.
     pub(super) fn alloc_trampoline(&mut self, func: TrampolineEntity<T>) -> Trampoline {
         let idx = self.trampolines.alloc(func);
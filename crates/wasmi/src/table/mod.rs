@@ -1,6 +1,7 @@
 pub use self::{
     element::{ElementSegment, ElementSegmentEntity, ElementSegmentIdx},
     error::TableError,
+    typed::TypedTable,
 };
 use super::{AsContext, AsContextMut, Stored};
 use crate::{
@@ -11,6 +12,7 @@ use crate::{
     Func,
     FuncRef,
     Value,
+    WasmType,
 };
 use alloc::vec::Vec;
 use core::cmp::max;
@@ -19,6 +21,7 @@ use wasmi_core::{TrapCode, UntypedValue, ValueType};
 
 mod element;
 mod error;
+mod typed;
 
 #[cfg(test)]
 mod tests;
@@ -728,4 +731,25 @@ impl Table {
             .resolve_table_mut(self)
             .fill(dst, val, len)
     }
+    /// Creates a new [`TypedTable`] from this [`Table`].
+    ///
+    /// # Note
+    ///
+    /// This performs a static type check given `T` as the element type of
+    /// the [`Table`] so that those type checks can be avoided when accessing
+    /// the created [`TypedTable`].
+    ///
+    /// # Errors
+    ///
+    /// If the element type of `self` does not match `T`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ctx` does not own this [`Table`].
+    pub fn typed<T>(&self, ctx: impl AsContext) -> Result<TypedTable<T>, TableError>
+    where
+        T: WasmType,
+    {
+        TypedTable::new(ctx, *self)
+    }
 }
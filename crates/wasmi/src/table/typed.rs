@@ -0,0 +1,171 @@
+use super::{Table, TableError};
+use crate::{AsContext, AsContextMut, WasmType};
+use core::{fmt, fmt::Debug, marker::PhantomData};
+use wasmi_core::TrapCode;
+
+/// A typed [`Table`] instance.
+///
+/// # Note
+///
+/// This allows for more efficient accesses by avoiding type checks upon
+/// accessing the table elements since those type checks are performed
+/// upon [`TypedTable`] construction and enforced by the Rust type system.
+///
+/// The element type `T` is usually either [`FuncRef`] or [`ExternRef`].
+///
+/// [`FuncRef`]: crate::FuncRef
+/// [`ExternRef`]: crate::ExternRef
+#[repr(transparent)]
+pub struct TypedTable<T> {
+    /// The element type of the table encoded in the Rust type system.
+    element: PhantomData<fn() -> T>,
+    /// The underlying [`Table`] instance.
+    table: Table,
+}
+
+impl<T> Debug for TypedTable<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TypedTable")
+            .field("element", &self.element)
+            .field("table", &self.table)
+            .finish()
+    }
+}
+
+impl<T> Copy for TypedTable<T> {}
+
+impl<T> Clone for TypedTable<T> {
+    fn clone(&self) -> TypedTable<T> {
+        *self
+    }
+}
+
+impl<T> TypedTable<T> {
+    /// Returns the underlying [`Table`].
+    ///
+    /// # Note
+    ///
+    /// This loses the static type information in the process.
+    pub fn table(&self) -> &Table {
+        &self.table
+    }
+
+    /// Returns the current size of the [`TypedTable`].
+    ///
+    /// # Panics
+    ///
+    /// If `ctx` does not own this [`TypedTable`].
+    pub fn size(&self, ctx: impl AsContext) -> u32 {
+        self.table.size(ctx)
+    }
+}
+
+impl<T> TypedTable<T>
+where
+    T: WasmType,
+{
+    /// Creates a new [`TypedTable`] for the given [`Table`] using the static typing.
+    ///
+    /// # Errors
+    ///
+    /// If the element type of `table` does not match `T`.
+    pub(crate) fn new(ctx: impl AsContext, table: Table) -> Result<Self, TableError> {
+        table.ty(ctx).matches_element_type(<T as WasmType>::ty())?;
+        Ok(Self {
+            element: PhantomData,
+            table,
+        })
+    }
+
+    /// Returns the [`TypedTable`] element value at `index`.
+    ///
+    /// Returns `None` if `index` is out of bounds.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `ctx` does not own this [`TypedTable`].
+    /// - Panics if the element is no valid representation of `T`.
+    pub fn get(&self, ctx: impl AsContext, index: u32) -> Option<T> {
+        ctx.as_context()
+            .store
+            .inner
+            .resolve_table(&self.table)
+            .get_untyped(index)
+            .map(|value| {
+                T::from_untyped(value)
+                    .unwrap_or_else(|error| panic!("invalid element of typed table: {error}"))
+            })
+    }
+
+    /// Sets the `value` of this [`TypedTable`] at `index`.
+    ///
+    /// # Errors
+    ///
+    /// If `index` is out of bounds.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ctx` does not own this [`TypedTable`].
+    pub fn set(&self, mut ctx: impl AsContextMut, index: u32, value: T) -> Result<(), TableError> {
+        ctx.as_context_mut()
+            .store
+            .inner
+            .resolve_table_mut(&self.table)
+            .set_untyped(index, value.into_untyped())
+    }
+
+    /// Grows the table by the given amount of elements.
+    ///
+    /// Returns the old size of the [`TypedTable`] upon success.
+    ///
+    /// # Note
+    ///
+    /// The newly added elements are initialized to `init`.
+    ///
+    /// # Errors
+    ///
+    /// If the table is grown beyond its maximum limits.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ctx` does not own this [`TypedTable`].
+    pub fn grow(&self, mut ctx: impl AsContextMut, delta: u32, init: T) -> Result<u32, TableError> {
+        let (inner, mut limiter) = ctx
+            .as_context_mut()
+            .store
+            .store_inner_and_resource_limiter_ref();
+        let table = inner.resolve_table_mut(&self.table);
+        let current = table.size();
+        let maximum = table.ty().maximum().unwrap_or(u32::MAX);
+        table
+            .grow_untyped(delta, init.into_untyped(), &mut limiter)
+            .map_err(|_| TableError::GrowOutOfBounds {
+                maximum,
+                current,
+                delta,
+            })
+    }
+
+    /// Fill `table[dst..(dst + len)]` with the given value.
+    ///
+    /// # Errors
+    ///
+    /// If the region to be filled is out of bounds for the [`TypedTable`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ctx` does not own this [`TypedTable`].
+    pub fn fill(
+        &self,
+        mut ctx: impl AsContextMut,
+        dst: u32,
+        val: T,
+        len: u32,
+    ) -> Result<(), TrapCode> {
+        ctx.as_context_mut()
+            .store
+            .inner
+            .resolve_table_mut(&self.table)
+            .fill_untyped(dst, val.into_untyped(), len)
+    }
+}
@@ -0,0 +1,140 @@
+//! Tests to check if traps carry the backtrace of the Wasm function frames.
+
+use wasmi::{
+    core::{FrameInfo, Trap, TrapCode},
+    Caller,
+    Engine,
+    Func,
+    Linker,
+    ResumableCall,
+    Store,
+    Value,
+};
+
+/// The Wasm module under test.
+///
+/// The `unreachable` operator is located via the preceding `i32.const 1000` operator.
+const WAT: &str = r#"
+    (module
+        (import "env" "fail" (func $fail))
+        (func $trap (param i32)
+            i32.const 1000
+            drop
+            unreachable
+        )
+        (func (export "run") (param i32)
+            local.get 0
+            call $trap
+        )
+        (func (export "host") (call $fail))
+        (func $recurse (export "recurse") (call $recurse))
+    )
+"#;
+
+/// Instantiates the test module and returns its Wasm binary and the exported `name` function.
+fn setup(name: &str) -> (Store<()>, Vec<u8>, Func) {
+    let wasm = wat::parse_str(WAT).unwrap();
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, ());
+    let module = wasmi::Module::new(&engine, &wasm[..]).unwrap();
+    let mut linker = <Linker<()>>::new(&engine);
+    linker
+        .func_wrap("env", "fail", |_caller: Caller<()>| -> Result<(), Trap> {
+            Err(Trap::new("host failure"))
+        })
+        .unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let func = instance.get_func(&store, name).unwrap();
+    (store, wasm, func)
+}
+
+/// Calls `func` with `inputs` and returns the resulting [`Trap`].
+fn call_trap(store: &mut Store<()>, func: Func, inputs: &[Value]) -> Trap {
+    match func.call(store, inputs, &mut []).unwrap_err() {
+        wasmi::Error::Trap(trap) => trap,
+        error => panic!("expected a trap but found: {error}"),
+    }
+}
+
+/// Returns the function indices and names of the `frames`.
+fn funcs(frames: &[FrameInfo]) -> Vec<(u32, Option<&str>)> {
+    frames
+        .iter()
+        .map(|frame| (frame.func_index(), frame.func_name()))
+        .collect()
+}
+
+#[test]
+fn wasm_trap_backtrace() {
+    let (mut store, wasm, run) = setup("run");
+    let trap = call_trap(&mut store, run, &[Value::I32(0)]);
+    assert!(matches!(
+        trap.trap_code(),
+        Some(TrapCode::UnreachableCodeReached)
+    ));
+    let frames = trap.backtrace().unwrap().frames();
+    assert_eq!(funcs(frames), [(1, Some("trap")), (2, Some("run"))]);
+    let i32_const = wasm
+        .windows(3)
+        .position(|window| window == [0x41, 0xE8, 0x07])
+        .unwrap();
+    let unreachable = i32_const + 4;
+    assert_eq!(frames[0].wasm_offset(), Some(unreachable));
+    // The caller frame points to its `call` operator.
+    let call = unreachable + 6;
+    assert_eq!(frames[1].wasm_offset(), Some(call));
+    assert_eq!(wasm[call], 0x10);
+}
+
+#[test]
+fn host_trap_backtrace() {
+    let (mut store, wasm, host) = setup("host");
+    let trap = call_trap(&mut store, host, &[]);
+    let frames = trap.backtrace().unwrap().frames();
+    assert_eq!(funcs(frames), [(3, Some("host"))]);
+    let offset = frames[0].wasm_offset().unwrap();
+    assert_eq!(wasm[offset], 0x10);
+}
+
+#[test]
+fn resumable_host_trap_has_no_backtrace() {
+    let (mut store, _wasm, host) = setup("host");
+    let invocation = match host.call_resumable(&mut store, &[], &mut []).unwrap() {
+        ResumableCall::Resumable(invocation) => invocation,
+        ResumableCall::Finished => panic!("expected a resumable call"),
+    };
+    // The backtrace is not captured since the call might be resumed.
+    assert!(invocation.host_error().backtrace().is_none());
+}
+
+#[test]
+fn stack_overflow_backtrace() {
+    let (mut store, _wasm, recurse) = setup("recurse");
+    let trap = call_trap(&mut store, recurse, &[]);
+    assert!(matches!(trap.trap_code(), Some(TrapCode::StackOverflow)));
+    let frames = trap.backtrace().unwrap().frames();
+    assert!(frames.len() > 1);
+    assert!(frames
+        .iter()
+        .all(|frame| frame.func_index() == 4 && frame.func_name() == Some("recurse")));
+}
+
+#[test]
+fn display_backtrace() {
+    let (mut store, _wasm, run) = setup("run");
+    let trap = call_trap(&mut store, run, &[Value::I32(0)]);
+    let message = trap.to_string();
+    let mut lines = message.lines();
+    assert_eq!(
+        lines.next(),
+        Some("wasm `unreachable` instruction executed")
+    );
+    assert_eq!(lines.next(), Some("wasm backtrace:"));
+    assert!(lines.next().unwrap().ends_with("<unknown>!trap"));
+    assert!(lines.next().unwrap().ends_with("<unknown>!run"));
+    assert_eq!(lines.next(), None);
+}
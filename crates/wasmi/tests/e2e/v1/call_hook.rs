@@ -0,0 +1,171 @@
+//! Tests for the call hook of the [`Store`].
+
+use wasmi::{CallHook, Caller, Engine, Func, Linker, Module, Store, TypedResumableCall, Value};
+use wasmi_core::Trap;
+
+/// Converts the `wat` string source into `wasm` encoded byte.
+fn wat2wasm(wat: &str) -> Vec<u8> {
+    wat::parse_str(wat).unwrap()
+}
+
+const WAT: &str = r#"
+    (module
+        (import "env" "host" (func $host (param i32) (result i32)))
+        (func (export "run") (param i32) (result i32)
+            (call $host (local.get 0))
+        )
+    )
+"#;
+
+/// Instantiates [`WAT`] and records all observed [`CallHook`] events in the store.
+fn setup() -> (Store<Vec<CallHook>>, Func) {
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, Vec::new());
+    store.call_hook(|events, hook| {
+        events.push(hook);
+        Ok(())
+    });
+    let mut linker = <Linker<Vec<CallHook>>>::new(&engine);
+    linker
+        .func_wrap(
+            "env",
+            "host",
+            |_caller: Caller<Vec<CallHook>>, input: i32| -> Result<i32, Trap> {
+                match input {
+                    0 => Err(Trap::new("host trap")),
+                    input => Ok(input + 1),
+                }
+            },
+        )
+        .unwrap();
+    let module = Module::new(store.engine(), &wat2wasm(WAT)[..]).unwrap();
+    let run = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap()
+        .get_func(&store, "run")
+        .unwrap();
+    (store, run)
+}
+
+#[test]
+fn observes_transitions() {
+    let (mut store, run) = setup();
+    let run = run.typed::<i32, i32>(&store).unwrap();
+    assert_eq!(run.call(&mut store, 41).unwrap(), 42);
+    assert_eq!(
+        store.data(),
+        &[
+            CallHook::CallingWasm,
+            CallHook::CallingHost,
+            CallHook::ReturningFromHost,
+            CallHook::ReturningFromWasm,
+        ]
+    );
+    store.data_mut().clear();
+    assert!(run.call(&mut store, 0).is_err());
+    assert_eq!(
+        store.data(),
+        &[
+            CallHook::CallingWasm,
+            CallHook::CallingHost,
+            CallHook::ReturningFromHost,
+            CallHook::ReturningFromWasm,
+        ]
+    );
+    store.remove_call_hook();
+    store.data_mut().clear();
+    run.call(&mut store, 1).unwrap();
+    assert!(store.data().is_empty());
+}
+
+#[test]
+fn host_func_as_root() {
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, Vec::new());
+    store.call_hook(|events, hook| {
+        events.push(hook);
+        Ok(())
+    });
+    let func = Func::wrap(&mut store, || {});
+    func.typed::<(), ()>(&store)
+        .unwrap()
+        .call(&mut store, ())
+        .unwrap();
+    assert_eq!(
+        store.data(),
+        &[CallHook::CallingHost, CallHook::ReturningFromHost]
+    );
+}
+
+#[test]
+fn hook_traps() {
+    let (mut store, run) = setup();
+    let run = run.typed::<i32, i32>(&store).unwrap();
+    store.call_hook(|events, hook| {
+        events.push(hook);
+        match hook {
+            CallHook::CallingHost => Err(Trap::new("host calls are forbidden")),
+            _ => Ok(()),
+        }
+    });
+    let error = run.call(&mut store, 1).unwrap_err();
+    assert!(error.to_string().contains("host calls are forbidden"));
+    assert_eq!(
+        store.data(),
+        &[
+            CallHook::CallingWasm,
+            CallHook::CallingHost,
+            CallHook::ReturningFromWasm,
+        ]
+    );
+    // The host trap takes precedence over the trap of the call hook.
+    store.call_hook(|_, hook| match hook {
+        CallHook::ReturningFromHost => Err(Trap::new("hook trap")),
+        _ => Ok(()),
+    });
+    let error = run.call(&mut store, 0).unwrap_err();
+    assert!(error.to_string().contains("host trap"));
+    let error = run.call(&mut store, 1).unwrap_err();
+    assert!(error.to_string().contains("hook trap"));
+}
+
+#[test]
+fn resumable_calls() {
+    let (mut store, run) = setup();
+    let run = run.typed::<i32, i32>(&store).unwrap();
+    let invocation = match run.call_resumable(&mut store, 0).unwrap() {
+        TypedResumableCall::Resumable(invocation) => invocation,
+        TypedResumableCall::Finished(_) => panic!("expected TypedResumableCall::Resumable"),
+    };
+    store.data_mut().clear();
+    match invocation.resume(&mut store, &[Value::I32(5)]).unwrap() {
+        TypedResumableCall::Finished(result) => assert_eq!(result, 5),
+        TypedResumableCall::Resumable(_) => panic!("expected TypedResumableCall::Finished"),
+    }
+    assert_eq!(
+        store.data(),
+        &[CallHook::CallingWasm, CallHook::ReturningFromWasm]
+    );
+}
+
+#[test]
+fn hook_traps_are_not_resumable() {
+    let (mut store, run) = setup();
+    let run = run.typed::<i32, i32>(&store).unwrap();
+    for kind in [CallHook::CallingHost, CallHook::ReturningFromHost] {
+        store.call_hook(move |_, hook| match hook == kind {
+            true => Err(Trap::new("hook trap")),
+            false => Ok(()),
+        });
+        let error = run.call_resumable(&mut store, 1).unwrap_err();
+        assert!(error.to_string().contains("hook trap"));
+    }
+    // Host traps remain resumable.
+    store.remove_call_hook();
+    assert!(matches!(
+        run.call_resumable(&mut store, 0).unwrap(),
+        TypedResumableCall::Resumable(_)
+    ));
+}
@@ -0,0 +1,106 @@
+//! Tests to check if the compilation and instantiation fuel budgets work as intended.
+
+use wasmi::{
+    errors::{InstantiationError, ModuleError},
+    Config,
+    Engine,
+    Error,
+    Linker,
+    Module,
+    Store,
+};
+
+/// Converts the `wat` string source into `wasm` encoded byte.
+fn wat2wasm(wat: &str) -> Vec<u8> {
+    wat::parse_str(wat).unwrap()
+}
+
+/// Compiles the `wat` source with the given compilation fuel budget.
+fn compile_with_fuel(wat: &str, fuel: u64) -> Result<Module, Error> {
+    let mut config = Config::default();
+    config.set_compilation_fuel(fuel);
+    let engine = Engine::new(&config);
+    Module::new(&engine, &wat2wasm(wat)[..])
+}
+
+/// Compiles and instantiates the `wat` source with the given instantiation fuel budget.
+fn instantiate_with_fuel(wat: &str, fuel: u64) -> Result<(), Error> {
+    let mut config = Config::default();
+    config.set_instantiation_fuel(fuel);
+    let engine = Engine::new(&config);
+    let mut store = Store::new(&engine, ());
+    let linker = <Linker<()>>::new(&engine);
+    let module = Module::new(&engine, &wat2wasm(wat)[..]).unwrap();
+    linker.instantiate(&mut store, &module).map(|_| ())
+}
+
+/// A module with a function body that requires some amount of compilation fuel.
+const WAT_FUNC: &str = r#"
+    (module
+        (func (export "test") (param $a i32) (param $b i32) (result i32)
+            (i32.add
+                (i32.mul (local.get $a) (local.get $b))
+                (i32.sub (local.get $a) (local.get $b))
+            )
+        )
+    )
+"#;
+
+#[test]
+fn compilation_fuel_sufficient() {
+    assert!(compile_with_fuel(WAT_FUNC, 1000).is_ok());
+}
+
+#[test]
+fn compilation_fuel_exhausted() {
+    assert!(matches!(
+        compile_with_fuel(WAT_FUNC, 10),
+        Err(Error::Module(ModuleError::OutOfFuel)),
+    ));
+}
+
+#[test]
+fn compilation_fuel_exhausted_in_large_func() {
+    let body = "(drop (i32.const 0))\n".repeat(10_000);
+    let wat = format!("(module (func {body}))");
+    assert!(matches!(
+        compile_with_fuel(&wat, 100),
+        Err(Error::Module(ModuleError::OutOfFuel)),
+    ));
+}
+
+#[test]
+fn compilation_fuel_without_funcs() {
+    assert!(compile_with_fuel("(module (memory 1))", 0).is_ok());
+}
+
+#[test]
+fn instantiation_fuel_data_segment() {
+    let wat = r#"
+        (module
+            (memory 1)
+            (data (i32.const 0) "0123456789")
+        )
+    "#;
+    assert!(matches!(
+        instantiate_with_fuel(wat, 9),
+        Err(Error::Instantiation(InstantiationError::OutOfFuel)),
+    ));
+    assert!(instantiate_with_fuel(wat, 10).is_ok());
+}
+
+#[test]
+fn instantiation_fuel_element_segment() {
+    let wat = r#"
+        (module
+            (table 4 funcref)
+            (func $f)
+            (elem (i32.const 0) $f $f $f $f)
+        )
+    "#;
+    assert!(matches!(
+        instantiate_with_fuel(wat, 3),
+        Err(Error::Instantiation(InstantiationError::OutOfFuel)),
+    ));
+    assert!(instantiate_with_fuel(wat, 4).is_ok());
+}
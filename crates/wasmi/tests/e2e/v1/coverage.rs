@@ -0,0 +1,176 @@
+//! Tests for the code coverage recorded by executions of a [`Store`].
+
+use wasmi::{Config, CoverageReport, Engine, Linker, Module, Store};
+
+/// The Wasm module under test.
+const WAT: &str = r#"
+    (module
+        (func $abs (export "abs") (param i32) (result i32)
+            (if (result i32) (i32.lt_s (local.get 0) (i32.const 0))
+                (then (i32.sub (i32.const 0) (local.get 0)))
+                (else (local.get 0))
+            )
+        )
+        (func $count (export "count") (param i32) (result i32)
+            (local i32)
+            (loop $continue
+                (local.set 1 (i32.add (local.get 1) (i32.const 1)))
+                (br_if $continue (i32.lt_s (local.get 1) (local.get 0)))
+            )
+            (local.get 1)
+        )
+        (func $unused (export "unused"))
+    )
+"#;
+
+/// Returns a [`Store`] and [`Module`] of the test module compiled with `config`.
+fn setup(config: &Config) -> (Store<()>, Module) {
+    let engine = Engine::new(config);
+    let wasm = wat::parse_str(WAT).unwrap();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    (Store::new(&engine, ()), module)
+}
+
+/// Returns a [`Config`] with code coverage enabled.
+fn coverage_config() -> Config {
+    let mut config = Config::default();
+    config.coverage(true);
+    config
+}
+
+/// Instantiates the `module` and calls its exported `abs` and `count` functions.
+fn run(store: &mut Store<()>, module: &Module) {
+    let instance = <Linker<()>>::new(store.engine())
+        .instantiate(&mut *store, module)
+        .unwrap()
+        .start(&mut *store)
+        .unwrap();
+    let abs = instance.get_typed_func::<i32, i32>(&*store, "abs").unwrap();
+    for (input, expected) in [(5, 5), (-3, 3), (7, 7)] {
+        assert_eq!(abs.call(&mut *store, input).unwrap(), expected);
+    }
+    let count = instance
+        .get_typed_func::<i32, i32>(&*store, "count")
+        .unwrap();
+    assert_eq!(count.call(&mut *store, 4).unwrap(), 4);
+}
+
+/// Returns the hits of all basic blocks of the function named `name` in the `report`.
+fn block_hits(report: &CoverageReport, name: &str) -> Vec<u64> {
+    report
+        .funcs()
+        .iter()
+        .find(|func| func.name() == Some(name))
+        .unwrap_or_else(|| panic!("missing coverage for {name:?}"))
+        .blocks()
+        .iter()
+        .map(|block| block.hits())
+        .collect()
+}
+
+#[test]
+fn coverage_disabled() {
+    let (mut store, module) = setup(&Config::default());
+    run(&mut store, &module);
+    assert!(store.coverage(&module).is_none());
+}
+
+#[test]
+fn coverage_hits() {
+    let (mut store, module) = setup(&coverage_config());
+    run(&mut store, &module);
+    let report = store.coverage(&module).unwrap();
+    // Entry, `then`, `else` and after the `if`.
+    assert_eq!(block_hits(&report, "abs"), [3, 1, 2, 3]);
+    // Entry, `loop` header and the fallthrough of `br_if`.
+    assert_eq!(block_hits(&report, "count"), [1, 4, 1]);
+    assert_eq!(block_hits(&report, "unused"), [0]);
+    assert_eq!(report.func(0).unwrap().hits(), 3);
+    assert!(report.funcs().iter().all(|func| func
+        .blocks()
+        .iter()
+        .all(|block| block.file().is_none() && block.line().is_none())));
+}
+
+#[test]
+fn coverage_accumulates_and_resets() {
+    let (mut store, module) = setup(&coverage_config());
+    run(&mut store, &module);
+    run(&mut store, &module);
+    let report = store.coverage(&module).unwrap();
+    assert_eq!(block_hits(&report, "abs"), [6, 2, 4, 6]);
+    store.reset_coverage();
+    let report = store.coverage(&module).unwrap();
+    assert_eq!(block_hits(&report, "abs"), [0, 0, 0, 0]);
+    // Other stores of the same engine record their own coverage.
+    let mut other = Store::new(store.engine(), ());
+    run(&mut other, &module);
+    assert_eq!(block_hits(&store.coverage(&module).unwrap(), "abs"), [0; 4]);
+    assert_eq!(
+        block_hits(&other.coverage(&module).unwrap(), "abs"),
+        [3, 1, 2, 3]
+    );
+    // Executions after a reset are recorded again.
+    run(&mut store, &module);
+    assert_eq!(
+        block_hits(&store.coverage(&module).unwrap(), "abs"),
+        [3, 1, 2, 3]
+    );
+}
+
+#[test]
+fn coverage_module_compiled_after_instantiation() {
+    let (mut store, module) = setup(&coverage_config());
+    run(&mut store, &module);
+    // Counters of modules compiled after the first instantiation of the
+    // store are available to the store once they are instantiated.
+    let wasm = wat::parse_str(WAT).unwrap();
+    let later = Module::new(store.engine(), &wasm[..]).unwrap();
+    run(&mut store, &later);
+    assert_eq!(
+        block_hits(&store.coverage(&later).unwrap(), "abs"),
+        [3, 1, 2, 3]
+    );
+    assert_eq!(
+        block_hits(&store.coverage(&module).unwrap(), "abs"),
+        [3, 1, 2, 3]
+    );
+}
+
+#[test]
+fn coverage_lcov() {
+    let (mut store, module) = setup(&coverage_config());
+    run(&mut store, &module);
+    let report = store.coverage(&module).unwrap();
+    let abs = report.func(0).unwrap().blocks()[0].wasm_offset();
+    let lcov = report.to_lcov();
+    assert!(lcov.starts_with("TN:\nSF:module.wasm\n"));
+    assert!(lcov.contains(&format!("FN:{abs},abs\n")));
+    assert!(lcov.contains("FNDA:3,abs\nFNDA:1,count\nFNDA:0,unused\nFNF:3\nFNH:2\n"));
+    assert!(lcov.contains(&format!("DA:{abs},3\n")));
+    // The entry and `loop` header of `count` share the offset of the `loop` operator.
+    assert!(lcov.contains("LF:7\nLH:6\n"));
+    assert!(lcov.ends_with("end_of_record\n"));
+}
+
+#[test]
+fn coverage_json() {
+    let (mut store, module) = setup(&coverage_config());
+    run(&mut store, &module);
+    let report = store.coverage(&module).unwrap();
+    let unused = report.func(2).unwrap().blocks()[0].wasm_offset();
+    let json = report.to_json();
+    assert!(json.starts_with(
+        r#"{"module":null,"functions":[{"index":0,"name":"abs","hits":3,"blocks":[{"offset":"#
+    ));
+    assert!(json.ends_with(&format!(
+        r#"{{"index":2,"name":"unused","hits":0,"blocks":[{{"offset":{unused},"hits":0,"file":null,"line":null}}]}}]}}"#
+    )));
+}
+
+#[test]
+fn coverage_disassembly() {
+    let (_, module) = setup(&coverage_config());
+    let listing = module.disassemble();
+    assert_eq!(listing.matches("count_coverage counter[").count(), 8);
+}
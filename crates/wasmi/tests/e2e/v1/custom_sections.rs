@@ -0,0 +1,43 @@
+//! Tests to check if custom sections are retained as configured.
+
+use wasmi::{Config, Engine, Module};
+
+/// Appends a custom section with `name` and `data` to the `wasm` binary.
+fn push_custom_section(wasm: &mut Vec<u8>, name: &str, data: &[u8]) {
+    let len = 1 + name.len() + data.len();
+    assert!(
+        len < 0x80 && name.len() < 0x80,
+        "test sections must be small"
+    );
+    wasm.extend([0x00, len as u8, name.len() as u8]);
+    wasm.extend(name.as_bytes());
+    wasm.extend(data);
+}
+
+/// Returns a Wasm binary with some custom sections.
+fn wasm() -> Vec<u8> {
+    let mut wasm = wat::parse_str("(module (func (export \"f\")))").unwrap();
+    push_custom_section(&mut wasm, "producers", b"wasmi");
+    push_custom_section(&mut wasm, "abi-version", &[1, 2]);
+    push_custom_section(&mut wasm, "producers", b"tests");
+    wasm
+}
+
+#[test]
+fn retain_custom_sections() {
+    let module = Module::new(&Engine::default(), &wasm()[..]).unwrap();
+    let producers: Vec<&[u8]> = module.custom_sections("producers").collect();
+    assert_eq!(producers, [&b"wasmi"[..], &b"tests"[..]]);
+    let abi: Vec<&[u8]> = module.custom_sections("abi-version").collect();
+    assert_eq!(abi, [&[1, 2][..]]);
+    assert_eq!(module.custom_sections("missing").count(), 0);
+}
+
+#[test]
+fn skip_custom_sections() {
+    let mut config = Config::default();
+    config.retain_custom_sections(false);
+    let module = Module::new(&Engine::new(&config), &wasm()[..]).unwrap();
+    assert_eq!(module.custom_sections("producers").count(), 0);
+    assert_eq!(module.custom_sections("abi-version").count(), 0);
+}
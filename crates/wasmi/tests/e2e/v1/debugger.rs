@@ -0,0 +1,271 @@
+//! Tests to check if breakpoints, single-stepping and inspection of paused executions work.
+
+use wasmi::{
+    core::UntypedValue,
+    errors::DebuggerError,
+    DebugCall,
+    Engine,
+    Func,
+    Linker,
+    Module,
+    PausedInvocation,
+    Store,
+    Value,
+};
+
+/// The Wasm module under test.
+///
+/// The `i32.const 1000` operator is used to locate the function body of `run`.
+const WAT: &str = r#"
+    (module
+        (global $g (mut i32) (i32.const 7))
+        (func $add (param i32 i32) (result i32)
+            local.get 0
+            local.get 1
+            i32.add
+        )
+        (func (export "run") (param i32) (result i32)
+            (local i32)
+            i32.const 1000
+            local.set 1
+            local.get 0
+            local.get 1
+            call $add
+            global.get $g
+            i32.add
+        )
+    )
+"#;
+
+/// The Wasm byte offsets of the operators of the `run` function.
+struct Offsets {
+    i32_const: usize,
+    local_set: usize,
+    call: usize,
+    global_get: usize,
+}
+
+impl Offsets {
+    /// Locates the operators of the `run` function in the `wasm` binary.
+    fn new(wasm: &[u8]) -> Self {
+        let i32_const = wasm
+            .windows(3)
+            .position(|window| window == [0x41, 0xE8, 0x07])
+            .unwrap();
+        let local_set = i32_const + 3;
+        let local_get_0 = local_set + 2;
+        let local_get_1 = local_get_0 + 2;
+        let call = local_get_1 + 2;
+        let global_get = call + 2;
+        Self {
+            i32_const,
+            local_set,
+            call,
+            global_get,
+        }
+    }
+}
+
+/// Instantiates the test module and returns it with its offsets and its `run` function.
+fn setup() -> (Store<()>, Module, Offsets, Func) {
+    let wasm = wat::parse_str(WAT).unwrap();
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, ());
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let instance = <Linker<()>>::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let run = instance.get_func(&store, "run").unwrap();
+    (store, module, Offsets::new(&wasm), run)
+}
+
+/// Asserts that the `call` has been paused and returns the [`PausedInvocation`].
+fn paused(call: DebugCall) -> PausedInvocation {
+    match call {
+        DebugCall::Paused(invocation) => invocation,
+        DebugCall::Finished => panic!("expected the call to be paused"),
+    }
+}
+
+/// Converts the `values` into `i32` values.
+fn i32s(values: &[UntypedValue]) -> Vec<i32> {
+    values.iter().copied().map(i32::from).collect()
+}
+
+#[test]
+fn breakpoint_and_resume() {
+    let (mut store, module, offsets, run) = setup();
+    store.set_breakpoint(&module, 1, offsets.i32_const).unwrap();
+    let mut results = [Value::I32(0)];
+    let invocation = paused(
+        run.call_debug(&mut store, &[Value::I32(5)], &mut results)
+            .unwrap(),
+    );
+    let frames = invocation.frames();
+    assert_eq!(frames.len(), 1);
+    assert_eq!(frames[0].func_index(), 1);
+    assert_eq!(frames[0].wasm_offset(), Some(offsets.i32_const));
+    assert_eq!(i32s(invocation.locals(0).unwrap()), [5, 0]);
+    assert!(invocation.operands(0).unwrap().is_empty());
+    let call = invocation.resume(&mut store, &mut results).unwrap();
+    assert!(matches!(call, DebugCall::Finished));
+    assert_eq!(results[0].i32(), Some(1012));
+}
+
+#[test]
+fn step_through_operators() {
+    let (mut store, module, offsets, run) = setup();
+    store.set_breakpoint(&module, 1, offsets.i32_const).unwrap();
+    let mut results = [Value::I32(0)];
+    let invocation = paused(
+        run.call_debug(&mut store, &[Value::I32(5)], &mut results)
+            .unwrap(),
+    );
+    let invocation = paused(invocation.step(&mut store, &mut results).unwrap());
+    assert_eq!(
+        invocation.frames()[0].wasm_offset(),
+        Some(offsets.local_set)
+    );
+    assert_eq!(i32s(invocation.operands(0).unwrap()), [1000]);
+    let invocation = paused(invocation.step(&mut store, &mut results).unwrap());
+    assert_eq!(i32s(invocation.locals(0).unwrap()), [5, 1000]);
+    assert!(invocation.operands(0).unwrap().is_empty());
+    let call = invocation.resume(&mut store, &mut results).unwrap();
+    assert!(matches!(call, DebugCall::Finished));
+    assert_eq!(results[0].i32(), Some(1012));
+}
+
+#[test]
+fn step_into_and_out_of_call() {
+    let (mut store, module, offsets, run) = setup();
+    store.set_breakpoint(&module, 1, offsets.call).unwrap();
+    let mut results = [Value::I32(0)];
+    let invocation = paused(
+        run.call_debug(&mut store, &[Value::I32(5)], &mut results)
+            .unwrap(),
+    );
+    assert_eq!(i32s(invocation.operands(0).unwrap()), [5, 1000]);
+    let invocation = paused(invocation.step(&mut store, &mut results).unwrap());
+    let frames = invocation.frames();
+    assert_eq!(frames.len(), 2);
+    assert_eq!(frames[0].func_index(), 0);
+    assert_eq!(frames[1].func_index(), 1);
+    assert_eq!(frames[1].wasm_offset(), Some(offsets.call));
+    assert_eq!(i32s(invocation.locals(0).unwrap()), [5, 1000]);
+    assert_eq!(i32s(invocation.locals(1).unwrap()), [5, 1000]);
+    assert!(invocation.operands(1).unwrap().is_empty());
+    let invocation = paused(invocation.step_out(&mut store, &mut results).unwrap());
+    let frames = invocation.frames();
+    assert_eq!(frames.len(), 1);
+    assert_eq!(frames[0].wasm_offset(), Some(offsets.global_get));
+    assert_eq!(i32s(invocation.operands(0).unwrap()), [1005]);
+}
+
+#[test]
+fn step_over_call() {
+    let (mut store, module, offsets, run) = setup();
+    store.set_breakpoint(&module, 1, offsets.call).unwrap();
+    let mut results = [Value::I32(0)];
+    let invocation = paused(
+        run.call_debug(&mut store, &[Value::I32(5)], &mut results)
+            .unwrap(),
+    );
+    let invocation = paused(invocation.step_over(&mut store, &mut results).unwrap());
+    assert_eq!(invocation.frames().len(), 1);
+    assert_eq!(
+        invocation.frames()[0].wasm_offset(),
+        Some(offsets.global_get)
+    );
+}
+
+#[test]
+fn modify_paused_state() {
+    let (mut store, module, offsets, run) = setup();
+    store.set_breakpoint(&module, 1, offsets.call).unwrap();
+    let mut results = [Value::I32(0)];
+    let mut invocation = paused(
+        run.call_debug(&mut store, &[Value::I32(5)], &mut results)
+            .unwrap(),
+    );
+    invocation.operands_mut(0).unwrap()[0] = UntypedValue::from(10_i32);
+    let global = invocation.global(&store, 0, 0).unwrap();
+    assert_eq!(global.get(&store).i32(), Some(7));
+    global.set(&mut store, Value::I32(100)).unwrap();
+    let call = invocation.resume(&mut store, &mut results).unwrap();
+    assert!(matches!(call, DebugCall::Finished));
+    assert_eq!(results[0].i32(), Some(1110));
+}
+
+#[test]
+fn remove_breakpoints() {
+    let (mut store, module, offsets, run) = setup();
+    store.set_breakpoint(&module, 1, offsets.i32_const).unwrap();
+    store.set_breakpoint(&module, 1, offsets.call).unwrap();
+    assert_eq!(
+        store.remove_breakpoint(&module, 1, offsets.i32_const),
+        Ok(true)
+    );
+    assert_eq!(
+        store.remove_breakpoint(&module, 1, offsets.i32_const),
+        Ok(false)
+    );
+    let mut results = [Value::I32(0)];
+    let invocation = paused(
+        run.call_debug(&mut store, &[Value::I32(5)], &mut results)
+            .unwrap(),
+    );
+    assert_eq!(invocation.frames()[0].wasm_offset(), Some(offsets.call));
+    drop(invocation);
+    store.clear_breakpoints();
+    let call = run
+        .call_debug(&mut store, &[Value::I32(5)], &mut results)
+        .unwrap();
+    assert!(matches!(call, DebugCall::Finished));
+    assert_eq!(results[0].i32(), Some(1012));
+}
+
+#[test]
+fn regular_calls_ignore_breakpoints() {
+    let (mut store, module, offsets, run) = setup();
+    store.set_breakpoint(&module, 1, offsets.i32_const).unwrap();
+    let mut results = [Value::I32(0)];
+    run.call(&mut store, &[Value::I32(5)], &mut results)
+        .unwrap();
+    assert_eq!(results[0].i32(), Some(1012));
+}
+
+#[test]
+fn invalid_breakpoints() {
+    let (mut store, module, offsets, _run) = setup();
+    assert_eq!(
+        store.set_breakpoint(&module, 2, offsets.i32_const),
+        Err(DebuggerError::UnknownFunction { func_index: 2 })
+    );
+    assert_eq!(
+        store.set_breakpoint(&module, 0, offsets.i32_const),
+        Err(DebuggerError::InvalidOffset {
+            func_index: 0,
+            offset: offsets.i32_const
+        })
+    );
+}
+
+#[test]
+fn request_pause_on_entry() {
+    let (mut store, _module, offsets, run) = setup();
+    store.request_pause();
+    let mut results = [Value::I32(0)];
+    let invocation = paused(
+        run.call_debug(&mut store, &[Value::I32(5)], &mut results)
+            .unwrap(),
+    );
+    assert_eq!(invocation.frames()[0].func_index(), 1);
+    assert_eq!(
+        invocation.frames()[0].wasm_offset(),
+        Some(offsets.i32_const)
+    );
+    let call = invocation.resume(&mut store, &mut results).unwrap();
+    assert!(matches!(call, DebugCall::Finished));
+}
@@ -0,0 +1,108 @@
+//! Tests for the disassembly of the `wasmi` bytecode of a [`Module`].
+
+use wasmi::{Config, Engine, Module};
+
+/// The Wasm module under test.
+const WAT: &str = r#"
+    (module
+        (import "env" "host" (func $host (param i32)))
+        (global $counter (mut i32) (i32.const 0))
+        (memory 1)
+        (table 1 funcref)
+        (func $add (param $lhs i32) (param $rhs i32) (result i32)
+            local.get $lhs
+            local.get $rhs
+            i32.add
+        )
+        (func (export "run") (param i32) (result i64)
+            (block $exit (result i32)
+                i32.const 1
+                local.get 0
+                br_if $exit
+                drop
+                i32.const 5
+                local.get 0
+                br_table 0 0
+            )
+            i32.const 2
+            call $add
+            call $host
+            global.get $counter
+            i32.load offset=8
+            i32.const 0
+            call_indirect (param i32)
+            i64.const 0x123456789abc
+        )
+    )
+"#;
+
+/// Returns the disassembly of the test module compiled with `config`.
+fn disassemble(config: &Config) -> (Vec<u8>, String) {
+    let wasm = wat::parse_str(WAT).unwrap();
+    let module = Module::new(&Engine::new(config), &wasm[..]).unwrap();
+    (wasm, module.disassemble())
+}
+
+/// Returns the line of the `listing` that contains `pattern`.
+fn line_of<'a>(listing: &'a str, pattern: &str) -> &'a str {
+    listing
+        .lines()
+        .find(|line| line.contains(pattern))
+        .unwrap_or_else(|| panic!("missing {pattern:?} in disassembly:\n{listing}"))
+}
+
+#[test]
+fn disassemble_funcs() {
+    let (_, listing) = disassemble(&Config::default());
+    assert!(listing.starts_with("func[1] <add>: (i32, i32) -> (i32)\n"));
+    assert!(listing.contains("\n\nfunc[2]: (i32) -> (i64)\n"));
+    assert!(line_of(&listing, "<add>").ends_with("-> (i32)"));
+    assert!(line_of(&listing, "i32_add").contains("      2  "));
+    assert!(line_of(&listing, "return drop=2 keep=1").contains("      3  "));
+}
+
+#[test]
+fn disassemble_operands() {
+    let (_, listing) = disassemble(&Config::default());
+    // Branch offsets are resolved to the index of their target instruction.
+    let br_if = line_of(&listing, "br_if_nez");
+    let target = br_if.rsplit("-> ").next().unwrap();
+    let target_line = format!("  {target:>6}  ");
+    assert!(line_of(&listing, &target_line).contains("const32 0x00000002"));
+    // Branch table targets carry their `DropKeep` in parameter words.
+    assert!(line_of(&listing, "br_table 2").ends_with("; 2 targets incl. default"));
+    assert_eq!(
+        listing
+            .lines()
+            .filter(|line| line.ends_with("  (drop=0 keep=1)"))
+            .count(),
+        2
+    );
+    assert!(listing.contains("call_internal func[1] <add>\n"));
+    assert!(listing.contains("call func[0] <host>\n"));
+    assert!(line_of(&listing, "global_get global[0]").ends_with("; <counter>"));
+    assert!(listing.contains("i32_load offset=8\n"));
+    assert!(listing.contains("call_indirect type[0]\n"));
+    assert!(listing.contains("  (table[0])\n"));
+    // Constants that do not fit into 32 bits are stored in the constant pool.
+    assert!(line_of(&listing, "const_ref const[0]").ends_with("; 0x0000123456789abc"));
+}
+
+#[test]
+fn disassemble_wasm_offsets() {
+    let (wasm, listing) = disassemble(&Config::default());
+    // The `i32.add` operator is the only `0x6A` byte of the Wasm binary after its header.
+    let i32_add = wasm.iter().skip(8).position(|byte| *byte == 0x6A).unwrap() + 8;
+    assert!(line_of(&listing, "i32_add").contains(&format!("  {i32_add:#08x}  ")));
+}
+
+#[test]
+fn disassemble_fuel() {
+    let (_, listing) = disassemble(&Config::default());
+    assert!(!listing.contains("consume_fuel"));
+    let mut config = Config::default();
+    config.consume_fuel(true);
+    let (_, listing) = disassemble(&config);
+    let mut lines = listing.lines().skip(2);
+    assert!(lines.next().unwrap().contains("consume_fuel "));
+}
@@ -0,0 +1,262 @@
+//! Tests to check if traps are symbolized via the DWARF debug information of modules.
+
+use wasmi::{core::Trap, Config, Engine, Linker, Module, Store};
+
+/// The Wasm module under test.
+///
+/// The `unreachable` operator is located via the preceding `i32.const 1000` operator.
+const WAT: &str = r#"
+    (module
+        (func (export "run")
+            i32.const 1000
+            drop
+            unreachable
+        )
+    )
+"#;
+
+/// Encodes `value` as unsigned LEB128 and appends it to `bytes`.
+fn uleb(bytes: &mut Vec<u8>, mut value: usize) {
+    loop {
+        let byte = (value & 0x7F) as u8;
+        value >>= 7;
+        if value == 0 {
+            bytes.push(byte);
+            return;
+        }
+        bytes.push(byte | 0x80);
+    }
+}
+
+/// Appends the custom section with `name` and `data` to the `wasm` binary.
+fn push_custom_section(wasm: &mut Vec<u8>, name: &str, data: &[u8]) {
+    let mut payload = Vec::new();
+    uleb(&mut payload, name.len());
+    payload.extend(name.as_bytes());
+    payload.extend(data);
+    wasm.push(0x00);
+    uleb(wasm, payload.len());
+    wasm.extend(payload);
+}
+
+/// Prefixes `body` with its 32-bit DWARF unit length.
+fn unit(body: Vec<u8>) -> Vec<u8> {
+    let mut unit = (body.len() as u32).to_le_bytes().to_vec();
+    unit.extend(body);
+    unit
+}
+
+/// Returns the range of the code section payload within the `wasm` binary.
+fn code_section(wasm: &[u8]) -> (usize, usize) {
+    let mut pos = 8;
+    loop {
+        let id = wasm[pos];
+        pos += 1;
+        let (mut len, mut shift) = (0, 0);
+        loop {
+            let byte = wasm[pos];
+            pos += 1;
+            len |= usize::from(byte & 0x7F) << shift;
+            shift += 7;
+            if byte & 0x80 == 0 {
+                break;
+            }
+        }
+        if id == 10 {
+            return (pos, len);
+        }
+        pos += len;
+    }
+}
+
+/// Returns the Wasm binary of [`WAT`] with DWARF debug information and the
+/// offset of its `unreachable` operator.
+///
+/// The DWARF describes a function `run` defined in `src/lib.rs` that inlines
+/// a function `helper` defined in `src/helper.rs` at line 42. The `unreachable`
+/// operator is attributed to line 3 of the inlined `helper` function.
+fn wasm_with_dwarf() -> (Vec<u8>, usize) {
+    let mut wasm = wat::parse_str(WAT).unwrap();
+    let unreachable = wasm
+        .windows(3)
+        .position(|window| window == [0x41, 0xE8, 0x07])
+        .unwrap()
+        + 4;
+    let (code_start, code_len) = code_section(&wasm);
+    let trap = unreachable - code_start;
+    let addr = |value: usize| (value as u32).to_le_bytes();
+
+    #[rustfmt::skip]
+    let abbrev = [
+        // compile unit: name, stmt_list, low_pc, high_pc
+        1, 0x11, 1, 0x03, 0x08, 0x10, 0x17, 0x11, 0x01, 0x12, 0x06, 0, 0,
+        // subprogram: name, low_pc, high_pc
+        2, 0x2E, 1, 0x03, 0x08, 0x11, 0x01, 0x12, 0x06, 0, 0,
+        // inlined subroutine: name, low_pc, high_pc, call_file, call_line
+        3, 0x1D, 0, 0x03, 0x08, 0x11, 0x01, 0x12, 0x06, 0x58, 0x0B, 0x59, 0x0B, 0, 0,
+        0,
+    ];
+
+    let mut info = vec![4, 0, 0, 0, 0, 0, 4];
+    info.push(1);
+    info.extend(b"src/lib.rs\0");
+    info.extend(addr(0));
+    info.extend(addr(0));
+    info.extend(addr(code_len));
+    info.push(2);
+    info.extend(b"run\0");
+    info.extend(addr(0));
+    info.extend(addr(code_len));
+    info.push(3);
+    info.extend(b"helper\0");
+    info.extend(addr(trap));
+    info.extend(addr(1));
+    info.extend([1, 42]);
+    info.extend([0, 0]);
+
+    #[rustfmt::skip]
+    let mut header = vec![
+        // minimum_instruction_length, maximum_operations_per_instruction,
+        // default_is_stmt, line_base, line_range, opcode_base
+        1, 1, 1, 0xFB, 14, 13,
+        // standard_opcode_lengths
+        0, 1, 1, 1, 1, 0, 0, 0, 1, 0, 0, 1,
+        // include_directories
+        0,
+    ];
+    header.extend(b"src/lib.rs\0\0\0\0");
+    header.extend(b"src/helper.rs\0\0\0\0");
+    header.push(0);
+    // Line 40 of `src/lib.rs` at address 0.
+    let mut program = vec![0x00, 5, 0x02];
+    program.extend(addr(0));
+    program.extend([0x03, 39, 0x01]);
+    // Line 3 of `src/helper.rs` at the `unreachable` operator.
+    program.push(0x02);
+    uleb(&mut program, trap);
+    program.extend([0x04, 2, 0x03, 0x5B, 0x01]);
+    // End of the sequence at the end of the code section.
+    program.push(0x02);
+    uleb(&mut program, code_len - trap);
+    program.extend([0x00, 1, 0x01]);
+    let mut line = vec![4, 0];
+    line.extend((header.len() as u32).to_le_bytes());
+    line.extend(header);
+    line.extend(program);
+
+    push_custom_section(&mut wasm, ".debug_abbrev", &abbrev);
+    push_custom_section(&mut wasm, ".debug_info", &unit(info));
+    push_custom_section(&mut wasm, ".debug_line", &unit(line));
+    (wasm, unreachable)
+}
+
+/// Returns the [`Trap`] of calling the exported `run` function of the `wasm` module.
+fn run_trap(config: &Config, wasm: &[u8]) -> Trap {
+    let engine = Engine::new(config);
+    let module = Module::new(&engine, wasm).unwrap();
+    let mut store = Store::new(&engine, ());
+    let instance = <Linker<()>>::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let run = instance.get_typed_func::<(), ()>(&store, "run").unwrap();
+    run.call(&mut store, ()).unwrap_err()
+}
+
+/// Returns the name, file and line of all source level `symbols`.
+fn locations(
+    symbols: &[wasmi::core::FrameSymbol],
+) -> Vec<(Option<&str>, Option<&str>, Option<u32>)> {
+    symbols
+        .iter()
+        .map(|symbol| (symbol.name(), symbol.file(), symbol.line()))
+        .collect()
+}
+
+#[test]
+fn module_symbols() {
+    let (wasm, unreachable) = wasm_with_dwarf();
+    let module = Module::new(&Engine::default(), &wasm[..]).unwrap();
+    assert_eq!(
+        locations(&module.symbols(unreachable)),
+        [
+            (Some("helper"), Some("src/helper.rs"), Some(3)),
+            (Some("run"), Some("src/lib.rs"), Some(42)),
+        ]
+    );
+    // Offsets outside of the code section are not covered by the DWARF.
+    assert!(module.symbols(0).is_empty());
+}
+
+#[test]
+fn missing_dwarf() {
+    let wasm = wat::parse_str(WAT).unwrap();
+    let module = Module::new(&Engine::default(), &wasm[..]).unwrap();
+    assert!(module.symbols(wasm.len() - 2).is_empty());
+    let trap = run_trap(&Config::default(), &wasm);
+    assert!(trap.backtrace().unwrap().frames()[0].symbols().is_empty());
+}
+
+#[test]
+fn trap_symbols() {
+    let (wasm, unreachable) = wasm_with_dwarf();
+    let trap = run_trap(&Config::default(), &wasm);
+    let frames = trap.backtrace().unwrap().frames();
+    assert_eq!(frames[0].wasm_offset(), Some(unreachable));
+    assert_eq!(
+        locations(frames[0].symbols()),
+        [
+            (Some("helper"), Some("src/helper.rs"), Some(3)),
+            (Some("run"), Some("src/lib.rs"), Some(42)),
+        ]
+    );
+    let message = trap.to_string();
+    assert!(message.contains("helper at src/helper.rs:3"));
+    assert!(message.contains("run at src/lib.rs:42"));
+}
+
+#[test]
+fn dwarf_is_retained_without_custom_sections() {
+    let (wasm, _) = wasm_with_dwarf();
+    let mut config = Config::default();
+    config.retain_custom_sections(false);
+    let trap = run_trap(&config, &wasm);
+    assert_eq!(trap.backtrace().unwrap().frames()[0].symbols().len(), 2);
+}
+
+#[test]
+fn dwarf_is_retained_with_custom_sections() {
+    let (wasm, unreachable) = wasm_with_dwarf();
+    let mut config = Config::default();
+    config.retain_custom_sections(true);
+    let module = Module::new(&Engine::new(&config), &wasm[..]).unwrap();
+    assert_eq!(module.custom_sections(".debug_info").count(), 1);
+    assert_eq!(module.symbols(unreachable).len(), 2);
+}
+
+#[test]
+fn coverage_source_lines() {
+    let (wasm, _) = wasm_with_dwarf();
+    let mut config = Config::default();
+    config.coverage(true);
+    let engine = Engine::new(&config);
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let mut store = Store::new(&engine, ());
+    let instance = <Linker<()>>::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let run = instance.get_typed_func::<(), ()>(&store, "run").unwrap();
+    run.call(&mut store, ()).unwrap_err();
+    let report = store.coverage(&module).unwrap();
+    let entry = &report.funcs()[0].blocks()[0];
+    assert_eq!((entry.file(), entry.line()), (Some("src/lib.rs"), Some(40)));
+    let lcov = report.to_lcov();
+    assert!(lcov.starts_with("TN:\nSF:src/lib.rs\nFN:40,func[0]\nFNDA:1,func[0]\n"));
+    assert!(lcov.contains("DA:40,1\n"));
+    assert!(report
+        .to_json()
+        .contains(r#""file":"src/lib.rs","line":40"#));
+}
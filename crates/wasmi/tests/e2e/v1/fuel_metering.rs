@@ -1,8 +1,19 @@
 //! Tests to check if wasmi's fuel metering works as intended.
 
 use std::fmt::Debug;
-use wasmi::{Config, Engine, Func, Linker, Module, Store};
-use wasmi_core::{Trap, TrapCode};
+use wasmi::{
+    errors::FuelCostsError,
+    Caller,
+    Config,
+    Engine,
+    FuelCosts,
+    Func,
+    HostFuel,
+    Linker,
+    Module,
+    Store,
+};
+use wasmi_core::{Trap, TrapCode, F32};
 
 /// Setup [`Engine`] and [`Store`] for fuel metering.
 fn test_setup() -> (Store<()>, Linker<()>) {
@@ -93,3 +104,145 @@ fn metered_i32_add() {
     assert_success(func.call(&mut store, (1, 2)));
     assert_eq!(store.fuel_consumed(), Some(5));
 }
+
+/// Setup [`Store`] and [`Instance`] for fuel metering with custom [`FuelCosts`].
+fn test_setup_with_costs(wasm: &[u8], costs: FuelCosts) -> (Store<()>, Func) {
+    let mut config = Config::default();
+    config.consume_fuel(true).set_fuel_costs(costs).unwrap();
+    let engine = Engine::new(&config);
+    let mut store = Store::new(&engine, ());
+    let linker = <Linker<()>>::new(&engine);
+    let module = create_module(&store, wasm);
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let func = instance.get_func(&store, "test").unwrap();
+    (store, func)
+}
+
+#[test]
+fn metered_i32_div_custom_costs() {
+    let wasm = wat2wasm(
+        r#"
+        (module
+            (func (export "test") (param $a i32) (param $b i32) (result i32)
+                (i32.div_s
+                    (local.get $a)
+                    (local.get $b)
+                )
+            )
+        )
+    "#,
+    );
+    let mut costs = FuelCosts::default();
+    costs.div = 10;
+    let (mut store, func) = test_setup_with_costs(&wasm, costs);
+    let func = func.typed::<(i32, i32), i32>(&store).unwrap();
+    store.add_fuel(13).unwrap();
+    assert_out_of_fuel(func.call(&mut store, (10, 2)));
+    assert_eq!(store.fuel_consumed(), Some(0));
+    store.add_fuel(1).unwrap();
+    assert_success(func.call(&mut store, (10, 2)));
+    assert_eq!(store.fuel_consumed(), Some(14));
+}
+
+#[test]
+fn metered_f32_add_custom_costs() {
+    let wasm = wat2wasm(
+        r#"
+        (module
+            (func (export "test") (param $a f32) (param $b f32) (result f32)
+                (f32.add
+                    (local.get $a)
+                    (local.get $b)
+                )
+            )
+        )
+    "#,
+    );
+    let mut costs = FuelCosts::default();
+    costs.float = 7;
+    let (mut store, func) = test_setup_with_costs(&wasm, costs);
+    let func = func.typed::<(F32, F32), F32>(&store).unwrap();
+    store.add_fuel(100).unwrap();
+    assert_success(func.call(&mut store, (F32::from_float(1.0), F32::from_float(2.0))));
+    assert_eq!(store.fuel_consumed(), Some(11));
+}
+
+#[test]
+fn invalid_fuel_costs() {
+    let mut config = Config::default();
+    let mut costs = FuelCosts::default();
+    costs.base = u64::MAX;
+    assert!(matches!(
+        config.set_fuel_costs(costs),
+        Err(FuelCostsError::OutOfBounds { name: "base", .. }),
+    ));
+    // The invalid fuel costs have not been applied.
+    assert_eq!(config.fuel_costs().base, FuelCosts::default().base);
+}
+
+#[test]
+fn metered_host_func_fixed_costs() {
+    let (mut store, _linker) = test_setup();
+    let func = Func::wrap_with_fuel(&mut store, 100, |value: i32| value)
+        .typed::<i32, i32>(&store)
+        .unwrap();
+    store.add_fuel(99).unwrap();
+    assert_out_of_fuel(func.call(&mut store, 1));
+    assert_eq!(store.fuel_consumed(), Some(0));
+    store.add_fuel(1).unwrap();
+    assert_eq!(func.call(&mut store, 1).unwrap(), 1);
+    assert_eq!(store.fuel_consumed(), Some(100));
+}
+
+#[test]
+fn metered_host_func_dynamic_costs() {
+    let wasm = wat2wasm(
+        r#"
+        (module
+            (import "env" "write" (func $write (param i32 i32)))
+            (func (export "test") (param $ptr i32) (param $len i32)
+                (call $write (local.get $ptr) (local.get $len))
+            )
+        )
+    "#,
+    );
+    let mut config = Config::default();
+    config.consume_fuel(true);
+    let engine = Engine::new(&config);
+    let mut store = Store::new(&engine, 0_u32);
+    let mut linker = <Linker<u32>>::new(&engine);
+    linker
+        .func_wrap_with_fuel(
+            "env",
+            "write",
+            HostFuel::dynamic(|params| u64::from(u32::from(params[1]))),
+            |mut caller: Caller<'_, u32>, _ptr: i32, _len: i32| {
+                *caller.data_mut() += 1;
+            },
+        )
+        .unwrap();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let func = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap()
+        .get_typed_func::<(i32, i32), ()>(&store, "test")
+        .unwrap();
+    store.add_fuel(10_000).unwrap();
+    func.call(&mut store, (0, 10)).unwrap();
+    let consumed_small = store.fuel_consumed().unwrap();
+    func.call(&mut store, (0, 1000)).unwrap();
+    let consumed_large = store.fuel_consumed().unwrap() - consumed_small;
+    assert_eq!(consumed_large - consumed_small, 990);
+    assert_eq!(*store.data(), 2);
+    // The host function must not be invoked if there is not enough fuel
+    // and running out of fuel must not yield a resumable call.
+    let result = func.call_resumable(&mut store, (0, 100_000));
+    assert_out_of_fuel(result.map(|_| ()));
+    assert_eq!(*store.data(), 2);
+}
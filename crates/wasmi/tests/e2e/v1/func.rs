@@ -3,7 +3,7 @@
 use core::slice;
 
 use assert_matches::assert_matches;
-use wasmi::{errors::FuncError, Engine, Error, Func, FuncType, Store, Value};
+use wasmi::{errors::FuncError, Engine, Error, ExternRef, Func, FuncType, Store, Value};
 use wasmi_core::{ValueType, F32, F64};
 
 fn test_setup() -> Store<()> {
@@ -503,3 +503,75 @@ fn static_type_check_works() {
         Err(Error::Func(FuncError::MismatchingResultType))
     );
 }
+
+#[test]
+fn static_rust_types_works() {
+    let mut store = test_setup();
+    let func = Func::wrap(
+        &mut store,
+        |flag: bool, x: f32, y: f64, object: Option<ExternRef>| (!flag, x * 2.0, y + 1.0, object),
+    );
+    assert_eq!(
+        func.ty(&store),
+        FuncType::new(
+            [
+                ValueType::I32,
+                ValueType::F32,
+                ValueType::F64,
+                ValueType::ExternRef,
+            ],
+            [
+                ValueType::I32,
+                ValueType::F32,
+                ValueType::F64,
+                ValueType::ExternRef,
+            ],
+        )
+    );
+    let typed = func
+        .typed::<(bool, f32, f64, Option<ExternRef>), (bool, f32, f64, Option<ExternRef>)>(&store)
+        .unwrap();
+    let (flag, x, y, object) = typed.call(&mut store, (true, 1.5, 2.5, None)).unwrap();
+    assert!(!flag);
+    assert_eq!(x, 3.0);
+    assert_eq!(y, 3.5);
+    assert!(object.is_none());
+    let object = ExternRef::new(&mut store, 42_i32);
+    let (_, _, _, object) = typed
+        .call(&mut store, (false, 0.0, 0.0, Some(object)))
+        .unwrap();
+    assert_eq!(
+        object.unwrap().data(&store).unwrap().downcast_ref::<i32>(),
+        Some(&42)
+    );
+    // Case: Rust types are interchangeable with their Wasm value types.
+    assert!(func
+        .typed::<(i32, F32, F64, ExternRef), (u32, F32, F64, ExternRef)>(&store)
+        .is_ok());
+}
+
+#[test]
+fn char_params_and_results() {
+    let mut store = test_setup();
+    let upper = Func::wrap(&mut store, |c: char| c.to_ascii_uppercase());
+    assert_eq!(
+        upper.ty(&store),
+        FuncType::new([ValueType::I32], [ValueType::I32])
+    );
+    let typed = upper.typed::<char, char>(&store).unwrap();
+    assert_eq!(typed.call(&mut store, 'a').unwrap(), 'A');
+    // Case: decoding a parameter that is no Unicode scalar value traps.
+    let mut results = [Value::I32(0)];
+    let error = upper
+        .call(&mut store, &[Value::I32(0xD800)], &mut results)
+        .unwrap_err();
+    assert!(error.to_string().contains("invalid Unicode scalar value"));
+    let raw = upper.typed::<u32, u32>(&store).unwrap();
+    assert!(raw.call(&mut store, 0x11_0000).is_err());
+    // Case: decoding a result that is no Unicode scalar value traps.
+    let identity = Func::wrap(&mut store, |value: u32| value);
+    let typed = identity.typed::<u32, char>(&store).unwrap();
+    assert_eq!(typed.call(&mut store, 0x41).unwrap(), 'A');
+    let error = typed.call(&mut store, 0xD800).unwrap_err();
+    assert!(error.to_string().contains("invalid Unicode scalar value"));
+}
@@ -0,0 +1,241 @@
+//! Tests to check if interrupting wasmi executions works as intended.
+
+use std::{
+    panic::{self, AssertUnwindSafe},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    thread,
+    time::Duration,
+};
+use wasmi::{Engine, Func, Linker, Module, Store};
+use wasmi_core::{Trap, TrapCode};
+
+/// Converts the `wat` string source into `wasm` encoded byte.
+fn wat2wasm(wat: &str) -> Vec<u8> {
+    wat::parse_str(wat).unwrap()
+}
+
+/// Setup [`Store`] and [`Instance`] and returns the exported `"test"` function.
+fn test_setup(wat: &str) -> (Store<()>, Func) {
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, ());
+    let linker = <Linker<()>>::new(&engine);
+    let module = Module::new(&engine, &wat2wasm(wat)[..]).unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let func = instance.get_func(&store, "test").unwrap();
+    (store, func)
+}
+
+/// Asserts that the call trapped with [`TrapCode::Interrupted`].
+fn assert_interrupted<T>(call_result: Result<T, Trap>)
+where
+    T: std::fmt::Debug,
+{
+    assert!(matches!(
+        call_result.unwrap_err().trap_code(),
+        Some(TrapCode::Interrupted),
+    ));
+}
+
+/// Repeatedly interrupts the executions of a [`Store`] from another thread.
+struct Watchdog {
+    /// Set once the watched execution has returned.
+    done: Arc<AtomicBool>,
+    /// The thread issuing the interrupt requests.
+    thread: thread::JoinHandle<()>,
+}
+
+impl Watchdog {
+    /// Interrupts the executions of the [`Store`] after a short delay until [`Watchdog::stop`].
+    ///
+    /// # Note
+    ///
+    /// Interrupt requests that race with the start of an execution are discarded
+    /// which is why the request is re-issued until the execution has returned.
+    fn new(store: &Store<()>) -> Self {
+        let handle = store.interrupt_handle();
+        let done = Arc::new(AtomicBool::new(false));
+        let thread = thread::spawn({
+            let done = done.clone();
+            move || {
+                thread::sleep(Duration::from_millis(50));
+                while !done.load(Ordering::Relaxed) {
+                    handle.interrupt();
+                    thread::sleep(Duration::from_millis(10));
+                }
+            }
+        });
+        Self { done, thread }
+    }
+
+    /// Stops interrupting the executions and waits for the watchdog thread.
+    fn stop(self) {
+        self.done.store(true, Ordering::Relaxed);
+        self.thread.join().unwrap();
+    }
+}
+
+#[test]
+fn interrupt_infinite_br_loop() {
+    let (mut store, func) = test_setup(
+        r#"
+        (module
+            (func (export "test")
+                (loop $continue
+                    (br $continue)
+                )
+            )
+        )
+    "#,
+    );
+    let func = func.typed::<(), ()>(&store).unwrap();
+    let watchdog = Watchdog::new(&store);
+    assert_interrupted(func.call(&mut store, ()));
+    watchdog.stop();
+}
+
+#[test]
+fn interrupt_infinite_br_if_loop() {
+    let (mut store, func) = test_setup(
+        r#"
+        (module
+            (func (export "test") (param $n i32) (result i32)
+                (loop $continue
+                    (local.set $n (i32.add (local.get $n) (i32.const 1)))
+                    (br_if $continue (i32.const 1))
+                )
+                (local.get $n)
+            )
+        )
+    "#,
+    );
+    let func = func.typed::<i32, i32>(&store).unwrap();
+    let watchdog = Watchdog::new(&store);
+    assert_interrupted(func.call(&mut store, 0));
+    watchdog.stop();
+}
+
+#[test]
+fn interrupt_deep_recursion() {
+    let (mut store, func) = test_setup(
+        r#"
+        (module
+            (func $f (export "test") (param $depth i32)
+                (if (i32.eqz (local.get $depth))
+                    (then (loop $continue (br $continue)))
+                    (else (call $f (i32.sub (local.get $depth) (i32.const 1))))
+                )
+            )
+        )
+    "#,
+    );
+    let func = func.typed::<i32, ()>(&store).unwrap();
+    let watchdog = Watchdog::new(&store);
+    assert_interrupted(func.call(&mut store, 1000));
+    watchdog.stop();
+}
+
+#[test]
+fn interrupt_before_call_is_discarded() {
+    let (mut store, func) = test_setup(
+        r#"
+        (module
+            (func $f (export "test") (param $n i32) (result i32)
+                (if (result i32) (i32.eqz (local.get $n))
+                    (then (i32.const 0))
+                    (else (call $f (i32.sub (local.get $n) (i32.const 1))))
+                )
+            )
+        )
+    "#,
+    );
+    let func = func.typed::<i32, i32>(&store).unwrap();
+    // No execution is in progress so the interrupt request
+    // must not affect the next unrelated execution.
+    store.interrupt_handle().interrupt();
+    assert_eq!(func.call(&mut store, 10).unwrap(), 0);
+}
+
+#[test]
+fn interrupt_from_host_func() {
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, ());
+    let mut linker = <Linker<()>>::new(&engine);
+    let handle = store.interrupt_handle();
+    linker
+        .func_wrap("env", "interrupt", move || handle.interrupt())
+        .unwrap();
+    let wasm = wat2wasm(
+        r#"
+        (module
+            (import "env" "interrupt" (func $interrupt))
+            (func (export "test")
+                (call $interrupt)
+                (loop $continue (br $continue))
+            )
+        )
+    "#,
+    );
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let func = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap()
+        .get_typed_func::<(), ()>(&store, "test")
+        .unwrap();
+    // The interrupt request is issued while the execution is in progress.
+    assert_interrupted(func.call(&mut store, ()));
+}
+
+#[test]
+fn interrupt_after_panicking_host_func_is_discarded() {
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, ());
+    let mut linker = <Linker<()>>::new(&engine);
+    linker
+        .func_wrap("env", "panic", |n: i32| {
+            if n != 0 {
+                panic!("host function panicked")
+            }
+        })
+        .unwrap();
+    let wasm = wat2wasm(
+        r#"
+        (module
+            (import "env" "panic" (func $panic (param i32)))
+            (func (export "test") (param $panic i32) (param $n i32) (result i32)
+                (call $panic (local.get $panic))
+                (block $exit
+                    (loop $continue
+                        (br_if $exit (i32.eqz (local.get $n)))
+                        (local.set $n (i32.sub (local.get $n) (i32.const 1)))
+                        (br $continue)
+                    )
+                )
+                (local.get $n)
+            )
+        )
+    "#,
+    );
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let func = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap()
+        .get_typed_func::<(i32, i32), i32>(&store, "test")
+        .unwrap();
+    let result = panic::catch_unwind(AssertUnwindSafe(|| func.call(&mut store, (1, 10))));
+    assert!(result.is_err());
+    // The unwound execution must have been left so that the next
+    // execution discards the interrupt request issued in between.
+    store.interrupt_handle().interrupt();
+    assert_eq!(func.call(&mut store, (0, 10)).unwrap(), 0);
+}
@@ -0,0 +1,213 @@
+//! Tests for typed guest pointers and memory views.
+
+use wasmi::{
+    errors::MemoryError,
+    Caller,
+    Engine,
+    Extern,
+    Instance,
+    Linker,
+    Memory,
+    MemoryValue,
+    Module,
+    Store,
+    WasmPtr,
+    WasmSlice,
+};
+use wasmi_core::Trap;
+
+/// Converts the `wat` string source into `wasm` encoded byte.
+fn wat2wasm(wat: &str) -> Vec<u8> {
+    wat::parse_str(wat).unwrap()
+}
+
+/// A struct stored as two consecutive little-endian `i32` values.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+impl MemoryValue for Point {
+    const SIZE: usize = 8;
+
+    fn from_bytes(bytes: &[u8]) -> Self {
+        Self {
+            x: i32::from_bytes(&bytes[..4]),
+            y: i32::from_bytes(&bytes[4..]),
+        }
+    }
+
+    fn to_bytes(self, bytes: &mut [u8]) {
+        self.x.to_bytes(&mut bytes[..4]);
+        self.y.to_bytes(&mut bytes[4..]);
+    }
+}
+
+/// Returns the linear memory exported as `memory` by the instance of the `caller`.
+fn caller_memory<T>(caller: &Caller<T>) -> Memory {
+    match caller.get_export("memory") {
+        Some(Extern::Memory(memory)) => memory,
+        _ => panic!("missing memory export"),
+    }
+}
+
+/// Instantiates the `wat` with host functions using typed guest pointers.
+fn setup(wat: &str) -> (Store<Vec<String>>, Instance) {
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, Vec::new());
+    let mut linker = <Linker<Vec<String>>>::new(&engine);
+    linker
+        .func_wrap(
+            "env",
+            "log",
+            |mut caller: Caller<Vec<String>>, ptr: WasmPtr<u8>, len: u32| -> Result<(), Trap> {
+                let (view, log) = caller.memory_view("memory").unwrap();
+                let message = ptr
+                    .slice(len)
+                    .read_str(&view)
+                    .map_err(|error| Trap::new(error.to_string()))?;
+                log.push(message.into());
+                Ok(())
+            },
+        )
+        .unwrap();
+    linker
+        .func_wrap(
+            "env",
+            "translate",
+            |mut caller: Caller<Vec<String>>, points: WasmPtr<Point>, len: u32, dx: i32| {
+                let memory = caller_memory(&caller);
+                let mut view = memory.view(&mut caller);
+                let points = points.slice(len);
+                for index in 0..len {
+                    let mut point = points.read(&view, index).unwrap();
+                    point.x += dx;
+                    points.write(&mut view, index, point).unwrap();
+                }
+            },
+        )
+        .unwrap();
+    let module = Module::new(store.engine(), &wat2wasm(wat)[..]).unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    (store, instance)
+}
+
+const WAT: &str = r#"
+    (module
+        (import "env" "log" (func $log (param i32 i32)))
+        (import "env" "translate" (func $translate (param i32 i32 i32)))
+        (memory (export "memory") 1)
+        (data (i32.const 0) "hello")
+        (data (i32.const 16) "\01\00\00\00\02\00\00\00\03\00\00\00\04\00\00\00")
+        (func (export "log") (param i32 i32)
+            (call $log (local.get 0) (local.get 1))
+        )
+        (func (export "translate") (param i32 i32 i32)
+            (call $translate (local.get 0) (local.get 1) (local.get 2))
+        )
+    )
+"#;
+
+#[test]
+fn host_reads_str() {
+    let (mut store, instance) = setup(WAT);
+    let log = instance
+        .get_typed_func::<(i32, i32), ()>(&store, "log")
+        .unwrap();
+    log.call(&mut store, (0, 5)).unwrap();
+    assert_eq!(store.data(), &["hello"]);
+    let error = log.call(&mut store, (65535, 2)).unwrap_err();
+    assert!(error.to_string().contains("out of bounds"));
+}
+
+#[test]
+fn host_writes_structs() {
+    let (mut store, instance) = setup(WAT);
+    let translate = instance
+        .get_typed_func::<(i32, i32, i32), ()>(&store, "translate")
+        .unwrap();
+    translate.call(&mut store, (16, 2, 10)).unwrap();
+    let memory = instance.get_memory(&store, "memory").unwrap();
+    let view = memory.view(&mut store);
+    let points = WasmSlice::new(WasmPtr::<Point>::new(16), 2);
+    assert_eq!(
+        points.read_to_vec(&view).unwrap(),
+        [Point { x: 11, y: 2 }, Point { x: 13, y: 4 }]
+    );
+}
+
+#[test]
+fn typed_accesses() {
+    let (mut store, instance) = setup(WAT);
+    let memory = instance.get_memory(&store, "memory").unwrap();
+    let mut view = memory.view(&mut store);
+    let ptr = WasmPtr::<u32>::new(100);
+    ptr.write(&mut view, 0xDEAD_BEEF).unwrap();
+    assert_eq!(view.data()[100..104], [0xEF, 0xBE, 0xAD, 0xDE]);
+    assert_eq!(ptr.read(&view).unwrap(), 0xDEAD_BEEF);
+    assert_eq!(
+        ptr.cast::<[u16; 2]>().read(&view).unwrap(),
+        [0xBEEF, 0xDEAD]
+    );
+    assert_eq!(ptr.advance(2), Some(WasmPtr::new(108)));
+    let ptrs = WasmPtr::<WasmPtr<u32>>::new(200).slice(2);
+    ptrs.write_slice(&mut view, &[ptr, WasmPtr::new(0)])
+        .unwrap();
+    assert_eq!(ptrs.read(&view, 0).unwrap(), ptr);
+    assert!(ptrs.read(&view, 1).unwrap().is_null());
+    assert!(matches!(
+        ptrs.read(&view, 2),
+        Err(MemoryError::OutOfBoundsAccess)
+    ));
+    let bytes = WasmPtr::<u8>::new(0).slice(5);
+    bytes.bytes_mut(&mut view).unwrap()[0] = 0xFF;
+    assert!(matches!(
+        bytes.read_str(&view),
+        Err(MemoryError::InvalidUtf8)
+    ));
+    let last = WasmPtr::<u64>::new(view.len() as u32 - 4);
+    assert!(matches!(
+        last.read(&view),
+        Err(MemoryError::OutOfBoundsAccess)
+    ));
+    assert!(matches!(
+        view.write(usize::MAX, &[0x00]),
+        Err(MemoryError::OutOfBoundsAccess)
+    ));
+}
+
+/// A value without any representation in linear memory.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+struct Empty;
+
+impl MemoryValue for Empty {
+    const SIZE: usize = 0;
+
+    fn from_bytes(bytes: &[u8]) -> Self {
+        assert!(bytes.is_empty());
+        Self
+    }
+
+    fn to_bytes(self, bytes: &mut [u8]) {
+        assert!(bytes.is_empty());
+    }
+}
+
+#[test]
+fn zero_sized_accesses() {
+    let (mut store, instance) = setup(WAT);
+    let memory = instance.get_memory(&store, "memory").unwrap();
+    let mut view = memory.view(&mut store);
+    let empties = WasmPtr::<Empty>::new(16).slice(3);
+    assert_eq!(empties.read_to_vec(&view).unwrap(), [Empty; 3]);
+    empties.write_slice(&mut view, &[Empty; 3]).unwrap();
+    let arrays = WasmPtr::<[Empty; 2]>::new(16).slice(2);
+    assert_eq!(arrays.read_to_vec(&view).unwrap(), [[Empty; 2]; 2]);
+    arrays.write_slice(&mut view, &[[Empty; 2]; 2]).unwrap();
+    assert_eq!(view.data()[16..20], [0x01, 0x00, 0x00, 0x00]);
+}
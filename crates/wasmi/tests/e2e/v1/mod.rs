@@ -1,6 +1,26 @@
+mod backtrace;
+mod call_hook;
+mod compilation_fuel;
+mod coverage;
+mod custom_sections;
+#[cfg(feature = "debugger")]
+mod debugger;
+mod disassemble;
+#[cfg(feature = "dwarf")]
+mod dwarf;
 mod fuel_consumption_mode;
 mod fuel_metering;
 mod func;
 mod host_calls_wasm;
+mod interrupt;
+mod memory_view;
+mod names;
+#[cfg(feature = "profiler")]
+mod profiler;
 mod resource_limiter;
 mod resumable_call;
+#[cfg(feature = "trace")]
+mod trace;
+mod typed_handles;
+#[cfg(feature = "debugger")]
+mod watchpoints;
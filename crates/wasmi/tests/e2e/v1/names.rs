@@ -0,0 +1,113 @@
+//! Tests to check if the `name` custom section is parsed and used.
+
+use wasmi::{core::Trap, Engine, Func, Linker, Module, Store};
+
+/// The Wasm module under test.
+///
+/// The `wat` crate emits the `name` custom section for all `$` identifiers.
+const WAT: &str = r#"
+    (module $named
+        (type $unit (func))
+        (import "env" "host" (func $host))
+        (memory $mem 1)
+        (global $counter (mut i32) (i32.const 0))
+        (table $funcs 1 funcref)
+        (func $inner (export "exported_inner") (param $lhs i32) (param $rhs i32) (local $tmp i32)
+            block $exit
+                unreachable
+            end
+        )
+        (func (export "run")
+            i32.const 1
+            i32.const 2
+            call $inner
+        )
+    )
+"#;
+
+/// Parses the test module.
+fn module(engine: &Engine) -> Module {
+    let wasm = wat::parse_str(WAT).unwrap();
+    Module::new(engine, &wasm[..]).unwrap()
+}
+
+#[test]
+fn module_names() {
+    let module = module(&Engine::default());
+    assert_eq!(module.name(), Some("named"));
+    assert_eq!(module.func_name(0), Some("host"));
+    assert_eq!(module.func_name(1), Some("inner"));
+    assert_eq!(module.func_name(2), None);
+    assert_eq!(module.local_name(1, 0), Some("lhs"));
+    assert_eq!(module.local_name(1, 1), Some("rhs"));
+    assert_eq!(module.local_name(1, 2), Some("tmp"));
+    assert_eq!(module.local_name(1, 3), None);
+    let names = module.names();
+    assert_eq!(names.func_type(0), Some("unit"));
+    assert_eq!(names.memory(0), Some("mem"));
+    assert_eq!(names.global(0), Some("counter"));
+    assert_eq!(names.table(0), Some("funcs"));
+    assert_eq!(names.label(1, 0), Some("exit"));
+}
+
+#[test]
+fn missing_names() {
+    let wasm = wat::parse_str("(module (func (export \"f\")))").unwrap();
+    let module = Module::new(&Engine::default(), &wasm[..]).unwrap();
+    assert_eq!(module.name(), None);
+    assert_eq!(module.func_name(0), None);
+    assert_eq!(module.local_name(0, 0), None);
+}
+
+#[test]
+fn malformed_names_are_ignored() {
+    let mut wasm = wat::parse_str("(module (func))").unwrap();
+    // A `name` custom section with a truncated function name subsection.
+    wasm.extend([0x00, 0x08, 0x04, b'n', b'a', b'm', b'e', 0x01, 0x05, 0x01]);
+    let module = Module::new(&Engine::default(), &wasm[..]).unwrap();
+    assert_eq!(module.func_name(0), None);
+}
+
+#[test]
+fn names_in_backtrace() {
+    let engine = Engine::default();
+    let module = module(&engine);
+    let mut store = Store::new(&engine, ());
+    let mut linker = <Linker<()>>::new(&engine);
+    linker.func_wrap("env", "host", || {}).unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let run = instance.get_typed_func::<(), ()>(&store, "run").unwrap();
+    let trap: Trap = run.call(&mut store, ()).unwrap_err();
+    let frames = trap.backtrace().unwrap().frames();
+    assert_eq!(frames[0].module_name(), Some("named"));
+    assert_eq!(frames[0].func_name(), Some("inner"));
+    // The name section takes precedence but export names are used as fallback.
+    assert_eq!(frames[1].func_name(), Some("run"));
+    assert!(trap.to_string().contains("named!inner"));
+}
+
+#[test]
+fn func_names() {
+    let engine = Engine::default();
+    let module = module(&engine);
+    let mut store = Store::new(&engine, ());
+    let mut linker = <Linker<()>>::new(&engine);
+    linker.func_wrap("env", "host", || {}).unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    // The name section takes precedence but export names are used as fallback.
+    let inner = instance.get_func(&store, "exported_inner").unwrap();
+    assert_eq!(inner.name(&store), Some("inner"));
+    let run = instance.get_func(&store, "run").unwrap();
+    assert_eq!(run.name(&store), Some("run"));
+    // Host functions have no name.
+    let host = Func::wrap(&mut store, || {});
+    assert_eq!(host.name(&store), None);
+}
@@ -0,0 +1,157 @@
+//! Tests for the sampling profiler of Wasm executions of a [`Store`].
+
+use std::{thread, time::Duration};
+use wasmi::{Engine, FuncProfile, Linker, Module, Profile, Store};
+
+/// The Wasm module under test.
+const WAT: &str = r#"
+    (module $app
+        (func $leaf (param i32) (result i32)
+            (i32.mul (local.get 0) (i32.const 2))
+        )
+        (func $middle (param i32) (result i32)
+            (call $leaf (call $leaf (local.get 0)))
+        )
+        (func $fac (param i32) (result i32)
+            (if (result i32) (i32.eqz (local.get 0))
+                (then (i32.const 1))
+                (else
+                    (i32.mul
+                        (local.get 0)
+                        (call $fac (i32.sub (local.get 0) (i32.const 1)))
+                    )
+                )
+            )
+        )
+        (func (export "run") (param i32) (result i32)
+            (i32.add (call $middle (local.get 0)) (call $fac (i32.const 5)))
+        )
+    )
+"#;
+
+/// Calls the exported `run` function of the test module while profiling with `interval`.
+fn profile(interval: u64) -> Profile {
+    let engine = Engine::default();
+    let wasm = wat::parse_str(WAT).unwrap();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let mut store = Store::new(&engine, ());
+    let instance = <Linker<()>>::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let run = instance.get_typed_func::<i32, i32>(&store, "run").unwrap();
+    store.start_profiling(interval);
+    assert_eq!(run.call(&mut store, 3).unwrap(), 12 + 120);
+    store.stop_profiling().unwrap()
+}
+
+/// Returns the costs of the function named `name` in the `funcs` of a [`Profile`].
+fn func<'a>(funcs: &'a [FuncProfile<'a>], name: &str) -> &'a FuncProfile<'a> {
+    funcs
+        .iter()
+        .find(|func| func.func_name() == Some(name))
+        .unwrap_or_else(|| panic!("missing profile for {name:?}"))
+}
+
+#[test]
+fn profile_is_none_without_profiling() {
+    let mut store = Store::new(&Engine::default(), ());
+    assert!(store.stop_profiling().is_none());
+    store.start_profiling(1);
+    assert!(store.stop_profiling().is_some());
+    assert!(store.stop_profiling().is_none());
+}
+
+#[test]
+fn profile_costs() {
+    let profile = profile(1);
+    let funcs = profile.funcs();
+    assert_eq!(funcs.len(), 4);
+    let (run, middle, leaf, fac) = (
+        func(&funcs, "run"),
+        func(&funcs, "middle"),
+        func(&funcs, "leaf"),
+        func(&funcs, "fac"),
+    );
+    assert_eq!(run.inclusive(), profile.total());
+    assert_eq!(leaf.inclusive(), leaf.exclusive());
+    assert_eq!(middle.inclusive(), middle.exclusive() + leaf.inclusive());
+    assert_eq!(
+        run.inclusive(),
+        run.exclusive() + middle.inclusive() + fac.inclusive()
+    );
+    // The costs of recursive calls are accounted for only once.
+    assert_eq!(fac.inclusive(), fac.exclusive());
+    assert_eq!(
+        funcs.iter().map(FuncProfile::exclusive).sum::<u64>(),
+        profile.total()
+    );
+    assert!(funcs
+        .windows(2)
+        .all(|pair| pair[0].exclusive() >= pair[1].exclusive()));
+    assert_eq!(run.module_name(), Some("app"));
+    assert_eq!(leaf.func_index(), 0);
+}
+
+#[test]
+fn profile_folded() {
+    let profile = profile(1);
+    let folded = profile.to_folded();
+    let costs = |stack: &str| -> u64 {
+        folded
+            .lines()
+            .find_map(|line| line.strip_prefix(stack)?.strip_prefix(' ')?.parse().ok())
+            .unwrap_or_else(|| panic!("missing stack {stack:?} in:\n{folded}"))
+    };
+    let leaf = costs("app!run;app!middle;app!leaf");
+    assert_eq!(leaf, func(&profile.funcs(), "leaf").exclusive());
+    // Every call stack is sampled at least once with an interval of `1`.
+    costs("app!run");
+    costs("app!run;app!middle");
+    costs("app!run;app!fac;app!fac;app!fac");
+    let total = folded
+        .lines()
+        .map(|line| line.rsplit(' ').next().unwrap().parse::<u64>().unwrap())
+        .sum::<u64>();
+    assert_eq!(total, profile.total());
+}
+
+#[test]
+fn profile_host_calls() {
+    /// The time spent in the host function per call.
+    const SLEEP: Duration = Duration::from_millis(10);
+    let engine = Engine::default();
+    let wasm = wat::parse_str(
+        r#"
+        (module $app
+            (import "env" "sleep" (func $sleep))
+            (func $caller (call $sleep))
+            (func (export "run") (call $caller) (call $caller))
+        )
+    "#,
+    )
+    .unwrap();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let mut store = Store::new(&engine, ());
+    let mut linker = <Linker<()>>::new(&engine);
+    linker
+        .func_wrap("env", "sleep", || thread::sleep(SLEEP))
+        .unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let run = instance.get_typed_func::<(), ()>(&store, "run").unwrap();
+    store.start_profiling(1000);
+    run.call(&mut store, ()).unwrap();
+    let profile = store.stop_profiling().unwrap();
+    assert_eq!(profile.interval(), 1000);
+    let funcs = profile.funcs();
+    // The time spent in host functions is attributed to their Wasm callers.
+    let caller = func(&funcs, "caller");
+    assert!(caller.exclusive() >= 2 * SLEEP.as_nanos() as u64);
+    assert_eq!(funcs[0].func_name(), Some("caller"));
+    assert_eq!(func(&funcs, "run").inclusive(), profile.total());
+}
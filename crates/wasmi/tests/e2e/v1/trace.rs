@@ -0,0 +1,151 @@
+//! Tests to check if instruction-level execution tracing works as intended.
+
+use std::sync::{Arc, Mutex};
+use wasmi::{core::UntypedValue, Engine, Linker, Module, Store};
+
+/// Converts the `wat` string source into `wasm` encoded byte.
+fn wat2wasm(wat: &str) -> Vec<u8> {
+    wat::parse_str(wat).unwrap()
+}
+
+/// A traced instruction with its location and the live values on the stack.
+#[derive(Debug, Clone)]
+struct Traced {
+    /// The `Debug` output of the traced instruction.
+    instr: String,
+    /// The index of the executed function.
+    func_index: Option<u32>,
+    /// The offset of the instruction within its function.
+    offset: Option<usize>,
+    /// The Wasm bytecode offset of the instruction.
+    wasm_offset: Option<usize>,
+    /// The live values on the value stack.
+    stack: Vec<UntypedValue>,
+}
+
+impl Traced {
+    /// Returns `true` if the traced instruction is of the `name` kind.
+    fn is(&self, name: &str) -> bool {
+        self.instr.starts_with(name)
+    }
+}
+
+/// Calls the exported `"test"` function of the `wat` module with `(a, b)` and returns all traces.
+fn run_traced(wat: &str, a: i32, b: i32) -> (i32, Vec<Traced>) {
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, ());
+    let linker = <Linker<()>>::new(&engine);
+    let module = Module::new(&engine, &wat2wasm(wat)[..]).unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let func = instance
+        .get_typed_func::<(i32, i32), i32>(&store, "test")
+        .unwrap();
+    let traces = Arc::new(Mutex::new(Vec::new()));
+    let sink = traces.clone();
+    store.set_trace_hook(move |event| {
+        sink.lock().unwrap().push(Traced {
+            instr: format!("{:?}", event.instr()),
+            func_index: event.func_index(),
+            offset: event.offset(),
+            wasm_offset: event.wasm_offset(),
+            stack: event.stack().to_vec(),
+        });
+    });
+    let result = func.call(&mut store, (a, b)).unwrap();
+    let traces = traces.lock().unwrap().clone();
+    (result, traces)
+}
+
+#[test]
+fn trace_i32_add() {
+    let (result, traces) = run_traced(
+        r#"
+        (module
+            (func (export "test") (param $a i32) (param $b i32) (result i32)
+                (i32.add (local.get $a) (local.get $b))
+            )
+        )
+    "#,
+        1,
+        2,
+    );
+    assert_eq!(result, 3);
+    // The offsets of a straight-line function are consecutive from the start.
+    for (n, traced) in traces.iter().enumerate() {
+        assert_eq!(traced.func_index, Some(0));
+        assert_eq!(traced.offset, Some(n));
+    }
+    let add = traces
+        .iter()
+        .find(|traced| traced.is("I32Add"))
+        .expect("must have traced the `i32.add` instruction");
+    let top2: Vec<i32> = add.stack[add.stack.len() - 2..]
+        .iter()
+        .copied()
+        .map(i32::from)
+        .collect();
+    assert_eq!(top2, [1, 2]);
+    assert!(add.wasm_offset.is_some());
+    assert!(traces.last().unwrap().is("Return"));
+}
+
+#[test]
+fn trace_nested_call() {
+    let (result, traces) = run_traced(
+        r#"
+        (module
+            (func $sub (param $a i32) (param $b i32) (result i32)
+                (i32.sub (local.get $a) (local.get $b))
+            )
+            (func (export "test") (param $a i32) (param $b i32) (result i32)
+                (call $sub (local.get $a) (local.get $b))
+            )
+        )
+    "#,
+        5,
+        3,
+    );
+    assert_eq!(result, 2);
+    // Instructions of the callee restart at offset 0.
+    let call = traces
+        .iter()
+        .position(|traced| traced.is("CallInternal"))
+        .expect("must have traced the internal call");
+    assert_eq!(traces[call].func_index, Some(1));
+    assert_eq!(traces[call + 1].func_index, Some(0));
+    assert_eq!(traces[call + 1].offset, Some(0));
+    let sub = traces
+        .iter()
+        .find(|traced| traced.is("I32Sub"))
+        .expect("must have traced the `i32.sub` instruction");
+    assert_eq!(sub.func_index, Some(0));
+    // The callee is defined before its caller in the Wasm code section.
+    assert!(sub.wasm_offset.unwrap() < traces[call].wasm_offset.unwrap());
+}
+
+#[test]
+fn trace_hook_removed() {
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, ());
+    store.set_trace_hook(|_| panic!("trace hook must not be called"));
+    store.remove_trace_hook();
+    let module = Module::new(
+        &engine,
+        &wat2wasm(r#"(module (func (export "test") (param i32 i32) (result i32) (local.get 0)))"#)
+            [..],
+    )
+    .unwrap();
+    let instance = <Linker<()>>::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let func = instance
+        .get_typed_func::<(i32, i32), i32>(&store, "test")
+        .unwrap();
+    assert_eq!(func.call(&mut store, (7, 0)).unwrap(), 7);
+}
@@ -0,0 +1,131 @@
+//! Tests for typed global variable and table handles.
+
+use wasmi::{
+    errors::{GlobalError, TableError},
+    Engine,
+    ExternRef,
+    FuncRef,
+    Instance,
+    Linker,
+    Module,
+    Store,
+    Table,
+    TableType,
+};
+use wasmi_core::{TrapCode, ValueType};
+
+/// Converts the `wat` string source into `wasm` encoded byte.
+fn wat2wasm(wat: &str) -> Vec<u8> {
+    wat::parse_str(wat).unwrap()
+}
+
+const WAT: &str = r#"
+    (module
+        (global $counter (export "counter") (mut i32) (i32.const 0))
+        (global (export "pi") f64 (f64.const 2.5))
+        (table (export "funcs") 1 3 funcref)
+        (elem (i32.const 0) $inc)
+        (func $inc (export "inc")
+            (global.set $counter (i32.add (global.get $counter) (i32.const 1)))
+        )
+    )
+"#;
+
+/// Instantiates [`WAT`].
+fn setup() -> (Store<()>, Instance) {
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, ());
+    let linker = <Linker<()>>::new(&engine);
+    let module = Module::new(store.engine(), &wat2wasm(WAT)[..]).unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    (store, instance)
+}
+
+#[test]
+fn typed_global() {
+    let (mut store, instance) = setup();
+    let counter = instance
+        .get_global(&store, "counter")
+        .unwrap()
+        .typed::<u32>(&store)
+        .unwrap();
+    let inc = instance.get_typed_func::<(), ()>(&store, "inc").unwrap();
+    assert_eq!(counter.get(&store), 0);
+    inc.call(&mut store, ()).unwrap();
+    assert_eq!(counter.get(&store), 1);
+    counter.set(&mut store, 41).unwrap();
+    inc.call(&mut store, ()).unwrap();
+    assert_eq!(counter.get(&store), 42);
+    assert_eq!(counter.global().get(&store).i32(), Some(42));
+    let pi = instance.get_global(&store, "pi").unwrap();
+    assert!(matches!(
+        pi.typed::<i64>(&store),
+        Err(GlobalError::TypeMismatch {
+            expected: ValueType::F64,
+            encountered: ValueType::I64,
+        })
+    ));
+    let pi = pi.typed::<wasmi_core::F64>(&store).unwrap();
+    assert_eq!(pi.get(&store).to_float(), 2.5);
+    assert!(matches!(
+        pi.set(&mut store, 1.0.into()),
+        Err(GlobalError::ImmutableWrite)
+    ));
+}
+
+#[test]
+fn typed_funcref_table() {
+    let (mut store, instance) = setup();
+    let table = instance.get_table(&store, "funcs").unwrap();
+    assert!(matches!(
+        table.typed::<ExternRef>(&store),
+        Err(TableError::ElementTypeMismatch {
+            expected: ValueType::FuncRef,
+            actual: ValueType::ExternRef,
+        })
+    ));
+    let table = table.typed::<FuncRef>(&store).unwrap();
+    assert_eq!(table.size(&store), 1);
+    let inc = table.get(&store, 0).unwrap();
+    assert!(table.get(&store, 1).is_none());
+    assert_eq!(table.grow(&mut store, 2, inc).unwrap(), 1);
+    assert!(matches!(
+        table.grow(&mut store, 1, FuncRef::null()),
+        Err(TableError::GrowOutOfBounds { .. })
+    ));
+    table.set(&mut store, 1, FuncRef::null()).unwrap();
+    assert!(table.get(&store, 1).unwrap().is_null());
+    assert!(matches!(
+        table.set(&mut store, 3, FuncRef::null()),
+        Err(TableError::AccessOutOfBounds { .. })
+    ));
+    table.fill(&mut store, 0, FuncRef::null(), 3).unwrap();
+    assert!((0..3).all(|index| table.get(&store, index).unwrap().is_null()));
+    assert!(matches!(
+        table.fill(&mut store, 2, inc, 2),
+        Err(TrapCode::TableOutOfBounds)
+    ));
+}
+
+#[test]
+fn typed_externref_table() {
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, ());
+    let table = Table::new(
+        &mut store,
+        TableType::new(ValueType::ExternRef, 2, None),
+        ExternRef::null().into(),
+    )
+    .unwrap()
+    .typed::<ExternRef>(&store)
+    .unwrap();
+    let object = ExternRef::new(&mut store, 42_i32);
+    table.set(&mut store, 1, object).unwrap();
+    assert!(table.get(&store, 0).unwrap().is_null());
+    let data = table.get(&store, 1).unwrap();
+    assert_eq!(data.data(&store).unwrap().downcast_ref::<i32>(), Some(&42));
+}
@@ -0,0 +1,224 @@
+//! Tests for watchpoints on address ranges of linear memories.
+
+use std::{
+    ops::Range,
+    sync::{Arc, Mutex},
+};
+use wasmi::{
+    DebugCall,
+    Engine,
+    Instance,
+    Linker,
+    Memory,
+    Module,
+    Store,
+    Value,
+    WatchKind,
+    Watchpoint,
+};
+
+/// The Wasm module under test.
+const WAT: &str = r#"
+    (module
+        (memory (export "memory") 1)
+        (data $data "\01\02\03\04")
+        (func (export "load") (param i32) (result i32)
+            (i32.load offset=4 (local.get 0))
+        )
+        (func (export "store") (param i32 i32)
+            (i32.store8 (local.get 0) (local.get 1))
+        )
+        (func (export "fill") (param i32 i32 i32)
+            (memory.fill (local.get 0) (local.get 1) (local.get 2))
+        )
+        (func (export "copy") (param i32 i32 i32)
+            (memory.copy (local.get 0) (local.get 1) (local.get 2))
+        )
+        (func (export "init") (param i32)
+            (memory.init $data (local.get 0) (i32.const 0) (i32.const 4))
+        )
+    )
+"#;
+
+/// The accesses reported to the watchpoint hook.
+type Hits = Arc<Mutex<Vec<(Watchpoint, WatchKind, Range<usize>)>>>;
+
+/// Instantiates the test module and records all watchpoint hits reported to the hook.
+fn setup() -> (Store<()>, Instance, Memory, Hits) {
+    let engine = Engine::default();
+    let wasm = wat::parse_str(WAT).unwrap();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let mut store = Store::new(&engine, ());
+    let instance = <Linker<()>>::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let memory = instance.get_memory(&store, "memory").unwrap();
+    let hits = Hits::default();
+    let recorded = hits.clone();
+    store.set_watchpoint_hook(move |hit| {
+        recorded
+            .lock()
+            .unwrap()
+            .push((hit.watchpoint(), hit.access(), hit.range()));
+    });
+    (store, instance, memory, hits)
+}
+
+/// Calls the exported function `name` of `instance` with `inputs` and returns its results.
+fn call(store: &mut Store<()>, instance: &Instance, name: &str, inputs: &[i32]) -> Vec<Value> {
+    let func = instance.get_func(&*store, name).unwrap();
+    let inputs = inputs.iter().copied().map(Value::I32).collect::<Vec<_>>();
+    let mut outputs = vec![Value::I32(0); func.ty(&*store).results().len()];
+    func.call(&mut *store, &inputs, &mut outputs).unwrap();
+    outputs
+}
+
+/// Returns all hits recorded so far and clears them.
+fn take(hits: &Hits) -> Vec<(Watchpoint, WatchKind, Range<usize>)> {
+    core::mem::take(&mut *hits.lock().unwrap())
+}
+
+#[test]
+fn watch_loads_and_stores() {
+    let (mut store, instance, memory, hits) = setup();
+    let wp = memory.add_watchpoint(&mut store, 100..104, WatchKind::ReadWrite);
+    call(&mut store, &instance, "store", &[103, 42]);
+    call(&mut store, &instance, "store", &[104, 42]);
+    let result = call(&mut store, &instance, "load", &[98]);
+    assert_eq!(result[0].i32(), Some((42 << 8) | (42 << 16)));
+    call(&mut store, &instance, "load", &[0]);
+    assert_eq!(
+        take(&hits),
+        [
+            (wp, WatchKind::Write, 103..104),
+            (wp, WatchKind::Read, 102..106),
+        ]
+    );
+}
+
+#[test]
+fn watch_kinds() {
+    let (mut store, instance, memory, hits) = setup();
+    let read = memory.add_watchpoint(&mut store, 0..8, WatchKind::Read);
+    let write = memory.add_watchpoint(&mut store, 16..24, WatchKind::Write);
+    call(&mut store, &instance, "store", &[0, 1]);
+    call(&mut store, &instance, "load", &[12]);
+    call(&mut store, &instance, "store", &[20, 1]);
+    call(&mut store, &instance, "load", &[0]);
+    assert_eq!(
+        take(&hits),
+        [
+            (write, WatchKind::Write, 20..21),
+            (read, WatchKind::Read, 4..8)
+        ]
+    );
+}
+
+#[test]
+fn watch_bulk_memory() {
+    let (mut store, instance, memory, hits) = setup();
+    let wp = memory.add_watchpoint(&mut store, 200..210, WatchKind::ReadWrite);
+    call(&mut store, &instance, "fill", &[190, 7, 11]);
+    call(&mut store, &instance, "fill", &[190, 7, 10]);
+    call(&mut store, &instance, "fill", &[205, 7, 0]);
+    call(&mut store, &instance, "copy", &[0, 209, 4]);
+    call(&mut store, &instance, "copy", &[208, 0, 4]);
+    call(&mut store, &instance, "init", &[198]);
+    assert_eq!(
+        take(&hits),
+        [
+            (wp, WatchKind::Write, 190..201),
+            (wp, WatchKind::Read, 209..213),
+            (wp, WatchKind::Write, 208..212),
+            (wp, WatchKind::Write, 198..202),
+        ]
+    );
+}
+
+#[test]
+fn watch_out_of_bounds_traps_silently() {
+    let (mut store, instance, memory, hits) = setup();
+    memory.add_watchpoint(&mut store, 0..usize::MAX, WatchKind::ReadWrite);
+    let store_func = instance
+        .get_typed_func::<(i32, i32), ()>(&store, "store")
+        .unwrap();
+    assert!(store_func.call(&mut store, (65536, 1)).is_err());
+    assert!(take(&hits).is_empty());
+}
+
+#[test]
+fn remove_watchpoint() {
+    let (mut store, instance, memory, hits) = setup();
+    let wp = memory.add_watchpoint(&mut store, 0..4, WatchKind::Write);
+    call(&mut store, &instance, "store", &[0, 1]);
+    assert_eq!(take(&hits).len(), 1);
+    assert!(memory.remove_watchpoint(&mut store, wp));
+    assert!(!memory.remove_watchpoint(&mut store, wp));
+    call(&mut store, &instance, "store", &[0, 1]);
+    assert!(take(&hits).is_empty());
+    let other = memory.add_watchpoint(&mut store, 0..4, WatchKind::Write);
+    assert_ne!(wp, other);
+    store.remove_watchpoint_hook();
+    call(&mut store, &instance, "store", &[0, 1]);
+    assert!(take(&hits).is_empty());
+}
+
+#[test]
+fn watch_pauses_debugged_execution() {
+    let (mut store, instance, memory, hits) = setup();
+    let wp = memory.add_watchpoint(&mut store, 50..51, WatchKind::Write);
+    let fill = instance.get_func(&store, "fill").unwrap();
+    let inputs = [Value::I32(48), Value::I32(9), Value::I32(4)];
+    let paused = match fill.call_debug(&mut store, &inputs, &mut []).unwrap() {
+        DebugCall::Paused(invocation) => invocation,
+        DebugCall::Finished => panic!("expected the call to be paused"),
+    };
+    let hit = paused.watchpoint_hit().unwrap();
+    assert_eq!(hit.watchpoint(), wp);
+    assert_eq!(hit.access(), WatchKind::Write);
+    assert_eq!(hit.range(), 48..52);
+    // The execution pauses before the access is performed.
+    assert_eq!(memory.data(&store)[50], 0);
+    assert_eq!(take(&hits).len(), 1);
+    let call = paused.resume(&mut store, &mut []).unwrap();
+    assert!(matches!(call, DebugCall::Finished));
+    assert_eq!(memory.data(&store)[48..52], [9; 4]);
+    assert!(take(&hits).is_empty());
+}
+
+#[test]
+fn watch_rechecks_paused_access_after_watchpoints_changed() {
+    let (mut store, instance, memory, hits) = setup();
+    let wp = memory.add_watchpoint(&mut store, 0..1, WatchKind::Write);
+    let func = instance.get_func(&store, "store").unwrap();
+    let inputs = [Value::I32(0), Value::I32(1)];
+    let paused = match func.call_debug(&mut store, &inputs, &mut []).unwrap() {
+        DebugCall::Paused(invocation) => invocation,
+        DebugCall::Finished => panic!("expected the call to be paused"),
+    };
+    assert_eq!(paused.watchpoint_hit().unwrap().watchpoint(), wp);
+    assert!(memory.remove_watchpoint(&mut store, wp));
+    let other = memory.add_watchpoint(&mut store, 0..1, WatchKind::Write);
+    let paused = match paused.resume(&mut store, &mut []).unwrap() {
+        DebugCall::Paused(invocation) => invocation,
+        DebugCall::Finished => panic!("expected the call to be paused again"),
+    };
+    assert_eq!(paused.watchpoint_hit().unwrap().watchpoint(), other);
+    let outcome = paused.resume(&mut store, &mut []).unwrap();
+    assert!(matches!(outcome, DebugCall::Finished));
+    assert_eq!(memory.data(&store)[0], 1);
+    assert_eq!(take(&hits).len(), 2);
+    // Removing the watchpoint while paused does not swallow later accesses.
+    let paused = match func.call_debug(&mut store, &inputs, &mut []).unwrap() {
+        DebugCall::Paused(invocation) => invocation,
+        DebugCall::Finished => panic!("expected the call to be paused"),
+    };
+    assert!(memory.remove_watchpoint(&mut store, other));
+    let outcome = paused.resume(&mut store, &mut []).unwrap();
+    assert!(matches!(outcome, DebugCall::Finished));
+    memory.add_watchpoint(&mut store, 0..1, WatchKind::Write);
+    call(&mut store, &instance, "store", &[0, 2]);
+    assert_eq!(take(&hits).len(), 2);
+}